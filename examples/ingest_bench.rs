@@ -0,0 +1,247 @@
+//! Feeds synthetic span batches to the trace ingestor at a given rate, and
+//! reports how long each batch takes to show up in the published snapshot as
+//! the graph grows. With snapshots sharing their structure with the graph, the
+//! latency depends on the batch size rather than on the spans kept.
+//!
+//! With `--children-first`, the spans of a trace are sent leaves first, so
+//! that they wait in the orphanage until their parent arrives. A batch then
+//! shows up once its last span is either in the graph or waiting there.
+//!
+//! ```shell
+//! cargo run --release --example ingest_bench -- --rate 20000 --max-spans 1000000
+//! ```
+
+use amaru_doctor::otel::{TraceRetention, id::SpanId, ingestor::TraceIngestor, span_ext::SpanExt};
+use anyhow::Result;
+use clap::Parser;
+use opentelemetry_proto::tonic::trace::v1::Span;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::{MissedTickBehavior, interval, sleep_until};
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Spans sent per second
+    #[arg(long, default_value_t = 10_000)]
+    rate: u64,
+
+    /// Spans per batch
+    #[arg(long, default_value_t = 200)]
+    batch_size: usize,
+
+    /// Spans per trace, a root and its descendants
+    #[arg(long, default_value_t = 50)]
+    trace_size: usize,
+
+    /// How long to send spans for, in seconds
+    #[arg(long, default_value_t = 30)]
+    duration: u64,
+
+    /// The spans kept at most, past which the oldest traces and orphans are evicted
+    #[arg(long, default_value_t = 1_000_000)]
+    max_spans: usize,
+
+    /// The batches queued at most before sending waits
+    #[arg(long, default_value_t = 10_000)]
+    queue_capacity: usize,
+
+    /// Send the spans of a trace children before parents
+    #[arg(long)]
+    children_first: bool,
+}
+
+/// The last span of each batch sent, its parent, and when, oldest first.
+type Pending = Arc<Mutex<VecDeque<(SpanId, Option<SpanId>, Instant)>>>;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let ingestor = TraceIngestor::new(
+        args.queue_capacity,
+        TraceRetention {
+            expire_after: Duration::from_secs(3600),
+            max_spans: args.max_spans,
+        },
+    );
+    let snapshot = ingestor.snapshot();
+    let orphans = ingestor.orphans();
+    let pending: Pending = Arc::default();
+
+    let start = Instant::now();
+    let producer = tokio::spawn(produce(args, ingestor, pending.clone(), start));
+
+    println!("elapsed  spans kept  spans/s   p50 (ms)  p99 (ms)  max (ms)");
+    let mut latencies = Vec::new();
+    let mut all_latencies = Vec::new();
+    let mut sent = 0;
+    let mut next_report = start + Duration::from_secs(1);
+    // Batches are processed in order, so the oldest pending one shows up first
+    let mut poll = interval(Duration::from_millis(1));
+    poll.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    loop {
+        poll.tick().await;
+        let graph = snapshot.load();
+        let orphanage = orphans.load();
+        let now = Instant::now();
+        {
+            let mut pending = pending.lock().unwrap();
+            while let Some((span_id, parent_id, sent_at)) = pending.front() {
+                let waiting =
+                    parent_id.is_some_and(|parent_id| orphanage.is_waiting(&parent_id, span_id));
+                if !graph.spans.contains_key(span_id) && !waiting {
+                    break;
+                }
+                latencies.push(now - *sent_at);
+                pending.pop_front();
+            }
+        }
+
+        if now >= next_report {
+            sent += latencies.len();
+            println!(
+                "{:>6}s  {:>10}  {:>6}  {}",
+                (now - start).as_secs(),
+                graph.spans.len(),
+                latencies.len() * args.batch_size,
+                format_percentiles(&mut latencies),
+            );
+            all_latencies.append(&mut latencies);
+            next_report += Duration::from_secs(1);
+        }
+
+        if producer.is_finished() && pending.lock().unwrap().is_empty() {
+            break;
+        }
+    }
+    producer.await??;
+
+    sent += latencies.len();
+    all_latencies.append(&mut latencies);
+    let elapsed = start.elapsed().as_secs_f64();
+    println!(
+        "\n{} spans in {:.1}s ({:.0} spans/s for {} requested), latency {}",
+        sent * args.batch_size,
+        elapsed,
+        (sent * args.batch_size) as f64 / elapsed,
+        args.rate,
+        format_percentiles(&mut all_latencies),
+    );
+    Ok(())
+}
+
+/// Sends batches at the requested rate, for the requested duration. Sending
+/// waits when the queue is full, in which case the rate isn't kept up.
+async fn produce(
+    args: Args,
+    ingestor: TraceIngestor,
+    pending: Pending,
+    start: Instant,
+) -> Result<()> {
+    let mut generator = SpanGenerator::new(args.trace_size, args.children_first);
+    let batch_interval = Duration::from_secs_f64(args.batch_size as f64 / args.rate as f64);
+    let end = start + Duration::from_secs(args.duration);
+    let mut deadline = start;
+    while deadline < end {
+        sleep_until(deadline.into()).await;
+        let batch: Vec<Span> = (0..args.batch_size)
+            .map(|_| generator.next_span())
+            .collect();
+        let last = batch.last().map(|span| (span.span_id(), span.parent_id()));
+        let sent_at = Instant::now();
+        ingestor.ingest(batch).await?;
+        if let Some((span_id, parent_id)) = last {
+            pending
+                .lock()
+                .unwrap()
+                .push_back((span_id, parent_id, sent_at));
+        }
+        deadline += batch_interval;
+    }
+    Ok(())
+}
+
+/// Generates traces one after the other, each a root and its descendants as a
+/// binary tree, parents first unless `children_first`.
+struct SpanGenerator {
+    trace_size: usize,
+    children_first: bool,
+    trace_id: u64,
+    span_id: u64,
+    /// The spans of the current trace left to send.
+    trace: VecDeque<Span>,
+}
+
+impl SpanGenerator {
+    fn new(trace_size: usize, children_first: bool) -> Self {
+        Self {
+            trace_size: trace_size.max(1),
+            children_first,
+            trace_id: 0,
+            span_id: 0,
+            trace: VecDeque::new(),
+        }
+    }
+
+    fn next_span(&mut self) -> Span {
+        if self.trace.is_empty() {
+            self.trace = self.next_trace();
+        }
+        self.trace.pop_front().unwrap()
+    }
+
+    fn next_trace(&mut self) -> VecDeque<Span> {
+        self.trace_id += 1;
+        let trace_start = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        let first_id = self.span_id + 1;
+        self.span_id += self.trace_size as u64;
+
+        // The parent of the span at index i is at index (i - 1) / 2, so
+        // children always come after their parent
+        let mut trace: VecDeque<Span> = (0..self.trace_size)
+            .map(|index| {
+                let parent = index.checked_sub(1).map(|i| first_id + (i / 2) as u64);
+                let start = trace_start + index as u64 * 1_000;
+                Span {
+                    trace_id: [[0; 8], self.trace_id.to_be_bytes()].concat(),
+                    span_id: (first_id + index as u64).to_be_bytes().to_vec(),
+                    parent_span_id: parent
+                        .map(|id| id.to_be_bytes().to_vec())
+                        .unwrap_or_default(),
+                    name: if parent.is_none() {
+                        "validate_block".to_string()
+                    } else {
+                        format!("step{}", index % 8)
+                    },
+                    start_time_unix_nano: start,
+                    end_time_unix_nano: start + 1_000_000,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        if self.children_first {
+            trace.make_contiguous().reverse();
+        }
+        trace
+    }
+}
+
+/// Formats the p50, p99 and max of latencies, sorting them.
+fn format_percentiles(latencies: &mut [Duration]) -> String {
+    if latencies.is_empty() {
+        return format!("{:>8}  {:>8}  {:>8}", "-", "-", "-");
+    }
+    latencies.sort_unstable();
+    let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100].as_secs_f64() * 1_000.0;
+    format!(
+        "{:>8.2}  {:>8.2}  {:>8.2}",
+        percentile(50),
+        percentile(99),
+        percentile(100)
+    )
+}
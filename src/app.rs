@@ -2,7 +2,7 @@ use crate::{
     components::{Component, root::RootComponent},
     config::Config,
     model::button::InputEvent,
-    otel::TraceGraphSnapshot,
+    otel::service::OtelCollectorHandle,
     states::Action,
     tui::{Event, Tui},
 };
@@ -11,7 +11,10 @@ use anyhow::Result;
 use crossterm::event::{Event as CrosstermEvent, KeyEvent};
 use ratatui::prelude::{Backend, Rect};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, mpsc};
+use std::{
+    path::PathBuf,
+    sync::{Arc, mpsc},
+};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
 use tracing::debug;
 
@@ -37,8 +40,9 @@ pub enum Mode {
 impl App {
     pub fn new(
         ledger_db: ReadOnlyRocksDB,
+        ledger_path: PathBuf,
         chain_db: ReadOnlyChainDB,
-        trace_graph: TraceGraphSnapshot,
+        collector: OtelCollectorHandle,
         button_events: mpsc::Receiver<InputEvent>,
         frame_area: Rect,
     ) -> Result<Self> {
@@ -54,7 +58,12 @@ impl App {
             last_tick_key_events: Vec::new(),
             action_tx,
             action_rx,
-            root: RootComponent::new(Arc::new(ledger_db), Arc::new(chain_db), trace_graph),
+            root: RootComponent::new(
+                Arc::new(ledger_db),
+                ledger_path,
+                Arc::new(chain_db),
+                collector,
+            ),
         })
     }
 
@@ -1,21 +1,187 @@
 use std::path::PathBuf;
 
 use amaru_kernel::network::NetworkName;
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 use crate::config::{get_config_dir, get_data_dir};
 
 #[derive(Parser, Debug)]
 #[command(author, version = version(), about)]
 pub struct Cli {
-    #[arg(short, long, env = "AMARU_NETWORK", default_value = "preprod")]
+    #[arg(
+        short,
+        long,
+        env = "AMARU_NETWORK",
+        default_value = "preprod",
+        global = true
+    )]
     pub network: NetworkName,
 
-    #[arg(short, long, value_name = "FLOAT", env = "AMARU_LEDGER_DB")]
+    #[arg(
+        short,
+        long,
+        value_name = "FLOAT",
+        env = "AMARU_LEDGER_DB",
+        global = true
+    )]
     pub ledger_db: Option<PathBuf>,
 
-    #[arg(short, long, value_name = "FLOAT", env = "AMARU_CHAIN_DB")]
+    #[arg(
+        short,
+        long,
+        value_name = "FLOAT",
+        env = "AMARU_CHAIN_DB",
+        global = true
+    )]
     pub chain_db: Option<PathBuf>,
+
+    /// Records the traces received from amaru to a file in the data dir.
+    #[arg(long, conflicts_with = "replay")]
+    pub record: bool,
+
+    /// Displays the traces of a recording instead of receiving them from
+    /// amaru.
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub collector: CollectorArgs,
+
+    /// Runs a headless command instead of launching the TUI.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Overrides of the `collector` section of the config.
+#[derive(Args, Debug, Default)]
+pub struct CollectorArgs {
+    /// Binds the OTEL receivers to 127.0.0.1 only.
+    #[arg(
+        long,
+        overrides_with = "no_localhost_only",
+        env = "AMARU_DOCTOR_LOCALHOST_ONLY"
+    )]
+    pub localhost_only: bool,
+
+    /// Binds the OTEL receivers to all interfaces, even if the config says otherwise.
+    #[arg(long, overrides_with = "localhost_only")]
+    pub no_localhost_only: bool,
+
+    /// Starts the OTLP/gRPC receiver, even if the config disables it.
+    #[arg(long, overrides_with = "no_grpc")]
+    pub grpc: bool,
+
+    /// Doesn't start the OTLP/gRPC receiver.
+    #[arg(long, overrides_with = "grpc", env = "AMARU_DOCTOR_NO_GRPC")]
+    pub no_grpc: bool,
+
+    /// Starts the OTLP/HTTP receiver, even if the config disables it.
+    #[arg(long, overrides_with = "no_http")]
+    pub http: bool,
+
+    /// Doesn't start the OTLP/HTTP receiver.
+    #[arg(long, overrides_with = "http", env = "AMARU_DOCTOR_NO_HTTP")]
+    pub no_http: bool,
+
+    /// Port of the OTLP/gRPC receiver [default: 4317].
+    #[arg(long, value_name = "PORT", env = "AMARU_DOCTOR_GRPC_PORT")]
+    pub grpc_port: Option<u16>,
+
+    /// Port of the OTLP/HTTP receiver [default: 4318].
+    #[arg(long, value_name = "PORT", env = "AMARU_DOCTOR_HTTP_PORT")]
+    pub http_port: Option<u16>,
+
+    /// How long traces are kept, in seconds [default: 600].
+    #[arg(long, value_name = "SECS", env = "AMARU_DOCTOR_TRACE_RETENTION")]
+    pub trace_retention_secs: Option<u64>,
+
+    /// How many span batches can wait to be processed [default: 10000].
+    #[arg(long, value_name = "COUNT", env = "AMARU_DOCTOR_TRACE_QUEUE_CAPACITY")]
+    pub trace_queue_capacity: Option<usize>,
+
+    /// The oldest traces and orphans are evicted past this many spans [default: 1000000].
+    #[arg(long, value_name = "COUNT", env = "AMARU_DOCTOR_MAX_SPANS")]
+    pub max_spans: Option<usize>,
+
+    /// The oldest log records are dropped past this many [default: 10000].
+    #[arg(long, value_name = "COUNT", env = "AMARU_DOCTOR_MAX_LOGS")]
+    pub max_logs: Option<usize>,
+}
+
+impl CollectorArgs {
+    /// `--localhost-only` or `--no-localhost-only`, `None` when neither is set.
+    pub fn localhost_only(&self) -> Option<bool> {
+        flag(self.localhost_only, self.no_localhost_only)
+    }
+
+    /// `--grpc` or `--no-grpc`, `None` when neither is set.
+    pub fn grpc(&self) -> Option<bool> {
+        flag(self.grpc, self.no_grpc)
+    }
+
+    /// `--http` or `--no-http`, `None` when neither is set.
+    pub fn http(&self) -> Option<bool> {
+        flag(self.http, self.no_http)
+    }
+}
+
+/// Folds a `--flag` / `--no-flag` pair into an override of the config value.
+fn flag(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Dumps ledger entities to stdout as JSON lines.
+    Ledger {
+        #[arg(value_enum)]
+        entity: LedgerEntity,
+
+        /// Stops after this many entries.
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Builds the on-disk UTxO index used to search UTxOs by address or
+    /// credential, for the ledger's current tip.
+    UtxoIndex,
+    /// Dumps the differences between two epoch snapshots of the ledger as
+    /// JSON lines, UTxOs being summarized in a last line.
+    LedgerDiff {
+        /// Epoch of the snapshot to compare from.
+        from: u64,
+
+        /// Epoch of the snapshot to compare to.
+        to: u64,
+
+        /// Stops after this many entries.
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Converts the traces of a recording (see `--record`) to the Chrome
+    /// Trace Event format, for Perfetto or chrome://tracing, on stdout.
+    ChromeTrace {
+        /// The recording to convert.
+        recording: PathBuf,
+
+        /// Only converts the traces matching this filter, as entered in the
+        /// Otel page search bar.
+        #[arg(long)]
+        filter: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LedgerEntity {
+    Accounts,
+    BlockIssuers,
+    Dreps,
+    Pools,
+    Proposals,
+    Utxos,
 }
 
 const VERSION_MESSAGE: &str = concat!(
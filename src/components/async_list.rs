@@ -40,6 +40,11 @@ where
         }
     }
 
+    /// Whether the provider is still sending items.
+    pub fn is_loading(&self) -> bool {
+        self.is_loading
+    }
+
     /// Polls the provider for new data without blocking.
     /// This should be called on every application tick via the parent component.
     pub fn poll_data(&mut self) {
@@ -108,6 +113,10 @@ where
         self.buffer.get(self.view.selected_index())
     }
 
+    fn items(&self) -> &[T] {
+        &self.buffer
+    }
+
     fn select_index_by_row(&mut self, relative_row: usize) {
         self.view
             .select_index_by_row(relative_row, self.buffer.len());
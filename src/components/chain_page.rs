@@ -1,12 +1,17 @@
 use crate::{
     components::{
-        Component, ComponentLayout, chain_search::ChainSearchComponent,
+        Component, ComponentLayout,
+        chain_search::ChainSearchComponent,
+        list::{ListComponent, ListModel},
         search_bar::SearchBarComponent,
     },
     controller::{LayoutSpec, MoveFocus, find_next_focus, walk_layout},
-    states::{Action, ComponentId},
+    export::{ExportScope, export},
+    model::chain_walk::ChainWalkModel,
+    states::{Action, ComponentId, ExportFormat},
 };
 use amaru_stores::rocksdb::consensus::ReadOnlyChainDB;
+use anyhow::{Result, anyhow};
 use crossterm::event::Event;
 use either::Either::{Left, Right};
 use ratatui::{
@@ -16,12 +21,14 @@ use ratatui::{
 use std::{
     any::Any,
     collections::HashMap,
+    path::PathBuf,
     sync::{Arc, RwLock},
 };
 
 pub struct ChainPageComponent {
     id: ComponentId,
     pub search_bar: SearchBarComponent,
+    pub headers: ListComponent<ChainWalkModel>,
     pub chain_search: ChainSearchComponent,
     last_layout: RwLock<ComponentLayout>,
     active_focus: RwLock<ComponentId>,
@@ -29,10 +36,20 @@ pub struct ChainPageComponent {
 
 impl ChainPageComponent {
     pub fn new(chain_db: Arc<ReadOnlyChainDB>) -> Self {
+        let headers = ListComponent::new(
+            ComponentId::ChainHeadersList,
+            ChainWalkModel::from_tip(chain_db.clone()),
+        );
+        let mut chain_search = ChainSearchComponent::new(ComponentId::ChainSearch, chain_db);
+        if let Some(tip) = headers.model.selected_hash() {
+            chain_search.show(tip);
+        }
+
         Self {
             id: ComponentId::ChainPage,
             search_bar: SearchBarComponent::new(ComponentId::SearchBar),
-            chain_search: ChainSearchComponent::new(ComponentId::ChainSearch, chain_db),
+            headers,
+            chain_search,
             last_layout: RwLock::new(HashMap::new()),
             active_focus: RwLock::new(ComponentId::SearchBar),
         }
@@ -49,7 +66,19 @@ impl ChainPageComponent {
                         constraints: vec![(Constraint::Fill(1), Left(ComponentId::SearchBar))],
                     }),
                 ),
-                (Constraint::Fill(1), Left(ComponentId::ChainSearch)),
+                (
+                    Constraint::Fill(1),
+                    Right(LayoutSpec {
+                        direction: Direction::Horizontal,
+                        constraints: vec![
+                            (
+                                Constraint::Percentage(20),
+                                Left(ComponentId::ChainHeadersList),
+                            ),
+                            (Constraint::Fill(1), Left(ComponentId::ChainSearch)),
+                        ],
+                    }),
+                ),
             ],
         };
 
@@ -60,6 +89,38 @@ impl ChainPageComponent {
 
     pub fn handle_search(&mut self, query: &str) {
         self.chain_search.handle_search(query);
+        self.anchor_headers();
+    }
+
+    /// Anchors the headers list at the header a search resolved to, for the
+    /// chain around it to be browsed from there.
+    fn anchor_headers(&mut self) {
+        if let Some(header) = self.chain_search.take_resolved() {
+            self.headers.model.anchor_at(header);
+        }
+    }
+
+    /// Searches as if `query` was entered in the search bar, and focuses the
+    /// result.
+    pub fn search(&mut self, query: &str) {
+        self.search_bar.set_input(query);
+        self.handle_search(query);
+        *self.active_focus.write().unwrap() = ComponentId::ChainSearch;
+    }
+
+    /// Exports from the headers list or the search details, whichever has
+    /// the focus.
+    pub fn export(&self, scope: ExportScope, format: ExportFormat) -> Result<PathBuf> {
+        match *self.active_focus.read().unwrap() {
+            ComponentId::ChainHeadersList => {
+                let model = &self.headers.model;
+                export("headers", scope, format, model.selected_item(), || {
+                    model.walk()
+                })
+            }
+            ComponentId::ChainSearch => self.chain_search.export(scope, format),
+            _ => Err(anyhow!("Nothing to export")),
+        }
     }
 
     pub fn handle_navigation(&mut self, direction: MoveFocus) -> Vec<Action> {
@@ -91,6 +152,11 @@ impl ChainPageComponent {
             self.search_bar.render_focused(frame, *rect, is_focused);
         }
 
+        if let Some(rect) = my_layout.get(&ComponentId::ChainHeadersList) {
+            let is_focused = current_focus == ComponentId::ChainHeadersList;
+            self.headers.render_focused(frame, *rect, is_focused);
+        }
+
         if let Some(rect) = my_layout.get(&ComponentId::ChainSearch) {
             // Check if ChainSearch is the active one
             let is_focused = current_focus == ComponentId::ChainSearch;
@@ -123,6 +189,15 @@ impl Component for ChainPageComponent {
                 let mut acts = Vec::new();
                 if target_id == ComponentId::SearchBar {
                     acts.extend(self.search_bar.handle_event(ev, child_area));
+                } else if target_id == ComponentId::ChainHeadersList {
+                    let old_selection = self.headers.model.selected_hash();
+                    acts.extend(self.headers.handle_event(ev, child_area));
+                    let new_selection = self.headers.model.selected_hash();
+                    if new_selection != old_selection
+                        && let Some(hash) = new_selection
+                    {
+                        self.chain_search.show(hash);
+                    }
                 } else if target_id == ComponentId::ChainSearch {
                     acts.extend(self.chain_search.handle_event(ev, child_area));
                 }
@@ -141,4 +216,19 @@ impl Component for ChainPageComponent {
         *self.active_focus.write().unwrap() = active_focus;
         actions
     }
+
+    fn tick(&mut self) -> Vec<Action> {
+        if let Some(area) = self
+            .last_layout
+            .read()
+            .unwrap()
+            .get(&ComponentId::ChainHeadersList)
+        {
+            self.headers.model.set_height(area.height as usize);
+        }
+        let actions = self.chain_search.tick();
+        // A slot or height search waiting on the index may have resolved
+        self.anchor_headers();
+        actions
+    }
 }
@@ -1,26 +1,47 @@
 use crate::{
-    components::Component,
-    model::search::SearchCache,
-    states::{Action, ComponentId},
+    components::{Component, list::ListComponent},
+    export::{ExportScope, export, export_json},
+    model::{
+        block_body::{BlockBody, BlockTransaction},
+        chain_index::{ChainIndex, ChainQuery, Lookup},
+        search::SearchCache,
+    },
+    states::{Action, ComponentId, ExportFormat},
     view::item_details::draw_details,
+    viewmodel::dynamic_list::DynamicListViewModel,
 };
 use amaru_consensus::{BlockHeader, Nonces, ReadOnlyChainStore};
 use amaru_kernel::{Hash, RawBlock};
 use amaru_stores::rocksdb::consensus::ReadOnlyChainDB;
-use crossterm::event::{Event, MouseButton, MouseEventKind};
+use anyhow::{Result, anyhow};
+use crossterm::event::{Event, KeyCode, MouseButton, MouseEventKind};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::Paragraph,
 };
-use std::{any::Any, str::FromStr, sync::Arc};
+use std::{any::Any, path::PathBuf, slice, str::FromStr, sync::Arc};
 use tracing::warn;
 
-pub type ChainResult = (BlockHeader, RawBlock, Nonces);
+/// The block body is `None` when the raw block couldn't be decoded, in which
+/// case the raw bytes are shown instead.
+pub type ChainResult = (BlockHeader, RawBlock, Nonces, Option<BlockBody>);
 
 pub struct ChainSearchComponent {
     id: ComponentId,
     db: Arc<ReadOnlyChainDB>,
     state: SearchCache<Hash<32>, ChainResult>,
+    index: ChainIndex,
+    /// A slot or height query waiting on the index to be built far enough.
+    pending: Option<ChainQuery>,
+    /// An inline message about the last search, shown above the details.
+    status: Option<String>,
+    /// The header the last search resolved to, for the page to anchor the
+    /// headers list at.
+    resolved: Option<BlockHeader>,
+    /// The transactions of the displayed block.
+    transactions: ListComponent<DynamicListViewModel<BlockTransaction>>,
     focused_column: Option<usize>,
 }
 
@@ -28,13 +49,32 @@ impl ChainSearchComponent {
     pub fn new(id: ComponentId, db: Arc<ReadOnlyChainDB>) -> Self {
         Self {
             id,
+            index: ChainIndex::new(db.clone()),
             db,
             state: SearchCache::default(),
+            pending: None,
+            status: None,
+            resolved: None,
+            transactions: ListComponent::new(
+                ComponentId::ChainSearchTransactions,
+                DynamicListViewModel::new("Transactions"),
+            ),
             focused_column: None,
         }
     }
 
+    /// Splits off a one line strip for the status message, if there is one.
+    fn split_status(&self, area: Rect) -> (Option<Rect>, Rect) {
+        if self.status.is_none() {
+            return (None, area);
+        }
+        let [status, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+        (Some(status), body)
+    }
+
     fn get_layout_chunks(&self, area: Rect) -> std::rc::Rc<[Rect]> {
+        let (_, area) = self.split_status(area);
         Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -45,16 +85,77 @@ impl ChainSearchComponent {
             .split(area)
     }
 
+    /// Splits the block column into the transaction list and its details.
+    fn get_block_chunks(&self, area: Rect) -> [Rect; 2] {
+        Layout::vertical([Constraint::Percentage(30), Constraint::Fill(1)]).areas(area)
+    }
+
+    fn sync_transactions(&mut self) {
+        let transactions = self
+            .state
+            .get_current_res()
+            .and_then(|r| r.3.as_ref())
+            .map(|body| body.transactions.clone())
+            .unwrap_or_default();
+        self.transactions.model.set_items(transactions);
+    }
+
     pub fn handle_search(&mut self, query_str: &str) {
-        let Ok(hash) = Hash::<32>::from_str(query_str) else {
-            warn!("Invalid hash format: {}", query_str);
-            return;
-        };
+        self.pending = None;
+        match ChainQuery::from_str(query_str) {
+            Ok(query) => self.resolve(query),
+            Err(e) => {
+                warn!("{}", e);
+                self.status = Some(e);
+            }
+        }
+    }
 
+    fn resolve(&mut self, query: ChainQuery) {
+        match self.index.resolve(&query) {
+            Lookup::Found(hash) => {
+                self.status = None;
+                if self.load(hash) {
+                    self.resolved = self.state.get_current_res().map(|r| r.0.clone());
+                } else {
+                    self.status = Some(format!("Block {} not found", query));
+                }
+            }
+            Lookup::NotFound => {
+                self.status = Some(format!("Block {} not found", query));
+            }
+            Lookup::Pending => {
+                self.status = Some(format!(
+                    "Indexing chain for {} ({} headers so far)...",
+                    query,
+                    self.index.len()
+                ));
+                self.pending = Some(query);
+            }
+        }
+    }
+
+    /// Takes the header the last search resolved to, once.
+    pub fn take_resolved(&mut self) -> Option<BlockHeader> {
+        self.resolved.take()
+    }
+
+    /// Shows a header picked outside of the search bar, dropping any search
+    /// status.
+    pub fn show(&mut self, hash: Hash<32>) {
+        self.pending = None;
+        self.status = None;
+        self.load(hash);
+    }
+
+    /// Loads the header, block and nonces for a hash into the details panes.
+    /// Returns whether all three were found.
+    fn load(&mut self, hash: Hash<32>) -> bool {
         // Check Cache
         if self.state.results.contains_key(&hash) {
             self.state.parsed = Some(hash);
-            return;
+            self.sync_transactions();
+            return true;
         }
 
         let header_opt = self.db.load_header(&hash);
@@ -62,17 +163,59 @@ impl ChainSearchComponent {
         let nonces_opt = ReadOnlyChainStore::<BlockHeader>::get_nonces(&*self.db, &hash);
 
         if let (Some(header), Ok(block), Some(nonces)) = (header_opt, block_res, nonces_opt) {
-            self.state.cache_result(hash, (header, block, nonces));
+            let body = BlockBody::try_from(&block)
+                .inspect_err(|e| warn!("Block {}: {}", hash, e))
+                .ok();
+            self.state.cache_result(hash, (header, block, nonces, body));
+            self.sync_transactions();
+            return true;
+        }
+        false
+    }
+
+    /// Exports from the focused column: as JSON, the transactions of the
+    /// block when it could be decoded, or else the displayed header or block.
+    /// As CBOR, the block is always exported as stored, its transactions
+    /// having no bytes of their own once decoded.
+    pub fn export(&self, scope: ExportScope, format: ExportFormat) -> Result<PathBuf> {
+        let result = self
+            .state
+            .get_current_res()
+            .ok_or_else(|| anyhow!("No block displayed"))?;
+        match self.focused_column {
+            Some(1) if result.3.is_some() && format == ExportFormat::Json => {
+                let model = &self.transactions.model;
+                export_json("transactions", scope, model.selected_item(), || {
+                    model.items()
+                })
+            }
+            Some(1) => export("block", scope, format, Some(&result.1), || {
+                slice::from_ref(&result.1)
+            }),
+            Some(2) => Err(anyhow!("Nonces can't be exported")),
+            _ => export("header", scope, format, Some(&result.0), || {
+                slice::from_ref(&result.0)
+            }),
         }
     }
 
     pub fn render_focused(&self, f: &mut Frame, area: Rect, is_focused: bool) {
+        if let (Some(rect), Some(status)) = (self.split_status(area).0, &self.status) {
+            let style = if self.pending.is_some() {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Red)
+            };
+            f.render_widget(Paragraph::new(status.as_str()).style(style), rect);
+        }
+
         let chunks = self.get_layout_chunks(area);
 
         let result = self.state.get_current_res();
         let header = result.map(|r| &r.0);
         let block = result.map(|r| &r.1);
         let nonces = result.map(|r| &r.2);
+        let body = result.and_then(|r| r.3.as_ref());
 
         // Highlight specific columns if globally focused (and specific column selected)
         let f0 = is_focused && self.focused_column == Some(0);
@@ -80,7 +223,19 @@ impl ChainSearchComponent {
         let f2 = is_focused && self.focused_column == Some(2);
 
         draw_details(f, chunks[0], "Header Details".to_string(), header, f0);
-        draw_details(f, chunks[1], "Block Details".to_string(), block, f1);
+        if body.is_some() {
+            let [list_area, details_area] = self.get_block_chunks(chunks[1]);
+            self.transactions.render_focused(f, list_area, f1);
+            draw_details(
+                f,
+                details_area,
+                "Transaction Details".to_string(),
+                self.transactions.model.selected_item(),
+                f1,
+            );
+        } else {
+            draw_details(f, chunks[1], "Block Details".to_string(), block, f1);
+        }
         draw_details(f, chunks[2], "Nonces Details".to_string(), nonces, f2);
     }
 }
@@ -96,6 +251,13 @@ impl Component for ChainSearchComponent {
         self
     }
 
+    fn tick(&mut self) -> Vec<Action> {
+        if let Some(query) = self.pending.take() {
+            self.resolve(query);
+        }
+        Vec::new()
+    }
+
     fn handle_event(&mut self, event: &Event, area: Rect) -> Vec<Action> {
         if let Event::Mouse(mouse) = event
             && (mouse.kind == MouseEventKind::Moved
@@ -114,6 +276,23 @@ impl Component for ChainSearchComponent {
                 }
             }
         }
+
+        // The transaction list reacts to the usual list keys and mouse
+        // gestures while the block column is focused
+        if self.focused_column == Some(1) {
+            let [list_area, _] = self.get_block_chunks(self.get_layout_chunks(area)[1]);
+            self.transactions
+                .model
+                .set_height(list_area.height as usize);
+            let in_list = match event {
+                Event::Key(key) => matches!(key.code, KeyCode::Up | KeyCode::Down),
+                Event::Mouse(mouse) => list_area.contains((mouse.column, mouse.row).into()),
+                _ => false,
+            };
+            if in_list {
+                self.transactions.handle_event(event, list_area);
+            }
+        }
         Vec::new()
     }
 }
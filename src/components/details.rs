@@ -38,8 +38,19 @@ where
     }
 
     pub fn render_with_data(&self, f: &mut Frame, area: Rect, is_focused: bool, item: Option<&T>) {
+        self.render_with_view(f, area, is_focused, item);
+    }
+
+    /// Renders another view of the item, e.g. one side of a diff.
+    pub fn render_with_view<V: ToRichText>(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+        view: Option<&V>,
+    ) {
         self.is_focused.store(is_focused, Ordering::Relaxed);
-        draw_details(f, area, self.title.to_string(), item, is_focused);
+        draw_details(f, area, self.title.to_string(), view, is_focused);
     }
 
     fn perform_scroll(&mut self, direction: ScrollDirection) {
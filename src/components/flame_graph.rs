@@ -3,6 +3,7 @@ use crate::{
     model::otel_view::OtelViewState,
     otel::{
         TreeBounds,
+        critical_path::critical_path,
         graph::TraceGraph,
         id::{SpanId, TraceId},
         span_ext::SpanExt,
@@ -35,9 +36,21 @@ impl FlameGraphComponent {
         state: &OtelViewState,
         is_focused: bool,
     ) {
-        let mut block = Block::default()
-            .title("Trace Details")
-            .borders(Borders::ALL);
+        let mut hints = Vec::new();
+        let errors = count_errors(state);
+        if errors > 0 {
+            hints.push(format!("{} failed, e to cycle", errors));
+        }
+        hints.push(
+            if state.show_critical_path {
+                "critical path, c to hide"
+            } else {
+                "c for critical path"
+            }
+            .to_string(),
+        );
+        let title = format!("Trace Details ({})", hints.join(", "));
+        let mut block = Block::default().title(title).borders(Borders::ALL);
 
         if is_focused {
             block = block
@@ -72,6 +85,19 @@ impl Component for FlameGraphComponent {
     }
 }
 
+/// Counts the failed spans of the selected trace.
+fn count_errors(state: &OtelViewState) -> usize {
+    let Some(trace_id) = &state.selected_trace_id else {
+        return 0;
+    };
+    let graph = state.trace_graph.load();
+    graph
+        .trace_iter(trace_id)
+        .filter_map(|id| graph.spans.get(&id))
+        .filter(|span| span.is_error())
+        .count()
+}
+
 /// Determines which view to render based on the provided view state.
 fn get_flame_graph_lines(
     state: &OtelViewState,
@@ -160,7 +186,13 @@ fn get_lines(
     ancestors: impl Iterator<Item = SpanId>,
     descendants: impl Iterator<Item = SpanId>,
 ) -> Result<Vec<Line<'static>>> {
-    let renderer = SpanBarRenderer::new(graph, state, bounds, max_bar_width)?;
+    let critical_path = state
+        .selected_trace_id
+        .as_ref()
+        .filter(|_| state.show_critical_path)
+        .map(|trace_id| critical_path(graph, trace_id));
+    let focused_span = state.focused_span.as_ref().map(|span| span.span_id());
+    let renderer = SpanBarRenderer::new(graph, focused_span, bounds, max_bar_width, critical_path)?;
 
     let tagged_ancestors = ancestors.map(|id| (id, true));
     let tagged_descendants = descendants.map(|id| (id, false));
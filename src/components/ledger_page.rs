@@ -4,28 +4,62 @@ use crate::{
         search_bar::SearchBarComponent, search_list::SearchListComponent, tabs::TabsComponent,
     },
     controller::{LayoutSpec, MoveFocus, find_next_focus, walk_layout},
-    model::{ledger_search::LedgerUtxoProvider, list_view::ListModelView},
-    states::{Action, ComponentId, LedgerBrowse, LedgerMode, LedgerSearch},
-    store::owned_iter::{
-        OwnedAccountIter, OwnedBlockIssuerIter, OwnedDRepIter, OwnedPoolIter, OwnedProposalIter,
-        OwnedUtxoIter,
+    export::{ExportScope, export, export_cbor, export_json},
+    json::ToJson,
+    list_ledger_snapshots,
+    model::{
+        ledger_diff::{DiffEntry, DiffQuery, LedgerDiffProvider},
+        ledger_search::{
+            DRepIdQuery, LedgerAccountProvider, LedgerDRepProvider, LedgerPoolProvider,
+            LedgerProposalProvider, LedgerUtxoProvider, PoolIdQuery, ProposalIdQuery,
+            StakeCredentialQuery, UtxoQuery,
+        },
+        ledger_stats::{LedgerStats, LedgerStatsModel},
+        list_view::ListModelView,
+    },
+    open_ledger_snapshot,
+    states::{
+        Action, ComponentId, ExportFormat, LedgerBrowse, LedgerMode, LedgerSearch, LedgerSnapshot,
+    },
+    store::{
+        owned_iter::{
+            OwnedAccountIter, OwnedBlockIssuerIter, OwnedDRepIter, OwnedPoolIter,
+            OwnedProposalIter, OwnedUtxoIter,
+        },
+        raw_ledger::{LedgerColumn, RawLedger},
+        utxo_index::{SharedUtxoIndex, UtxoIndexStatus},
+    },
+    ui::{
+        to_list_item::{
+            AccountItem, BlockIssuerItem, DRepItem, PoolItem, ProposalItem, ToListItem, UtxoItem,
+        },
+        to_rich::diff::DiffSide,
     },
-    ui::to_list_item::{AccountItem, BlockIssuerItem, DRepItem, PoolItem, ProposalItem, UtxoItem},
 };
-use amaru_kernel::Address;
+use amaru_kernel::Epoch;
 use amaru_stores::rocksdb::ReadOnlyRocksDB;
-use crossterm::event::Event;
+use anyhow::{Result, anyhow};
+use crossterm::event::{Event, KeyCode};
 use either::Either::{Left, Right};
+use minicbor::Encode;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Rect},
 };
 use std::{
     any::Any,
+    borrow::Borrow,
     collections::HashMap,
+    hash::Hash,
+    iter,
+    path::{Path, PathBuf},
+    str::FromStr,
     sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 use strum::IntoEnumIterator;
+use tokio::sync::oneshot;
+use tracing::warn;
 
 pub struct LedgerPageComponent {
     id: ComponentId,
@@ -39,14 +73,69 @@ pub struct LedgerPageComponent {
     pool_details: DetailsComponent<PoolItem>,
     proposal_details: DetailsComponent<ProposalItem>,
     utxo_details: DetailsComponent<UtxoItem>,
+    stats_details: DetailsComponent<LedgerStats>,
     utxo_by_addr_details: DetailsComponent<UtxoItem>,
+    account_by_credential_details: DetailsComponent<AccountItem>,
+    pool_by_id_details: DetailsComponent<PoolItem>,
+    drep_by_id_details: DetailsComponent<DRepItem>,
+    proposal_by_id_details: DetailsComponent<ProposalItem>,
+    diff_before_details: DetailsComponent<DiffEntry>,
+    diff_after_details: DetailsComponent<DiffEntry>,
 
     // Lists
     // Options
     browse_options: ListComponent<ListModelView<LedgerBrowse>>,
     search_options: ListComponent<ListModelView<LedgerSearch>>,
 
-    // Content
+    snapshot_options: ListComponent<ListModelView<LedgerSnapshot>>,
+
+    // Content and search results, read from the active snapshot
+    lists: LedgerLists,
+    active_snapshot: LedgerSnapshot,
+    /// Where switching to the selected snapshot is at, when it isn't the
+    /// active one.
+    snapshot_switch: Option<SnapshotSwitch>,
+    latest_snapshot: Option<Epoch>,
+    live_db: Arc<ReadOnlyRocksDB>,
+    ledger_path: PathBuf,
+
+    // Search
+    search_bar: SearchBarComponent,
+    diff_list: SearchListComponent<DiffQuery, DiffEntry>,
+
+    last_layout: RwLock<ComponentLayout>,
+    active_focus: RwLock<ComponentId>,
+}
+
+/// How long the snapshot selection has to stay put before the snapshot is
+/// opened, not to open every snapshot the cursor moves over.
+const SNAPSHOT_SETTLE: Duration = Duration::from_millis(300);
+
+/// Switching the lists to another snapshot than the active one, which is
+/// opened in the background.
+enum SnapshotSwitch {
+    /// Selected at that time, to open once the selection settles.
+    Selected(LedgerSnapshot, Instant),
+    Opening(
+        LedgerSnapshot,
+        oneshot::Receiver<Result<Arc<ReadOnlyRocksDB>>>,
+    ),
+    Failed(LedgerSnapshot),
+}
+
+impl SnapshotSwitch {
+    fn snapshot(&self) -> LedgerSnapshot {
+        match self {
+            SnapshotSwitch::Selected(snapshot, _)
+            | SnapshotSwitch::Opening(snapshot, _)
+            | SnapshotSwitch::Failed(snapshot) => *snapshot,
+        }
+    }
+}
+
+/// The lists backed by a ledger db, rebuilt when another snapshot is
+/// selected.
+struct LedgerLists {
     accounts_list: ListComponent<ListModelView<AccountItem>>,
     block_issuers_list: ListComponent<ListModelView<BlockIssuerItem>>,
     dreps_list: ListComponent<ListModelView<DRepItem>>,
@@ -54,22 +143,187 @@ pub struct LedgerPageComponent {
     proposals_list: ListComponent<ListModelView<ProposalItem>>,
     utxos_list: ListComponent<ListModelView<UtxoItem>>,
 
-    // Search
-    search_bar: SearchBarComponent,
-    utxos_by_addr_list: SearchListComponent<Address, UtxoItem>,
+    utxos_by_addr_list: SearchListComponent<UtxoQuery, UtxoItem>,
+    account_by_credential_list: SearchListComponent<StakeCredentialQuery, AccountItem>,
+    pool_by_id_list: SearchListComponent<PoolIdQuery, PoolItem>,
+    drep_by_id_list: SearchListComponent<DRepIdQuery, DRepItem>,
+    proposal_by_id_list: SearchListComponent<ProposalIdQuery, ProposalItem>,
 
-    last_layout: RwLock<ComponentLayout>,
-    active_focus: RwLock<ComponentId>,
+    stats: LedgerStatsModel,
+    utxo_index: SharedUtxoIndex,
+
+    db: Arc<ReadOnlyRocksDB>,
+    /// The entries as stored, `None` when the db can't be opened for it.
+    raw: Option<Arc<RawLedger>>,
 }
 
-impl LedgerPageComponent {
-    pub fn new(db: Arc<ReadOnlyRocksDB>) -> Self {
+impl LedgerLists {
+    fn new(db: Arc<ReadOnlyRocksDB>, path: &Path, epoch: Option<Epoch>) -> Self {
         let list_height = 0; // Will be updated in render
+        let utxo_index = SharedUtxoIndex::new(db.clone());
+        let raw = RawLedger::open(path)
+            .inspect_err(|e| warn!("Can't read the stored ledger entries: {}", e))
+            .ok()
+            .map(Arc::new);
+
+        Self {
+            accounts_list: ListComponent::new(
+                ComponentId::LedgerAccountsList,
+                ListModelView::new("Accounts", OwnedAccountIter::new(db.clone()), list_height),
+            ),
+            block_issuers_list: ListComponent::new(
+                ComponentId::LedgerBlockIssuersList,
+                ListModelView::new(
+                    "Block Issuers",
+                    OwnedBlockIssuerIter::new(db.clone()),
+                    list_height,
+                ),
+            ),
+            dreps_list: ListComponent::new(
+                ComponentId::LedgerDRepsList,
+                ListModelView::new("DReps", OwnedDRepIter::new(db.clone()), list_height),
+            ),
+            pools_list: ListComponent::new(
+                ComponentId::LedgerPoolsList,
+                ListModelView::new("Pools", OwnedPoolIter::new(db.clone()), list_height),
+            ),
+            proposals_list: ListComponent::new(
+                ComponentId::LedgerProposalsList,
+                ListModelView::new("Proposals", OwnedProposalIter::new(db.clone()), list_height),
+            ),
+            utxos_list: ListComponent::new(
+                ComponentId::LedgerUtxosList,
+                ListModelView::new("Utxos", OwnedUtxoIter::new(db.clone()), list_height),
+            ),
+
+            utxos_by_addr_list: SearchListComponent::new(
+                ComponentId::LedgerUtxosByAddrList,
+                "Utxos by Address",
+                Box::new(LedgerUtxoProvider::new(db.clone(), utxo_index.clone())),
+            ),
+            account_by_credential_list: SearchListComponent::new(
+                ComponentId::LedgerAccountByCredentialList,
+                "Account by Credential",
+                Box::new(LedgerAccountProvider { db: db.clone() }),
+            ),
+            pool_by_id_list: SearchListComponent::new(
+                ComponentId::LedgerPoolByIdList,
+                "Pool by Id",
+                Box::new(LedgerPoolProvider { db: db.clone() }),
+            ),
+            drep_by_id_list: SearchListComponent::new(
+                ComponentId::LedgerDRepByIdList,
+                "DRep by Id",
+                Box::new(LedgerDRepProvider { raw: raw.clone() }),
+            ),
+            proposal_by_id_list: SearchListComponent::new(
+                ComponentId::LedgerProposalByIdList,
+                "Proposal by Id",
+                Box::new(LedgerProposalProvider { raw: raw.clone() }),
+            ),
+
+            stats: LedgerStatsModel::new(db.clone(), epoch),
+            utxo_index,
+
+            db,
+            raw,
+        }
+    }
+
+    fn raw(&self) -> Result<&RawLedger> {
+        self.raw
+            .as_deref()
+            .ok_or_else(|| anyhow!("Can't read the stored ledger entries"))
+    }
+
+    /// Exports the selected entry of a column, or all of them: as JSON, or as
+    /// stored.
+    fn export_column<K, V, I>(
+        &self,
+        name: &str,
+        column: LedgerColumn,
+        scope: ExportScope,
+        format: ExportFormat,
+        selected: Option<&(K, V)>,
+        items: impl FnOnce() -> I,
+    ) -> Result<PathBuf>
+    where
+        K: Encode<()>,
+        (K, V): ToJson,
+        I: IntoIterator,
+        I::Item: Borrow<(K, V)>,
+    {
+        match format {
+            ExportFormat::Cbor => {
+                let raw = self.raw()?;
+                export_cbor(
+                    name,
+                    scope,
+                    || {
+                        raw.get(
+                            column,
+                            &selected.ok_or_else(|| anyhow!("Nothing selected"))?.0,
+                        )
+                    },
+                    || raw.iter(column),
+                )
+            }
+            _ => export_json(name, scope, selected, items),
+        }
+    }
+
+    /// Exports the selected entry of a search, or all of its results, see
+    /// `export_column`.
+    fn export_results<Q, K, V>(
+        &self,
+        name: &str,
+        column: LedgerColumn,
+        scope: ExportScope,
+        format: ExportFormat,
+        list: &SearchListComponent<Q, (K, V)>,
+    ) -> Result<PathBuf>
+    where
+        Q: Clone + Eq + Hash + FromStr + Send + Sync + 'static,
+        K: Encode<()>,
+        (K, V): ToJson + ToListItem + Send + Sync + 'static,
+    {
+        let items = list.items()?;
+        match format {
+            ExportFormat::Cbor => {
+                let raw = self.raw()?;
+                export_cbor(
+                    name,
+                    scope,
+                    || {
+                        let (key, _) = list
+                            .selected_item()
+                            .ok_or_else(|| anyhow!("Nothing selected"))?;
+                        raw.get(column, key)
+                    },
+                    || items.iter().map(|(key, _)| raw.get(column, key)),
+                )
+            }
+            _ => export_json(name, scope, list.selected_item(), || items),
+        }
+    }
+}
+
+impl LedgerPageComponent {
+    pub fn new(db: Arc<ReadOnlyRocksDB>, ledger_path: PathBuf) -> Self {
         let options_height = 0;
+        let snapshots = list_ledger_snapshots(&ledger_path).unwrap_or_else(|e| {
+            warn!(
+                "Can't list ledger snapshots in {}: {}",
+                ledger_path.display(),
+                e
+            );
+            Vec::new()
+        });
+        let latest_snapshot = snapshots.last().copied();
 
         Self {
             id: ComponentId::LedgerPage,
-            mode_tabs: TabsComponent::new(ComponentId::LedgerModeTabs, true),
+            mode_tabs: Self::mode_tabs(LedgerSnapshot::Live),
 
             // Details
             account_details: DetailsComponent::new(
@@ -87,10 +341,32 @@ impl LedgerPageComponent {
                 "Proposal Details",
             ),
             utxo_details: DetailsComponent::new(ComponentId::LedgerUtxoDetails, "UTXO Details"),
+            stats_details: DetailsComponent::new(ComponentId::LedgerStatsDetails, "Ledger Stats"),
             utxo_by_addr_details: DetailsComponent::new(
                 ComponentId::LedgerUtxosByAddrDetails,
                 "UTXO Details",
             ),
+            account_by_credential_details: DetailsComponent::new(
+                ComponentId::LedgerAccountByCredentialDetails,
+                "Account Details",
+            ),
+            pool_by_id_details: DetailsComponent::new(
+                ComponentId::LedgerPoolByIdDetails,
+                "Pool Details",
+            ),
+            drep_by_id_details: DetailsComponent::new(
+                ComponentId::LedgerDRepByIdDetails,
+                "DRep Details",
+            ),
+            proposal_by_id_details: DetailsComponent::new(
+                ComponentId::LedgerProposalByIdDetails,
+                "Proposal Details",
+            ),
+            diff_before_details: DetailsComponent::new(
+                ComponentId::LedgerDiffBeforeDetails,
+                "Before",
+            ),
+            diff_after_details: DetailsComponent::new(ComponentId::LedgerDiffAfterDetails, "After"),
 
             // Options
             browse_options: ListComponent::new(
@@ -101,50 +377,135 @@ impl LedgerPageComponent {
                 ComponentId::LedgerSearchOptions,
                 ListModelView::new("Search Options", LedgerSearch::iter(), options_height),
             ),
-
-            // Lists
-            accounts_list: ListComponent::new(
-                ComponentId::LedgerAccountsList,
-                ListModelView::new("Accounts", OwnedAccountIter::new(db.clone()), list_height),
-            ),
-            block_issuers_list: ListComponent::new(
-                ComponentId::LedgerBlockIssuersList,
+            snapshot_options: ListComponent::new(
+                ComponentId::LedgerSnapshotOptions,
                 ListModelView::new(
-                    "Block Issuers",
-                    OwnedBlockIssuerIter::new(db.clone()),
-                    list_height,
+                    "Snapshots",
+                    iter::once(LedgerSnapshot::Live)
+                        .chain(snapshots.into_iter().map(LedgerSnapshot::Epoch)),
+                    options_height,
                 ),
             ),
-            dreps_list: ListComponent::new(
-                ComponentId::LedgerDRepsList,
-                ListModelView::new("DReps", OwnedDRepIter::new(db.clone()), list_height),
-            ),
-            pools_list: ListComponent::new(
-                ComponentId::LedgerPoolsList,
-                ListModelView::new("Pools", OwnedPoolIter::new(db.clone()), list_height),
-            ),
-            proposals_list: ListComponent::new(
-                ComponentId::LedgerProposalsList,
-                ListModelView::new("Proposals", OwnedProposalIter::new(db.clone()), list_height),
-            ),
-            utxos_list: ListComponent::new(
-                ComponentId::LedgerUtxosList,
-                ListModelView::new("Utxos", OwnedUtxoIter::new(db.clone()), list_height),
-            ),
 
             // Search
             search_bar: SearchBarComponent::new(ComponentId::SearchBar),
-            utxos_by_addr_list: SearchListComponent::new(
-                ComponentId::LedgerUtxosByAddrList,
-                "Utxos by Address",
-                Box::new(LedgerUtxoProvider { db: db.clone() }),
+            diff_list: SearchListComponent::new(
+                ComponentId::LedgerDiffList,
+                "Snapshot Diff",
+                Box::new(LedgerDiffProvider {
+                    ledger_path: ledger_path.clone(),
+                }),
             ),
 
+            lists: LedgerLists::new(
+                db.clone(),
+                &ledger_path,
+                latest_snapshot.map(|epoch| epoch + 1),
+            ),
+            active_snapshot: LedgerSnapshot::Live,
+            snapshot_switch: None,
+            latest_snapshot,
+            live_db: db,
+            ledger_path,
+
             last_layout: RwLock::new(ComponentLayout::new()),
             active_focus: RwLock::new(ComponentId::LedgerBrowseOptions),
         }
     }
 
+    fn mode_tabs(snapshot: LedgerSnapshot) -> TabsComponent<LedgerMode> {
+        let mut tabs = TabsComponent::new(ComponentId::LedgerModeTabs, true);
+        tabs.set_title(Some(format!("Ledger ({})", snapshot)));
+        tabs
+    }
+
+    /// Points the browse lists and searches to the snapshot selected in the
+    /// snapshot list, once the selection settled. The snapshot is opened in
+    /// the background, the lists of the active one being marked stale until
+    /// it is, or if it can't be.
+    fn sync_snapshot(&mut self) {
+        let Some(&selected) = self.snapshot_options.model.selected_item() else {
+            return;
+        };
+        let target = self
+            .snapshot_switch
+            .as_ref()
+            .map_or(self.active_snapshot, SnapshotSwitch::snapshot);
+        if selected != target {
+            self.snapshot_switch = (selected != self.active_snapshot)
+                .then(|| SnapshotSwitch::Selected(selected, Instant::now()));
+            self.update_title();
+            return;
+        }
+
+        match self.snapshot_switch.take() {
+            Some(SnapshotSwitch::Selected(snapshot, at)) if at.elapsed() >= SNAPSHOT_SETTLE => {
+                let (tx, rx) = oneshot::channel();
+                let live_db = self.live_db.clone();
+                let ledger_path = self.ledger_path.clone();
+                tokio::task::spawn_blocking(move || {
+                    let db = match snapshot {
+                        LedgerSnapshot::Live => Ok(live_db),
+                        LedgerSnapshot::Epoch(epoch) => {
+                            open_ledger_snapshot(&ledger_path, epoch).map(Arc::new)
+                        }
+                    };
+                    let _ = tx.send(db);
+                });
+                self.snapshot_switch = Some(SnapshotSwitch::Opening(snapshot, rx));
+            }
+            Some(SnapshotSwitch::Opening(snapshot, mut rx)) => match rx.try_recv() {
+                Ok(Ok(db)) => {
+                    // The live ledger is in the epoch following its latest
+                    // snapshot
+                    let (path, epoch) = match snapshot {
+                        LedgerSnapshot::Live => (
+                            self.ledger_path.clone(),
+                            self.latest_snapshot.map(|epoch| epoch + 1),
+                        ),
+                        LedgerSnapshot::Epoch(epoch) => {
+                            (self.ledger_path.join(epoch.to_string()), Some(epoch))
+                        }
+                    };
+                    self.lists = LedgerLists::new(db, &path, epoch);
+                    self.active_snapshot = snapshot;
+                    self.update_title();
+                }
+                Ok(Err(e)) => {
+                    warn!("Can't open ledger snapshot {}: {}", snapshot, e);
+                    self.snapshot_switch = Some(SnapshotSwitch::Failed(snapshot));
+                    self.update_title();
+                }
+                Err(oneshot::error::TryRecvError::Empty) => {
+                    self.snapshot_switch = Some(SnapshotSwitch::Opening(snapshot, rx));
+                }
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    self.snapshot_switch = Some(SnapshotSwitch::Failed(snapshot));
+                    self.update_title();
+                }
+            },
+            switch => self.snapshot_switch = switch,
+        }
+    }
+
+    /// Titles the page with the snapshot the lists read, and the one being
+    /// switched to if any.
+    fn update_title(&mut self) {
+        let title = match &self.snapshot_switch {
+            None => format!("Ledger ({})", self.active_snapshot),
+            Some(SnapshotSwitch::Failed(snapshot)) => format!(
+                "Ledger ({} can't be opened, showing {})",
+                snapshot, self.active_snapshot
+            ),
+            Some(switch) => format!(
+                "Ledger (opening {}, showing {})",
+                switch.snapshot(),
+                self.active_snapshot
+            ),
+        };
+        self.mode_tabs.set_title(Some(title));
+    }
+
     fn dispatch_to_child(&mut self, id: ComponentId, event: &Event, area: Rect) -> Vec<Action> {
         match id {
             // Mode tabs
@@ -156,17 +517,36 @@ impl LedgerPageComponent {
             // Options
             ComponentId::LedgerBrowseOptions => self.browse_options.handle_event(event, area),
             ComponentId::LedgerSearchOptions => self.search_options.handle_event(event, area),
+            ComponentId::LedgerSnapshotOptions => self.snapshot_options.handle_event(event, area),
 
             // Lists
-            ComponentId::LedgerAccountsList => self.accounts_list.handle_event(event, area),
+            ComponentId::LedgerAccountsList => self.lists.accounts_list.handle_event(event, area),
             ComponentId::LedgerBlockIssuersList => {
-                self.block_issuers_list.handle_event(event, area)
+                self.lists.block_issuers_list.handle_event(event, area)
+            }
+            ComponentId::LedgerDRepsList => self.lists.dreps_list.handle_event(event, area),
+            ComponentId::LedgerPoolsList => self.lists.pools_list.handle_event(event, area),
+            ComponentId::LedgerProposalsList => self.lists.proposals_list.handle_event(event, area),
+            ComponentId::LedgerUtxosList => self.lists.utxos_list.handle_event(event, area),
+            ComponentId::LedgerUtxosByAddrList => {
+                if let Event::Key(key) = event
+                    && key.code == KeyCode::Char('i')
+                {
+                    self.lists.utxo_index.build();
+                    return Vec::new();
+                }
+                self.lists.utxos_by_addr_list.handle_event(event, area)
+            }
+            ComponentId::LedgerAccountByCredentialList => self
+                .lists
+                .account_by_credential_list
+                .handle_event(event, area),
+            ComponentId::LedgerPoolByIdList => self.lists.pool_by_id_list.handle_event(event, area),
+            ComponentId::LedgerDRepByIdList => self.lists.drep_by_id_list.handle_event(event, area),
+            ComponentId::LedgerProposalByIdList => {
+                self.lists.proposal_by_id_list.handle_event(event, area)
             }
-            ComponentId::LedgerDRepsList => self.dreps_list.handle_event(event, area),
-            ComponentId::LedgerPoolsList => self.pools_list.handle_event(event, area),
-            ComponentId::LedgerProposalsList => self.proposals_list.handle_event(event, area),
-            ComponentId::LedgerUtxosList => self.utxos_list.handle_event(event, area),
-            ComponentId::LedgerUtxosByAddrList => self.utxos_by_addr_list.handle_event(event, area),
+            ComponentId::LedgerDiffList => self.diff_list.handle_event(event, area),
 
             // Details
             ComponentId::LedgerAccountDetails => self.account_details.handle_event(event, area),
@@ -175,25 +555,68 @@ impl LedgerPageComponent {
             ComponentId::LedgerPoolDetails => self.pool_details.handle_event(event, area),
             ComponentId::LedgerProposalDetails => self.proposal_details.handle_event(event, area),
             ComponentId::LedgerUtxoDetails => self.utxo_details.handle_event(event, area),
+            ComponentId::LedgerStatsDetails => self.stats_details.handle_event(event, area),
             ComponentId::LedgerUtxosByAddrDetails => {
                 self.utxo_by_addr_details.handle_event(event, area)
             }
+            ComponentId::LedgerAccountByCredentialDetails => {
+                self.account_by_credential_details.handle_event(event, area)
+            }
+            ComponentId::LedgerPoolByIdDetails => self.pool_by_id_details.handle_event(event, area),
+            ComponentId::LedgerDRepByIdDetails => self.drep_by_id_details.handle_event(event, area),
+            ComponentId::LedgerProposalByIdDetails => {
+                self.proposal_by_id_details.handle_event(event, area)
+            }
+            ComponentId::LedgerDiffBeforeDetails => {
+                self.diff_before_details.handle_event(event, area)
+            }
+            ComponentId::LedgerDiffAfterDetails => {
+                self.diff_after_details.handle_event(event, area)
+            }
 
             // Default
             _ => Vec::new(),
         }
     }
 
-    // Helper to determine which list is currently active in the UI
-    fn get_active_list_component_id(&self) -> ComponentId {
-        match self.browse_options.model.selected_item() {
+    // Helper to determine which list is currently active in the UI, if any
+    fn get_active_list_component_id(&self) -> Option<ComponentId> {
+        let id = match self.browse_options.model.selected_item() {
             Some(LedgerBrowse::Accounts) => ComponentId::LedgerAccountsList,
             Some(LedgerBrowse::BlockIssuers) => ComponentId::LedgerBlockIssuersList,
             Some(LedgerBrowse::DReps) => ComponentId::LedgerDRepsList,
             Some(LedgerBrowse::Pools) => ComponentId::LedgerPoolsList,
             Some(LedgerBrowse::Proposals) => ComponentId::LedgerProposalsList,
             Some(LedgerBrowse::Utxos) => ComponentId::LedgerUtxosList,
+            Some(LedgerBrowse::Stats) => return None,
             None => ComponentId::LedgerAccountsList,
+        };
+        Some(id)
+    }
+
+    // Helper to determine which search list and details are active in the UI
+    fn get_active_search_component_ids(&self) -> (ComponentId, ComponentId) {
+        match self.search_options.model.selected_item() {
+            Some(LedgerSearch::UtxosByAddress) | None => (
+                ComponentId::LedgerUtxosByAddrList,
+                ComponentId::LedgerUtxosByAddrDetails,
+            ),
+            Some(LedgerSearch::AccountByCredential) => (
+                ComponentId::LedgerAccountByCredentialList,
+                ComponentId::LedgerAccountByCredentialDetails,
+            ),
+            Some(LedgerSearch::PoolById) => (
+                ComponentId::LedgerPoolByIdList,
+                ComponentId::LedgerPoolByIdDetails,
+            ),
+            Some(LedgerSearch::DRepById) => (
+                ComponentId::LedgerDRepByIdList,
+                ComponentId::LedgerDRepByIdDetails,
+            ),
+            Some(LedgerSearch::ProposalById) => (
+                ComponentId::LedgerProposalByIdList,
+                ComponentId::LedgerProposalByIdDetails,
+            ),
         }
     }
 
@@ -202,8 +625,8 @@ impl LedgerPageComponent {
 
         let header_constraints = match ledger_mode {
             LedgerMode::Browse => vec![(Constraint::Fill(1), Left(ComponentId::LedgerModeTabs))],
-            LedgerMode::Search => vec![
-                (Constraint::Length(20), Left(ComponentId::LedgerModeTabs)),
+            LedgerMode::Search | LedgerMode::Diff => vec![
+                (Constraint::Length(26), Left(ComponentId::LedgerModeTabs)),
                 (Constraint::Fill(1), Left(ComponentId::SearchBar)),
             ],
         };
@@ -212,23 +635,34 @@ impl LedgerPageComponent {
             constraints: header_constraints,
         };
 
+        if ledger_mode == LedgerMode::Diff {
+            return Self::diff_layout_spec(header_spec);
+        }
+
         let (options_id, list_id) = match ledger_mode {
             LedgerMode::Browse => (
                 ComponentId::LedgerBrowseOptions,
                 self.get_active_list_component_id(),
             ),
-            LedgerMode::Search => (
+            LedgerMode::Search | LedgerMode::Diff => (
                 ComponentId::LedgerSearchOptions,
-                ComponentId::LedgerUtxosByAddrList,
+                Some(self.get_active_search_component_ids().0),
             ),
         };
 
+        let mut left_col_constraints = vec![
+            (
+                Constraint::Length(6),
+                Left(ComponentId::LedgerSnapshotOptions),
+            ),
+            (Constraint::Fill(1), Left(options_id)),
+        ];
+        if let Some(list_id) = list_id {
+            left_col_constraints.push((Constraint::Fill(3), Left(list_id)));
+        }
         let left_col_spec = LayoutSpec {
             direction: Direction::Vertical,
-            constraints: vec![
-                (Constraint::Fill(1), Left(options_id)),
-                (Constraint::Fill(3), Left(list_id)),
-            ],
+            constraints: left_col_constraints,
         };
 
         let details_id = match ledger_mode {
@@ -239,9 +673,10 @@ impl LedgerPageComponent {
                 Some(LedgerBrowse::Pools) => ComponentId::LedgerPoolDetails,
                 Some(LedgerBrowse::Proposals) => ComponentId::LedgerProposalDetails,
                 Some(LedgerBrowse::Utxos) => ComponentId::LedgerUtxoDetails,
+                Some(LedgerBrowse::Stats) => ComponentId::LedgerStatsDetails,
                 None => ComponentId::LedgerAccountDetails,
             },
-            LedgerMode::Search => ComponentId::LedgerUtxosByAddrDetails,
+            LedgerMode::Search | LedgerMode::Diff => self.get_active_search_component_ids().1,
         };
 
         let body_spec = LayoutSpec {
@@ -261,6 +696,36 @@ impl LedgerPageComponent {
         }
     }
 
+    /// The changes between two snapshots on the left, and the selected one as
+    /// it was in each snapshot side by side.
+    fn diff_layout_spec(header_spec: LayoutSpec) -> LayoutSpec {
+        let body_spec = LayoutSpec {
+            direction: Direction::Horizontal,
+            constraints: vec![
+                (
+                    Constraint::Percentage(20),
+                    Left(ComponentId::LedgerDiffList),
+                ),
+                (
+                    Constraint::Percentage(40),
+                    Left(ComponentId::LedgerDiffBeforeDetails),
+                ),
+                (
+                    Constraint::Percentage(40),
+                    Left(ComponentId::LedgerDiffAfterDetails),
+                ),
+            ],
+        };
+
+        LayoutSpec {
+            direction: Direction::Vertical,
+            constraints: vec![
+                (Constraint::Length(3), Right(header_spec)),
+                (Constraint::Fill(1), Right(body_spec)),
+            ],
+        }
+    }
+
     pub fn calculate_layout(&self, area: Rect) -> ComponentLayout {
         let spec = self.build_layout_spec();
         let mut layout = HashMap::new();
@@ -269,7 +734,128 @@ impl LedgerPageComponent {
     }
 
     fn handle_search(&mut self, query: &str) {
-        self.utxos_by_addr_list.handle_search(query);
+        if self.mode_tabs.selected() == LedgerMode::Diff {
+            self.diff_list.handle_search(query);
+            return;
+        }
+        match self.search_options.model.selected_item() {
+            Some(LedgerSearch::UtxosByAddress) | None => {
+                self.lists.utxos_by_addr_list.handle_search(query)
+            }
+            Some(LedgerSearch::AccountByCredential) => {
+                self.lists.account_by_credential_list.handle_search(query)
+            }
+            Some(LedgerSearch::PoolById) => self.lists.pool_by_id_list.handle_search(query),
+            Some(LedgerSearch::DRepById) => self.lists.drep_by_id_list.handle_search(query),
+            Some(LedgerSearch::ProposalById) => self.lists.proposal_by_id_list.handle_search(query),
+        }
+    }
+
+    /// Searches UTxOs as if `query` was entered in the search bar, and
+    /// focuses the results.
+    pub fn search_utxos(&mut self, query: &str) {
+        self.mode_tabs.select(LedgerMode::Search);
+        if let Some(index) = LedgerSearch::iter().position(|o| o == LedgerSearch::UtxosByAddress) {
+            self.search_options.model.select_index(index);
+        }
+        self.search_bar.set_input(query);
+        self.handle_search(query);
+        *self.active_focus.write().unwrap() = ComponentId::LedgerUtxosByAddrList;
+    }
+
+    /// Exports from the list shown for the current mode and option.
+    pub fn export(&self, scope: ExportScope, format: ExportFormat) -> Result<PathBuf> {
+        if let Some(switch) = &self.snapshot_switch
+            && self.mode_tabs.selected() != LedgerMode::Diff
+        {
+            return Err(anyhow!(
+                "The lists show {} rather than the selected {}",
+                self.active_snapshot,
+                switch.snapshot()
+            ));
+        }
+        let lists = &self.lists;
+        let db = &lists.db;
+        match self.mode_tabs.selected() {
+            LedgerMode::Browse => match self.browse_options.model.selected_item() {
+                Some(LedgerBrowse::Accounts) | None => lists.export_column(
+                    "accounts",
+                    LedgerColumn::Accounts,
+                    scope,
+                    format,
+                    lists.accounts_list.model.selected_item(),
+                    || OwnedAccountIter::new(db.clone()),
+                ),
+                Some(LedgerBrowse::BlockIssuers) => lists.export_column(
+                    "block-issuers",
+                    LedgerColumn::BlockIssuers,
+                    scope,
+                    format,
+                    lists.block_issuers_list.model.selected_item(),
+                    || OwnedBlockIssuerIter::new(db.clone()),
+                ),
+                Some(LedgerBrowse::DReps) => lists.export_column(
+                    "dreps",
+                    LedgerColumn::DReps,
+                    scope,
+                    format,
+                    lists.dreps_list.model.selected_item(),
+                    || OwnedDRepIter::new(db.clone()),
+                ),
+                Some(LedgerBrowse::Pools) => lists.export_column(
+                    "pools",
+                    LedgerColumn::Pools,
+                    scope,
+                    format,
+                    lists.pools_list.model.selected_item(),
+                    || OwnedPoolIter::new(db.clone()),
+                ),
+                Some(LedgerBrowse::Proposals) => lists.export_column(
+                    "proposals",
+                    LedgerColumn::Proposals,
+                    scope,
+                    format,
+                    lists.proposals_list.model.selected_item(),
+                    || OwnedProposalIter::new(db.clone()),
+                ),
+                Some(LedgerBrowse::Utxos) => lists.export_column(
+                    "utxos",
+                    LedgerColumn::Utxos,
+                    scope,
+                    format,
+                    lists.utxos_list.model.selected_item(),
+                    || OwnedUtxoIter::new(db.clone()),
+                ),
+                Some(LedgerBrowse::Stats) => Err(anyhow!("Ledger stats can't be exported")),
+            },
+            LedgerMode::Search => match self.search_options.model.selected_item() {
+                Some(LedgerSearch::UtxosByAddress) | None => {
+                    let list = &lists.utxos_by_addr_list;
+                    lists.export_results("utxos", LedgerColumn::Utxos, scope, format, list)
+                }
+                Some(LedgerSearch::AccountByCredential) => {
+                    let list = &lists.account_by_credential_list;
+                    lists.export_results("accounts", LedgerColumn::Accounts, scope, format, list)
+                }
+                Some(LedgerSearch::PoolById) => {
+                    let list = &lists.pool_by_id_list;
+                    lists.export_results("pools", LedgerColumn::Pools, scope, format, list)
+                }
+                Some(LedgerSearch::DRepById) => {
+                    let list = &lists.drep_by_id_list;
+                    lists.export_results("dreps", LedgerColumn::DReps, scope, format, list)
+                }
+                Some(LedgerSearch::ProposalById) => {
+                    let list = &lists.proposal_by_id_list;
+                    lists.export_results("proposals", LedgerColumn::Proposals, scope, format, list)
+                }
+            },
+            LedgerMode::Diff => {
+                let list = &self.diff_list;
+                let items = list.items()?;
+                export("changes", scope, format, list.selected_item(), || items)
+            }
+        }
     }
 
     pub fn handle_navigation(&mut self, direction: MoveFocus) -> Vec<Action> {
@@ -314,68 +900,150 @@ impl LedgerPageComponent {
                 ComponentId::LedgerSearchOptions => {
                     self.search_options.render_focused(frame, area, is_focused);
                 }
+                ComponentId::LedgerSnapshotOptions => {
+                    self.snapshot_options
+                        .render_focused(frame, area, is_focused);
+                }
 
                 // --- Lists ---
                 ComponentId::LedgerAccountsList => {
-                    self.accounts_list.render_focused(frame, area, is_focused);
+                    self.lists
+                        .accounts_list
+                        .render_focused(frame, area, is_focused);
                 }
                 ComponentId::LedgerBlockIssuersList => {
-                    self.block_issuers_list
+                    self.lists
+                        .block_issuers_list
                         .render_focused(frame, area, is_focused);
                 }
                 ComponentId::LedgerDRepsList => {
-                    self.dreps_list.render_focused(frame, area, is_focused);
+                    self.lists
+                        .dreps_list
+                        .render_focused(frame, area, is_focused);
                 }
                 ComponentId::LedgerPoolsList => {
-                    self.pools_list.render_focused(frame, area, is_focused);
+                    self.lists
+                        .pools_list
+                        .render_focused(frame, area, is_focused);
                 }
                 ComponentId::LedgerProposalsList => {
-                    self.proposals_list.render_focused(frame, area, is_focused);
+                    self.lists
+                        .proposals_list
+                        .render_focused(frame, area, is_focused);
                 }
                 ComponentId::LedgerUtxosList => {
-                    self.utxos_list.render_focused(frame, area, is_focused);
+                    self.lists
+                        .utxos_list
+                        .render_focused(frame, area, is_focused);
                 }
                 ComponentId::LedgerUtxosByAddrList => {
-                    self.utxos_by_addr_list
+                    self.lists
+                        .utxos_by_addr_list
+                        .render_focused(frame, area, is_focused);
+                }
+                ComponentId::LedgerAccountByCredentialList => {
+                    self.lists
+                        .account_by_credential_list
+                        .render_focused(frame, area, is_focused);
+                }
+                ComponentId::LedgerPoolByIdList => {
+                    self.lists
+                        .pool_by_id_list
+                        .render_focused(frame, area, is_focused);
+                }
+                ComponentId::LedgerDRepByIdList => {
+                    self.lists
+                        .drep_by_id_list
                         .render_focused(frame, area, is_focused);
                 }
+                ComponentId::LedgerProposalByIdList => {
+                    self.lists
+                        .proposal_by_id_list
+                        .render_focused(frame, area, is_focused);
+                }
+                ComponentId::LedgerDiffList => {
+                    self.diff_list.render_focused(frame, area, is_focused);
+                }
 
                 // --- Details ---
                 ComponentId::LedgerAccountDetails => {
-                    let item = self.accounts_list.model.selected_item();
+                    let item = self.lists.accounts_list.model.selected_item();
                     self.account_details
                         .render_with_data(frame, area, is_focused, item);
                 }
                 ComponentId::LedgerBlockIssuerDetails => {
-                    let item = self.block_issuers_list.model.selected_item();
+                    let item = self.lists.block_issuers_list.model.selected_item();
                     self.block_details
                         .render_with_data(frame, area, is_focused, item);
                 }
                 ComponentId::LedgerDRepDetails => {
-                    let item = self.dreps_list.model.selected_item();
+                    let item = self.lists.dreps_list.model.selected_item();
                     self.drep_details
                         .render_with_data(frame, area, is_focused, item);
                 }
                 ComponentId::LedgerPoolDetails => {
-                    let item = self.pools_list.model.selected_item();
+                    let item = self.lists.pools_list.model.selected_item();
                     self.pool_details
                         .render_with_data(frame, area, is_focused, item);
                 }
                 ComponentId::LedgerProposalDetails => {
-                    let item = self.proposals_list.model.selected_item();
+                    let item = self.lists.proposals_list.model.selected_item();
                     self.proposal_details
                         .render_with_data(frame, area, is_focused, item);
                 }
                 ComponentId::LedgerUtxoDetails => {
-                    let item = self.utxos_list.model.selected_item();
+                    let item = self.lists.utxos_list.model.selected_item();
                     self.utxo_details
                         .render_with_data(frame, area, is_focused, item);
                 }
+                ComponentId::LedgerStatsDetails => {
+                    let stats = self.lists.stats.stats();
+                    self.stats_details
+                        .render_with_data(frame, area, is_focused, Some(&*stats));
+                }
                 ComponentId::LedgerUtxosByAddrDetails => {
-                    let item = self.utxos_by_addr_list.selected_item();
+                    let item = self.lists.utxos_by_addr_list.selected_item();
                     self.utxo_by_addr_details
                         .render_with_data(frame, area, is_focused, item);
                 }
+                ComponentId::LedgerAccountByCredentialDetails => {
+                    let item = self.lists.account_by_credential_list.selected_item();
+                    self.account_by_credential_details
+                        .render_with_data(frame, area, is_focused, item);
+                }
+                ComponentId::LedgerPoolByIdDetails => {
+                    let item = self.lists.pool_by_id_list.selected_item();
+                    self.pool_by_id_details
+                        .render_with_data(frame, area, is_focused, item);
+                }
+                ComponentId::LedgerDRepByIdDetails => {
+                    let item = self.lists.drep_by_id_list.selected_item();
+                    self.drep_by_id_details
+                        .render_with_data(frame, area, is_focused, item);
+                }
+                ComponentId::LedgerProposalByIdDetails => {
+                    let item = self.lists.proposal_by_id_list.selected_item();
+                    self.proposal_by_id_details
+                        .render_with_data(frame, area, is_focused, item);
+                }
+                ComponentId::LedgerDiffBeforeDetails => {
+                    let side = self.diff_list.selected_item().map(DiffSide::Before);
+                    self.diff_before_details.render_with_view(
+                        frame,
+                        area,
+                        is_focused,
+                        side.as_ref(),
+                    );
+                }
+                ComponentId::LedgerDiffAfterDetails => {
+                    let side = self.diff_list.selected_item().map(DiffSide::After);
+                    self.diff_after_details.render_with_view(
+                        frame,
+                        area,
+                        is_focused,
+                        side.as_ref(),
+                    );
+                }
                 _ => {}
             }
         }
@@ -421,7 +1089,26 @@ impl Component for LedgerPageComponent {
     }
 
     fn tick(&mut self) -> Vec<Action> {
-        self.utxos_by_addr_list.tick();
+        self.sync_snapshot();
+        if self.mode_tabs.selected() == LedgerMode::Browse
+            && self.browse_options.model.selected_item() == Some(&LedgerBrowse::Stats)
+        {
+            self.lists.stats.start();
+        }
+
+        let utxos_title = match self.lists.utxo_index.status() {
+            UtxoIndexStatus::Missing => "Utxos by Address (i to index)",
+            UtxoIndexStatus::Building => "Utxos by Address (indexing...)",
+            UtxoIndexStatus::Ready => "Utxos by Address (indexed)",
+            UtxoIndexStatus::Failed => "Utxos by Address (indexing failed, i to retry)",
+        };
+        self.lists.utxos_by_addr_list.set_title(utxos_title);
+        self.lists.utxos_by_addr_list.tick();
+        self.lists.account_by_credential_list.tick();
+        self.lists.pool_by_id_list.tick();
+        self.lists.drep_by_id_list.tick();
+        self.lists.proposal_by_id_list.tick();
+        self.diff_list.tick();
 
         let layout = self.last_layout.read().unwrap();
 
@@ -431,25 +1118,35 @@ impl Component for LedgerPageComponent {
         if let Some(area) = layout.get(&ComponentId::LedgerSearchOptions) {
             self.search_options.model.set_height(area.height as usize);
         }
+        if let Some(area) = layout.get(&ComponentId::LedgerSnapshotOptions) {
+            self.snapshot_options.model.set_height(area.height as usize);
+        }
         if let Some(area) = layout.get(&ComponentId::LedgerAccountsList) {
-            self.accounts_list.model.set_height(area.height as usize);
+            self.lists
+                .accounts_list
+                .model
+                .set_height(area.height as usize);
         }
         if let Some(area) = layout.get(&ComponentId::LedgerBlockIssuersList) {
-            self.block_issuers_list
+            self.lists
+                .block_issuers_list
                 .model
                 .set_height(area.height as usize);
         }
         if let Some(area) = layout.get(&ComponentId::LedgerDRepsList) {
-            self.dreps_list.model.set_height(area.height as usize);
+            self.lists.dreps_list.model.set_height(area.height as usize);
         }
         if let Some(area) = layout.get(&ComponentId::LedgerPoolsList) {
-            self.pools_list.model.set_height(area.height as usize);
+            self.lists.pools_list.model.set_height(area.height as usize);
         }
         if let Some(area) = layout.get(&ComponentId::LedgerProposalsList) {
-            self.proposals_list.model.set_height(area.height as usize);
+            self.lists
+                .proposals_list
+                .model
+                .set_height(area.height as usize);
         }
         if let Some(area) = layout.get(&ComponentId::LedgerUtxosList) {
-            self.utxos_list.model.set_height(area.height as usize);
+            self.lists.utxos_list.model.set_height(area.height as usize);
         }
 
         Vec::new()
@@ -3,6 +3,7 @@ use crate::{
     model::list_view::ListModelView,
     states::{Action, ComponentId},
     ui::to_list_item::ToListItem,
+    viewmodel::dynamic_list::DynamicListViewModel,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -17,6 +18,8 @@ pub trait ListModel: Send + Sync + 'static {
     type Item;
     fn draw(&self, f: &mut Frame, area: Rect, is_focused: bool);
     fn selected_item(&self) -> Option<&Self::Item>;
+    /// The items loaded so far.
+    fn items(&self) -> &[Self::Item];
     fn select_index_by_row(&mut self, relative_row: usize);
     fn cursor_back(&mut self);
     fn cursor_next(&mut self);
@@ -40,6 +43,54 @@ where
         self.selected_item()
     }
 
+    fn items(&self) -> &[Self::Item] {
+        self.items()
+    }
+
+    fn select_index_by_row(&mut self, relative_row: usize) {
+        self.select_index_by_row(relative_row);
+    }
+
+    fn cursor_back(&mut self) {
+        self.cursor_back();
+    }
+
+    fn cursor_next(&mut self) {
+        self.cursor_next();
+    }
+
+    fn retreat_window(&mut self) {
+        self.retreat_window();
+    }
+
+    fn advance_window(&mut self) {
+        self.advance_window();
+    }
+
+    fn set_height(&mut self, height: usize) {
+        self.set_height(height);
+    }
+}
+
+// Implement for the in-memory List Model (fully replaced on each update)
+impl<T> ListModel for DynamicListViewModel<T>
+where
+    T: Clone + PartialEq + ToListItem + Send + Sync + 'static,
+{
+    type Item = T;
+
+    fn draw(&self, f: &mut Frame, area: Rect, is_focused: bool) {
+        self.draw(f, area, is_focused);
+    }
+
+    fn selected_item(&self) -> Option<&Self::Item> {
+        self.selected_item()
+    }
+
+    fn items(&self) -> &[Self::Item] {
+        self.items()
+    }
+
     fn select_index_by_row(&mut self, relative_row: usize) {
         self.select_index_by_row(relative_row);
     }
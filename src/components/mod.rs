@@ -14,7 +14,9 @@ pub mod otel_page;
 pub mod root;
 pub mod search_bar;
 pub mod search_list;
+pub mod spans_page;
 pub mod tabs;
+pub mod trace_compare;
 pub mod trace_list;
 
 pub type ComponentLayout = HashMap<ComponentId, Rect>;
@@ -1,56 +1,141 @@
 use crate::{
     components::{
         Component, ComponentLayout, details::DetailsComponent, flame_graph::FlameGraphComponent,
-        handle_container_event, trace_list::TraceListComponent,
+        handle_container_event, list::ListComponent, search_bar::SearchBarComponent,
+        trace_compare::TraceCompareComponent, trace_list::TraceListComponent,
     },
     controller::{LayoutSpec, MoveFocus, find_next_focus, walk_layout},
+    export::{ExportScope, export, export_chrome_trace},
     model::otel_view::OtelViewState,
-    otel::{TraceGraphSnapshot, graph::TraceGraph, id::SpanId, span_ext::SpanExt},
-    states::{Action, ComponentId},
+    otel::{
+        OrphanSnapshot, TraceGraphSnapshot,
+        attribute::KnownAttribute,
+        critical_path::self_time,
+        filter::TraceFilter,
+        graph::TraceGraph,
+        id::{SpanId, TraceId},
+        orphanage::{OrphanGroup, Orphanage},
+        rate::{IngestCounters, IngestRate},
+        span_ext::SpanExt,
+    },
+    receiver::ReceiverHealth,
+    states::{Action, ComponentId, ExportFormat},
+    tui::copy_to_clipboard,
+    ui::to_rich::span::{SpanDetails, format_any_value},
+    view::receiver_status::{draw_receiver_status, split_status},
+    viewmodel::dynamic_list::DynamicListViewModel,
 };
+use anyhow::Result;
+use arc_swap::ArcSwap;
 use crossterm::event::{Event, KeyCode, MouseEventKind};
 use either::Either::{Left, Right};
 use opentelemetry_proto::tonic::trace::v1::Span;
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Rect},
+    layout::{Alignment, Constraint, Direction, Rect},
+    style::{Color, Style},
+    text::{Line, Span as TextSpan},
+    widgets::Paragraph,
+};
+use std::{
+    any::Any,
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
-use std::{any::Any, cmp::Reverse, collections::HashMap, sync::RwLock};
+use tracing::warn;
+
+/// The window the ingestion rate is averaged over.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
 
 pub struct OtelPageComponent {
     id: ComponentId,
 
     pub view_state: OtelViewState,
+    search_bar: SearchBarComponent,
     pub trace_list: TraceListComponent,
     pub flame_graph: FlameGraphComponent,
     pub span_details: DetailsComponent<Span>,
+    health: Vec<ReceiverHealth>,
+
+    counters: Arc<IngestCounters>,
+    rate: IngestRate,
+    /// Keeps the newest trace of the list selected as traces come in. Moving
+    /// the selection stops following.
+    follow: bool,
+
+    orphans: OrphanSnapshot,
+    orphan_list: ListComponent<DynamicListViewModel<OrphanGroup>>,
+    /// The orphans the list was last computed from.
+    last_orphans: Option<Arc<Orphanage>>,
+    /// The orphans shown in the flame graph in place of the selected trace,
+    /// as a trace of their own.
+    orphan_view: Option<OtelViewState>,
+    /// The comparison of the marked traces, shown in place of the flame
+    /// graph while open.
+    comparison: Option<TraceCompareComponent>,
+    /// The attribute selected in the span details, valid as long as its span
+    /// stays focused.
+    selected_attribute: Option<(SpanId, usize)>,
 
     last_layout: RwLock<ComponentLayout>,
     active_focus: RwLock<ComponentId>,
 }
 
 impl OtelPageComponent {
-    pub fn new(trace_graph: TraceGraphSnapshot) -> Self {
+    pub fn new(
+        trace_graph: TraceGraphSnapshot,
+        orphans: OrphanSnapshot,
+        counters: Arc<IngestCounters>,
+        health: Vec<ReceiverHealth>,
+    ) -> Self {
         Self {
             id: ComponentId::OtelPage,
             view_state: OtelViewState::new(trace_graph),
+            search_bar: SearchBarComponent::new(ComponentId::SearchBar),
             trace_list: TraceListComponent::new(ComponentId::OtelTraceList),
             flame_graph: FlameGraphComponent::new(ComponentId::OtelFlameGraph),
             span_details: DetailsComponent::new(ComponentId::OtelSpanDetails, "Span Details"),
+            health,
+
+            counters,
+            rate: IngestRate::new(RATE_WINDOW),
+            follow: false,
+
+            orphans,
+            orphan_list: ListComponent::new(
+                ComponentId::OtelOrphanList,
+                DynamicListViewModel::new("Orphans (Enter to show)"),
+            ),
+            last_orphans: None,
+            orphan_view: None,
+            comparison: None,
+            selected_attribute: None,
 
             last_layout: RwLock::new(HashMap::new()),
             active_focus: RwLock::new(ComponentId::OtelTraceList),
         }
     }
 
+    /// The state of the flame graph and span details: the orphans shown, or
+    /// else the selected trace.
+    fn flame_view(&self) -> &OtelViewState {
+        self.orphan_view.as_ref().unwrap_or(&self.view_state)
+    }
+
+    fn flame_view_mut(&mut self) -> &mut OtelViewState {
+        self.orphan_view.as_mut().unwrap_or(&mut self.view_state)
+    }
+
     fn get_visible_spans(&self, graph: &TraceGraph) -> Vec<SpanId> {
-        if let Some(selected_span) = &self.view_state.selected_span {
+        if let Some(selected_span) = &self.flame_view().selected_span {
             let selected_id = selected_span.span_id();
             let mut ancestors: Vec<SpanId> = graph.ancestor_iter(selected_id).collect();
             ancestors.reverse();
             let descendants = graph.descendent_iter(selected_id);
             ancestors.into_iter().chain(descendants).collect()
-        } else if let Some(trace_id) = &self.view_state.selected_trace_id {
+        } else if let Some(trace_id) = &self.flame_view().selected_trace_id {
             graph.trace_iter(trace_id).collect()
         } else {
             Vec::new()
@@ -58,14 +143,14 @@ impl OtelPageComponent {
     }
 
     fn scroll_trace_details(&mut self, direction: i32) {
-        let data = self.view_state.trace_graph.load();
+        let data = self.flame_view().trace_graph.load_full();
         let ordered_spans = self.get_visible_spans(&data);
         if ordered_spans.is_empty() {
             return;
         }
 
         let current_index = self
-            .view_state
+            .flame_view()
             .focused_span
             .as_ref()
             .and_then(|span| ordered_spans.iter().position(|id| *id == span.span_id()));
@@ -77,12 +162,242 @@ impl OtelPageComponent {
         let new_index = (current + direction).rem_euclid(len as i32) as usize;
 
         if Some(new_index) != current_index {
-            self.view_state.focused_span = ordered_spans
+            self.flame_view_mut().focused_span = ordered_spans
                 .get(new_index)
                 .and_then(|id| data.spans.get(id).cloned());
         }
     }
 
+    /// Focuses the next failed span of the visible ones after the focused
+    /// span, or the previous one when `direction` is negative.
+    fn focus_next_error(&mut self, direction: i32) {
+        let data = self.flame_view().trace_graph.load_full();
+        let ordered_spans = self.get_visible_spans(&data);
+        if ordered_spans.is_empty() {
+            return;
+        }
+
+        let current_index = self
+            .flame_view()
+            .focused_span
+            .as_ref()
+            .and_then(|span| ordered_spans.iter().position(|id| *id == span.span_id()));
+
+        let len = ordered_spans.len();
+        // Without a focused span, start from the first or the last one
+        let start = current_index.unwrap_or(if direction > 0 { len - 1 } else { 0 });
+        let next_error = (1..=len)
+            .map(|step| (start as i32 + direction * step as i32).rem_euclid(len as i32) as usize)
+            .filter_map(|index| data.spans.get(&ordered_spans[index]))
+            .find(|span| span.is_error());
+        if let Some(span) = next_error {
+            self.flame_view_mut().focused_span = Some(span.clone());
+        }
+    }
+
+    /// The span shown in the span details, unless they show a comparison.
+    fn detailed_span(&self) -> Option<Arc<Span>> {
+        if self.comparison.is_some() {
+            return None;
+        }
+        self.flame_view().focused_span.clone()
+    }
+
+    fn selected_attribute_index(&self, span: &Span) -> Option<usize> {
+        self.selected_attribute
+            .filter(|(span_id, _)| *span_id == span.span_id())
+            .map(|(_, index)| index)
+    }
+
+    /// Selects the next attribute of the detailed span, or the previous one
+    /// when `direction` is negative.
+    fn select_next_attribute(&mut self, direction: i32) {
+        let Some(span) = self.detailed_span() else {
+            return;
+        };
+        let len = span.attributes.len();
+        if len == 0 {
+            return;
+        }
+        let next = match self.selected_attribute_index(&span) {
+            Some(index) => (index as i32 + direction).rem_euclid(len as i32) as usize,
+            None if direction > 0 => 0,
+            None => len - 1,
+        };
+        self.selected_attribute = Some((span.span_id(), next));
+    }
+
+    /// The key and value of the selected attribute, if any.
+    fn selected_attribute(&self) -> Option<(String, String)> {
+        let span = self.detailed_span()?;
+        let attr = span.attributes.get(self.selected_attribute_index(&span)?)?;
+        Some((attr.key.clone(), format_any_value(&attr.value)))
+    }
+
+    /// Searches the selected attribute on the Chain or Ledger page, or
+    /// filters the traces by it, if it is a known one.
+    fn follow_attribute(&mut self) -> Vec<Action> {
+        let Some((key, value)) = self.selected_attribute() else {
+            return Vec::new();
+        };
+        let Some(attribute) = KnownAttribute::parse(&key, &value) else {
+            return Vec::new();
+        };
+        if let Some(query) = attribute.filter(&key, &value) {
+            self.search_bar.set_input(&query);
+            self.filter_traces(&query);
+            return Vec::new();
+        }
+        attribute.jump().into_iter().collect()
+    }
+
+    fn copy_attribute(&self) {
+        if let Some((_, value)) = self.selected_attribute()
+            && let Err(e) = copy_to_clipboard(&value)
+        {
+            warn!("Can't copy to the clipboard: {}", e);
+        }
+    }
+
+    /// Exports the focused span, or all the spans of the selected trace.
+    pub fn export(&self, scope: ExportScope, format: ExportFormat) -> Result<PathBuf> {
+        let selected = self
+            .flame_view()
+            .focused_span
+            .as_ref()
+            .or(self.flame_view().selected_span.as_ref());
+        let graph = self.flame_view().trace_graph.load();
+        let trace_id = self.flame_view().selected_trace_id;
+        export("spans", scope, format, selected.map(|span| &**span), || {
+            trace_id
+                .iter()
+                .flat_map(|trace_id| graph.trace_iter(trace_id))
+                .filter_map(|id| graph.spans.get(&id))
+                .map(|span| &**span)
+        })
+    }
+
+    /// Exports the selected trace, or all the traces kept, as a Chrome trace.
+    pub fn export_chrome_trace(&self, scope: ExportScope) -> Result<PathBuf> {
+        let graph = self.flame_view().trace_graph.load();
+        let trace_ids: Vec<TraceId> = match scope {
+            ExportScope::Selected => self.flame_view().selected_trace_id.into_iter().collect(),
+            ExportScope::List => {
+                let mut traces: Vec<_> = graph
+                    .traces
+                    .iter()
+                    .map(|(trace_id, meta)| (meta.start_time(), *trace_id))
+                    .collect();
+                traces.sort_unstable_by_key(|(start, _)| *start);
+                traces.into_iter().map(|(_, trace_id)| trace_id).collect()
+            }
+        };
+        export_chrome_trace(scope, &graph, &trace_ids)
+    }
+
+    /// Selects the newest trace of the list, if it isn't already.
+    fn follow_newest(&mut self) {
+        let Some(newest) = self.trace_list.newest() else {
+            return;
+        };
+        if self.trace_list.selected_item() != Some(&newest) {
+            self.trace_list.select(&newest);
+            self.view_state.select_trace(Some(newest));
+        }
+    }
+
+    fn toggle_follow(&mut self) {
+        self.follow = !self.follow;
+        if self.follow {
+            self.follow_newest();
+        }
+    }
+
+    /// Lists the orphans, if they changed since last time.
+    fn sync_orphans(&mut self) {
+        let orphans = self.orphans.load_full();
+        if self
+            .last_orphans
+            .as_ref()
+            .is_some_and(|last| Arc::ptr_eq(last, &orphans))
+        {
+            return;
+        }
+        let mut groups = orphans.groups();
+        groups.sort_unstable_by_key(|group| group.first_received);
+        self.orphan_list.model.set_items(groups);
+        self.last_orphans = Some(orphans);
+    }
+
+    /// Shows the selected orphans in the flame graph, as if the ones waiting
+    /// directly for the missing parent were roots.
+    fn show_orphans(&mut self) -> Vec<Action> {
+        let Some((group, orphans)) = self
+            .orphan_list
+            .model
+            .selected_item()
+            .zip(self.last_orphans.as_ref())
+        else {
+            return Vec::new();
+        };
+        let graph = orphans.subtree(&group.missing_parent);
+        let mut view = OtelViewState::new(Arc::new(ArcSwap::from_pointee(graph)));
+        view.select_trace(Some(group.trace_id));
+        self.orphan_view = Some(view);
+        self.comparison = None;
+        vec![Action::SetFocus(ComponentId::OtelFlameGraph)]
+    }
+
+    /// Compares the two traces marked in the trace list, if any.
+    fn compare_marked(&mut self) -> Vec<Action> {
+        let Some((baseline, other)) = self.trace_list.marked_pair() else {
+            return Vec::new();
+        };
+        self.comparison = Some(TraceCompareComponent::new(
+            ComponentId::OtelTraceCompare,
+            self.view_state.trace_graph.load_full(),
+            baseline,
+            other,
+        ));
+        vec![Action::SetFocus(ComponentId::OtelTraceCompare)]
+    }
+
+    /// Selects a trace, e.g. the one a log record was emitted in, and focuses
+    /// `span_id` in the flame graph.
+    pub fn show_trace(&mut self, trace_id: TraceId, span_id: Option<SpanId>) {
+        if !self.trace_list.select(&trace_id) {
+            warn!("Trace {} is no longer available", trace_id);
+            return;
+        }
+        self.orphan_view = None;
+        self.comparison = None;
+        self.view_state.select_trace(Some(trace_id));
+        let graph = self.view_state.trace_graph.load();
+        if let Some(span) = span_id.and_then(|id| graph.spans.get(&id)) {
+            self.view_state.focused_span = Some(span.clone());
+        }
+        *self.active_focus.write().unwrap() = ComponentId::OtelFlameGraph;
+    }
+
+    fn filter_traces(&mut self, query: &str) {
+        let filter = match query.parse::<TraceFilter>() {
+            Ok(filter) => filter,
+            Err(e) => {
+                self.search_bar.set_error(Some(e.to_string()));
+                return;
+            }
+        };
+        self.search_bar.set_error(None);
+
+        let old_selection = self.trace_list.selected_item().copied();
+        self.trace_list
+            .set_filter(filter, &self.view_state.trace_graph.load());
+        let new_selection = self.trace_list.selected_item().copied();
+        if new_selection != old_selection {
+            self.view_state.select_trace(new_selection);
+        }
+    }
+
     pub fn handle_navigation(&mut self, direction: MoveFocus) -> Vec<Action> {
         let layout = self.last_layout.read().unwrap();
         let active_focus = *self.active_focus.read().unwrap();
@@ -96,6 +411,13 @@ impl OtelPageComponent {
     }
 
     pub fn calculate_layout(&self, area: Rect) -> ComponentLayout {
+        let [area, _] = split_status(area);
+        // The comparison takes the place of the flame graph while open
+        let trace_view = if self.comparison.is_some() {
+            ComponentId::OtelTraceCompare
+        } else {
+            ComponentId::OtelFlameGraph
+        };
         let spec = LayoutSpec {
             direction: Direction::Vertical,
             constraints: vec![(
@@ -103,16 +425,23 @@ impl OtelPageComponent {
                 Right(LayoutSpec {
                     direction: Direction::Horizontal,
                     constraints: vec![
-                        (Constraint::Percentage(10), Left(ComponentId::OtelTraceList)),
                         (
-                            Constraint::Percentage(90),
+                            Constraint::Percentage(30),
+                            Right(LayoutSpec {
+                                direction: Direction::Vertical,
+                                constraints: vec![
+                                    (Constraint::Length(3), Left(ComponentId::SearchBar)),
+                                    (Constraint::Fill(3), Left(ComponentId::OtelTraceList)),
+                                    (Constraint::Fill(1), Left(ComponentId::OtelOrphanList)),
+                                ],
+                            }),
+                        ),
+                        (
+                            Constraint::Percentage(70),
                             Right(LayoutSpec {
                                 direction: Direction::Horizontal,
                                 constraints: vec![
-                                    (
-                                        Constraint::Percentage(70),
-                                        Left(ComponentId::OtelFlameGraph),
-                                    ),
+                                    (Constraint::Percentage(70), Left(trace_view)),
                                     (
                                         Constraint::Percentage(30),
                                         Left(ComponentId::OtelSpanDetails),
@@ -130,8 +459,33 @@ impl OtelPageComponent {
         layout
     }
 
+    /// Draws the ingestion rate and whether the newest trace is followed, on
+    /// the right of the status line.
+    fn draw_follow_status(&self, frame: &mut Frame, area: Rect) {
+        let rate = match self.rate.per_second() {
+            Some((traces, spans)) => format!("{:.1} traces/s, {:.0} spans/s | ", traces, spans),
+            None => String::new(),
+        };
+        let (follow, color) = if self.follow {
+            ("following (f)", Color::Green)
+        } else {
+            ("paused (f to follow)", Color::Gray)
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                TextSpan::raw(rate),
+                TextSpan::styled(follow, Style::default().fg(color)),
+            ]))
+            .alignment(Alignment::Right),
+            area,
+        );
+    }
+
     pub fn render(&self, frame: &mut Frame, area: Rect) {
         let my_layout = self.calculate_layout(area);
+        let status_area = split_status(area)[1];
+        draw_receiver_status(frame, status_area, &self.health);
+        self.draw_follow_status(frame, status_area);
 
         {
             let mut layout_guard = self.last_layout.write().unwrap();
@@ -139,25 +493,54 @@ impl OtelPageComponent {
         }
 
         let current_focus = *self.active_focus.read().unwrap();
+        if let Some(rect) = my_layout.get(&ComponentId::SearchBar) {
+            let is_focused = current_focus == ComponentId::SearchBar;
+            self.search_bar.render_focused(frame, *rect, is_focused);
+        }
+
         if let Some(rect) = my_layout.get(&ComponentId::OtelTraceList) {
             let is_focused = current_focus == ComponentId::OtelTraceList;
             self.trace_list.render_focused(frame, *rect, is_focused);
         }
 
+        if let Some(rect) = my_layout.get(&ComponentId::OtelOrphanList) {
+            let is_focused = current_focus == ComponentId::OtelOrphanList;
+            self.orphan_list.render_focused(frame, *rect, is_focused);
+        }
+
         if let Some(rect) = my_layout.get(&ComponentId::OtelFlameGraph) {
             let is_focused = current_focus == ComponentId::OtelFlameGraph;
             self.flame_graph
-                .render_with_state(frame, *rect, &self.view_state, is_focused);
+                .render_with_state(frame, *rect, self.flame_view(), is_focused);
         }
 
-        if let Some(rect) = my_layout.get(&ComponentId::OtelSpanDetails) {
+        if let Some(rect) = my_layout.get(&ComponentId::OtelTraceCompare)
+            && let Some(comparison) = &self.comparison
+        {
+            let is_focused = current_focus == ComponentId::OtelTraceCompare;
+            comparison.render_focused(frame, *rect, is_focused);
+        }
+
+        if let Some(rect) = my_layout.get(&ComponentId::OtelSpanDetails)
+            && let Some(comparison) = &self.comparison
+        {
+            let is_focused = current_focus == ComponentId::OtelSpanDetails;
+            self.span_details
+                .render_with_view(frame, *rect, is_focused, comparison.selected_row());
+        } else if let Some(rect) = my_layout.get(&ComponentId::OtelSpanDetails) {
             let is_focused = current_focus == ComponentId::OtelSpanDetails;
-            self.span_details.render_with_data(
-                frame,
-                *rect,
-                is_focused,
-                self.view_state.focused_span.as_deref(),
-            );
+            let graph = self.flame_view().trace_graph.load();
+            let details = self
+                .flame_view()
+                .focused_span
+                .as_deref()
+                .map(|span| SpanDetails {
+                    span,
+                    self_time: self_time(&graph, &span.span_id()),
+                    selected_attribute: self.selected_attribute_index(span),
+                });
+            self.span_details
+                .render_with_view(frame, *rect, is_focused, details.as_ref());
         }
     }
 }
@@ -177,7 +560,7 @@ impl Component for OtelPageComponent {
         let layout = self.last_layout.read().unwrap().clone();
         let mut active_focus = *self.active_focus.read().unwrap();
 
-        let actions = handle_container_event(
+        let mut actions = handle_container_event(
             &layout,
             &mut active_focus,
             event,
@@ -195,10 +578,33 @@ impl Component for OtelPageComponent {
                         let new_selection = self.trace_list.selected_item();
                         if new_selection != old_selection.as_ref() {
                             self.view_state.select_trace(new_selection.copied());
+                            self.orphan_view = None;
+                            self.follow = false;
+                        }
+                        if let Event::Key(key) = ev {
+                            match key.code {
+                                KeyCode::Char('f') => self.toggle_follow(),
+                                // Back from the orphans to the selected trace
+                                KeyCode::Enter => self.orphan_view = None,
+                                KeyCode::Char('d') => return self.compare_marked(),
+                                _ => {}
+                            }
                         }
                         acts
                     }
 
+                    ComponentId::OtelOrphanList => {
+                        if let Event::Key(key) = ev
+                            && key.code == KeyCode::Enter
+                        {
+                            return self.show_orphans();
+                        }
+                        self.orphan_list
+                            .model
+                            .set_height(child_area.height as usize);
+                        self.orphan_list.handle_event(ev, child_area)
+                    }
+
                     ComponentId::OtelFlameGraph => {
                         let acts = self.flame_graph.handle_event(ev, child_area);
 
@@ -207,13 +613,13 @@ impl Component for OtelPageComponent {
                             if mouse.kind == MouseEventKind::Moved {
                                 let relative_row =
                                     mouse.row.saturating_sub(child_area.y + 1) as usize;
-                                let trace_graph = self.view_state.trace_graph.load();
+                                let trace_graph = self.flame_view().trace_graph.load_full();
                                 let visible_spans = self.get_visible_spans(&trace_graph);
                                 let hovered_span_id = visible_spans.get(relative_row).copied();
                                 let new_focus = hovered_span_id
                                     .and_then(|span_id| trace_graph.spans.get(&span_id).cloned());
-                                if self.view_state.focused_span != new_focus {
-                                    self.view_state.focused_span = new_focus;
+                                if self.flame_view().focused_span != new_focus {
+                                    self.flame_view_mut().focused_span = new_focus;
                                 }
                             }
 
@@ -227,9 +633,9 @@ impl Component for OtelPageComponent {
                             // "Zoom In" by locking the currently focused span
                             if mouse.kind
                                 == MouseEventKind::Down(crossterm::event::MouseButton::Left)
-                                && let Some(focused) = &self.view_state.focused_span
+                                && let Some(focused) = self.flame_view().focused_span.clone()
                             {
-                                self.view_state.selected_span = Some(focused.clone());
+                                self.flame_view_mut().selected_span = Some(focused);
                             }
                         }
 
@@ -242,6 +648,16 @@ impl Component for OtelPageComponent {
                                 KeyCode::Up => {
                                     self.scroll_trace_details(-1);
                                 }
+                                KeyCode::Char('e') => {
+                                    self.focus_next_error(1);
+                                }
+                                KeyCode::Char('E') => {
+                                    self.focus_next_error(-1);
+                                }
+                                KeyCode::Char('c') => {
+                                    let view = self.flame_view_mut();
+                                    view.show_critical_path = !view.show_critical_path;
+                                }
                                 _ => {}
                             }
                         }
@@ -249,17 +665,73 @@ impl Component for OtelPageComponent {
                         acts
                     }
 
-                    ComponentId::OtelSpanDetails => self.span_details.handle_event(ev, child_area),
+                    ComponentId::OtelTraceCompare => {
+                        if let Event::Key(key) = ev
+                            && key.code == KeyCode::Char('d')
+                        {
+                            self.comparison = None;
+                            return vec![Action::SetFocus(ComponentId::OtelFlameGraph)];
+                        }
+                        self.comparison
+                            .as_mut()
+                            .map(|comparison| comparison.handle_event(ev, child_area))
+                            .unwrap_or_default()
+                    }
+
+                    ComponentId::OtelSpanDetails => {
+                        if let Event::Key(key) = ev {
+                            match key.code {
+                                KeyCode::Char('a') => {
+                                    self.select_next_attribute(1);
+                                    return Vec::new();
+                                }
+                                KeyCode::Char('A') => {
+                                    self.select_next_attribute(-1);
+                                    return Vec::new();
+                                }
+                                KeyCode::Enter => return self.follow_attribute(),
+                                KeyCode::Char('y') => {
+                                    self.copy_attribute();
+                                    return Vec::new();
+                                }
+                                _ => {}
+                            }
+                        }
+                        self.span_details.handle_event(ev, child_area)
+                    }
+                    ComponentId::SearchBar => self.search_bar.handle_event(ev, child_area),
                     _ => Vec::new(),
                 }
             },
         );
 
+        // Children move the focus, e.g. to the flame graph once orphans are
+        // shown
+        if let Some(Action::SetFocus(id)) = actions
+            .iter()
+            .rev()
+            .find(|a| matches!(a, Action::SetFocus(_)))
+        {
+            active_focus = *id;
+        }
+
+        // Intercept SubmitSearch from the search bar so it doesn't bubble to App
+        if let Some(pos) = actions
+            .iter()
+            .position(|a| matches!(a, Action::SubmitSearch(_)))
+            && let Action::SubmitSearch(query) = actions.remove(pos)
+        {
+            self.filter_traces(&query);
+        }
+
         *self.active_focus.write().unwrap() = active_focus;
         actions
     }
 
     fn tick(&mut self) -> Vec<Action> {
+        self.sync_orphans();
+        self.rate.sample(Instant::now(), self.counters.totals());
+
         let selected_trace = self.trace_list.selected_item().copied();
 
         // Sync the ViewState (Data) with the UI selection
@@ -269,11 +741,11 @@ impl Component for OtelPageComponent {
             return Vec::new();
         }
 
-        let data = self.view_state.trace_graph.load();
-        let mut trace_ids: Vec<_> = data.traces.keys().copied().collect();
-        trace_ids.sort_unstable_by_key(|id| Reverse(data.traces.get(id).unwrap().start_time()));
-
-        self.trace_list.sync_state(trace_ids);
+        self.trace_list
+            .sync_state(&self.view_state.trace_graph.load());
+        if self.follow {
+            self.follow_newest();
+        }
 
         Vec::new()
     }
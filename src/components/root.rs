@@ -1,21 +1,28 @@
 use crate::{
     components::{
         Component, ComponentLayout, chain_page::ChainPageComponent,
-        ledger_page::LedgerPageComponent, otel_page::OtelPageComponent, tabs::TabsComponent,
+        ledger_page::LedgerPageComponent, otel_page::OtelPageComponent,
+        spans_page::SpansPageComponent, tabs::TabsComponent,
     },
     controller::{LayoutSpec, MoveFocus, walk_layout},
+    export::ExportScope,
+    logs::page::LogsPageComponent,
     metrics::page::MetricsPageComponent,
-    otel::TraceGraphSnapshot,
-    states::{Action, ComponentId, InspectOption},
+    otel::service::OtelCollectorHandle,
+    states::{Action, ComponentId, ExportFormat, InspectOption},
 };
 use amaru_stores::rocksdb::{ReadOnlyRocksDB, consensus::ReadOnlyChainDB};
+use anyhow::{Result, anyhow};
 use crossterm::event::{Event, KeyCode, KeyModifiers};
 use either::Either::Left;
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Rect},
+    layout::{Alignment, Constraint, Direction, Rect},
+    style::{Color, Style},
+    widgets::Paragraph,
 };
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{any::Any, collections::HashMap, path::PathBuf, sync::Arc};
+use tracing::{info, warn};
 
 pub struct RootComponent {
     id: ComponentId,
@@ -23,30 +30,82 @@ pub struct RootComponent {
     pub ledger_page: LedgerPageComponent,
     pub chain_page: ChainPageComponent,
     pub otel_page: OtelPageComponent,
+    pub spans_page: SpansPageComponent,
+    pub logs_page: LogsPageComponent,
     pub metrics_page: MetricsPageComponent,
+    /// The outcome of the last export, shown next to the tabs.
+    notice: Option<(String, Color)>,
 }
 
 impl RootComponent {
     pub fn new(
         ledger_db: Arc<ReadOnlyRocksDB>,
+        ledger_path: PathBuf,
         chain_db: Arc<ReadOnlyChainDB>,
-        trace_graph: TraceGraphSnapshot,
+        collector: OtelCollectorHandle,
     ) -> Self {
         Self {
             id: ComponentId::Root,
             tabs: TabsComponent::new(ComponentId::InspectTabs, false),
-            ledger_page: LedgerPageComponent::new(ledger_db),
+            ledger_page: LedgerPageComponent::new(ledger_db, ledger_path),
             chain_page: ChainPageComponent::new(chain_db),
-            otel_page: OtelPageComponent::new(trace_graph),
-            metrics_page: MetricsPageComponent::new_with_service(),
+            logs_page: LogsPageComponent::new(
+                collector.logs,
+                collector.snapshot.clone(),
+                collector.health.clone(),
+            ),
+            spans_page: SpansPageComponent::new(collector.snapshot.clone()),
+            otel_page: OtelPageComponent::new(
+                collector.snapshot,
+                collector.orphans,
+                collector.counters,
+                collector.health.clone(),
+            ),
+            metrics_page: MetricsPageComponent::new(collector.metrics_rx, collector.health),
+            notice: None,
         }
     }
 
+    fn export(&mut self, scope: ExportScope, format: ExportFormat) {
+        let result = match self.tabs.selected() {
+            InspectOption::Ledger => self.ledger_page.export(scope, format),
+            InspectOption::Chain => self.chain_page.export(scope, format),
+            InspectOption::Otel => self.otel_page.export(scope, format),
+            InspectOption::Spans | InspectOption::Logs | InspectOption::Metrics => {
+                Err(anyhow!("Nothing to export"))
+            }
+        };
+        self.notify_export(result);
+    }
+
+    fn export_chrome_trace(&mut self, scope: ExportScope) {
+        let result = match self.tabs.selected() {
+            InspectOption::Otel => self.otel_page.export_chrome_trace(scope),
+            _ => Err(anyhow!("Only traces can be exported as Chrome traces")),
+        };
+        self.notify_export(result);
+    }
+
+    fn notify_export(&mut self, result: Result<PathBuf>) {
+        self.notice = Some(match result {
+            Ok(path) => {
+                info!("Exported to {}", path.display());
+                (format!("Exported to {}", path.display()), Color::Green)
+            }
+            Err(e) => {
+                warn!("Can't export: {}", e);
+                (format!("Can't export: {}", e), Color::Red)
+            }
+        });
+    }
+
     fn calculate_layout(&self, area: Rect) -> ComponentLayout {
         let active_page_id = match self.tabs.selected() {
             InspectOption::Ledger => ComponentId::LedgerPage,
             InspectOption::Chain => ComponentId::ChainPage,
             InspectOption::Otel => ComponentId::OtelPage,
+            InspectOption::Spans => ComponentId::SpansPage,
+            InspectOption::Logs => ComponentId::LogsPage,
             InspectOption::Metrics => ComponentId::MetricsPage,
         };
 
@@ -66,6 +125,8 @@ impl RootComponent {
                 InspectOption::Ledger => self.ledger_page.calculate_layout(*page_rect),
                 InspectOption::Chain => self.chain_page.calculate_layout(*page_rect),
                 InspectOption::Otel => self.otel_page.calculate_layout(*page_rect),
+                InspectOption::Spans => self.spans_page.calculate_layout(*page_rect),
+                InspectOption::Logs => self.logs_page.calculate_layout(*page_rect),
                 InspectOption::Metrics => self.metrics_page.calculate_layout(*page_rect),
             };
             layout.extend(child_layout);
@@ -79,6 +140,12 @@ impl RootComponent {
 
         if let Some(tabs_area) = my_layout.get(&ComponentId::InspectTabs) {
             self.tabs.render_focused(frame, *tabs_area, false);
+            if let Some((notice, color)) = &self.notice {
+                let notice = Paragraph::new(notice.as_str())
+                    .style(Style::default().fg(*color))
+                    .alignment(Alignment::Right);
+                frame.render_widget(notice, *tabs_area);
+            }
         }
 
         match self.tabs.selected() {
@@ -97,6 +164,16 @@ impl RootComponent {
                     self.otel_page.render(frame, *rect);
                 }
             }
+            InspectOption::Spans => {
+                if let Some(rect) = my_layout.get(&ComponentId::SpansPage) {
+                    self.spans_page.render(frame, *rect);
+                }
+            }
+            InspectOption::Logs => {
+                if let Some(rect) = my_layout.get(&ComponentId::LogsPage) {
+                    self.logs_page.render(frame, *rect);
+                }
+            }
             InspectOption::Metrics => {
                 if let Some(rect) = my_layout.get(&ComponentId::MetricsPage) {
                     self.metrics_page.render(frame, *rect);
@@ -123,6 +200,13 @@ impl Component for RootComponent {
         actions.extend(self.ledger_page.tick());
         actions.extend(self.chain_page.tick());
         actions.extend(self.otel_page.tick());
+        // The Spans and Logs pages are only kept up to date while shown, and
+        // catch up on their first tick once shown again
+        match self.tabs.selected() {
+            InspectOption::Spans => actions.extend(self.spans_page.tick()),
+            InspectOption::Logs => actions.extend(self.logs_page.tick()),
+            _ => {}
+        }
         actions.extend(self.metrics_page.tick());
         actions
     }
@@ -160,11 +244,44 @@ impl Component for RootComponent {
             InspectOption::Ledger => self.ledger_page.handle_event(event, page_area),
             InspectOption::Chain => self.chain_page.handle_event(event, page_area),
             InspectOption::Otel => self.otel_page.handle_event(event, page_area),
+            InspectOption::Spans => self.spans_page.handle_event(event, page_area),
+            InspectOption::Logs => self.logs_page.handle_event(event, page_area),
             InspectOption::Metrics => self.metrics_page.handle_event(event, page_area),
         }
     }
 
     fn handle_action(&mut self, action: Action) -> Vec<Action> {
+        match action {
+            Action::ExportSelected(format) => {
+                self.export(ExportScope::Selected, format);
+                return Vec::new();
+            }
+            Action::ExportList(format) => {
+                self.export(ExportScope::List, format);
+                return Vec::new();
+            }
+            Action::ExportChromeTrace(scope) => {
+                self.export_chrome_trace(scope);
+                return Vec::new();
+            }
+            Action::ShowTrace(trace_id, span_id) => {
+                self.tabs.select(InspectOption::Otel);
+                self.otel_page.show_trace(trace_id, span_id);
+                return Vec::new();
+            }
+            Action::SearchChain(query) => {
+                self.tabs.select(InspectOption::Chain);
+                self.chain_page.search(&query);
+                return Vec::new();
+            }
+            Action::SearchUtxos(query) => {
+                self.tabs.select(InspectOption::Ledger);
+                self.ledger_page.search_utxos(&query);
+                return Vec::new();
+            }
+            _ => {}
+        }
+
         // Map Action::Focus* to MoveFocus enum
         let direction = match action {
             Action::FocusUp => MoveFocus::Up,
@@ -179,6 +296,8 @@ impl Component for RootComponent {
             InspectOption::Ledger => self.ledger_page.handle_navigation(direction),
             InspectOption::Chain => self.chain_page.handle_navigation(direction),
             InspectOption::Otel => self.otel_page.handle_navigation(direction),
+            InspectOption::Spans => self.spans_page.handle_navigation(direction),
+            InspectOption::Logs => self.logs_page.handle_navigation(direction),
             InspectOption::Metrics => self.metrics_page.handle_navigation(direction),
         }
     }
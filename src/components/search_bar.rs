@@ -15,24 +15,48 @@ use tracing::debug;
 
 pub struct SearchBarComponent {
     id: ComponentId,
+    title: &'static str,
     input: String,
+    /// Why the last search was rejected, shown in the title.
+    error: Option<String>,
 }
 
 impl SearchBarComponent {
     pub fn new(id: ComponentId) -> Self {
+        Self::with_title(id, "Search")
+    }
+
+    pub fn with_title(id: ComponentId, title: &'static str) -> Self {
         Self {
             id,
+            title,
             input: String::new(),
+            error: None,
         }
     }
 
+    /// Replaces the input, e.g. with a search made from another page.
+    pub fn set_input(&mut self, input: &str) {
+        self.input = input.to_string();
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
     pub fn render_focused(&self, f: &mut Frame, area: Rect, is_focused: bool) {
-        let mut block = Block::default().title("Search").borders(Borders::ALL);
+        let mut block = Block::default().title(self.title).borders(Borders::ALL);
         if is_focused {
             block = block
                 .border_style(Style::default().fg(Color::Blue))
                 .title_style(Style::default().fg(Color::White));
         }
+        if let Some(error) = &self.error {
+            block = block.title(Line::styled(
+                format!(" {} ", error),
+                Style::default().fg(Color::Red),
+            ));
+        }
 
         let paragraph = Paragraph::new(Line::from(Span::raw(&self.input))).block(block);
         f.render_widget(paragraph, area);
@@ -5,6 +5,7 @@ use crate::{
     ui::to_list_item::ToListItem,
     view::empty_list::draw_empty_list,
 };
+use anyhow::{Result, anyhow};
 use crossterm::event::{Event, KeyCode, MouseButton, MouseEventKind};
 use ratatui::prelude::*;
 use std::{any::Any, hash::Hash, str::FromStr};
@@ -62,10 +63,25 @@ where
         }
     }
 
+    /// Sets the title shown until a search is made.
+    pub fn set_title(&mut self, title: &'static str) {
+        self.title = title;
+    }
+
     pub fn selected_item(&self) -> Option<&R> {
         self.state.get_current_res().and_then(|m| m.selected_item())
     }
 
+    /// All the results of the current search, failing while they are still
+    /// loading.
+    pub fn items(&self) -> Result<&[R]> {
+        match self.state.get_current_res() {
+            Some(model) if model.is_loading() => Err(anyhow!("The results are still loading")),
+            Some(model) => Ok(model.items()),
+            None => Ok(&[]),
+        }
+    }
+
     pub fn handle_search(&mut self, query: &str) {
         self.perform_search(query.to_string());
     }
@@ -0,0 +1,278 @@
+use crate::{
+    components::{
+        Component, ComponentLayout, details::DetailsComponent, handle_container_event,
+        list::ListComponent, search_bar::SearchBarComponent,
+    },
+    controller::{LayoutSpec, MoveFocus, find_next_focus, walk_layout},
+    otel::{
+        TraceGraphSnapshot,
+        aggregate::{SpanStats, aggregate},
+        graph::TraceGraph,
+    },
+    states::{Action, ComponentId},
+    viewmodel::dynamic_list::DynamicListViewModel,
+};
+use crossterm::event::{Event, KeyCode};
+use either::Either::{Left, Right};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Rect},
+};
+use std::{
+    any::Any,
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// Aggregating all the spans is too costly to follow every update of the
+/// graph.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The order of the span list, cycled with `s`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SpanSort {
+    #[default]
+    P99,
+    Count,
+    Name,
+}
+
+impl SpanSort {
+    fn next(self) -> Self {
+        match self {
+            SpanSort::P99 => SpanSort::Count,
+            SpanSort::Count => SpanSort::Name,
+            SpanSort::Name => SpanSort::P99,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            SpanSort::P99 => "Spans by p99, s to sort (count p50 p90 p99 max errors histogram)",
+            SpanSort::Count => "Spans by count, s to sort (count p50 p90 p99 max errors histogram)",
+            SpanSort::Name => "Spans by name, s to sort (count p50 p90 p99 max errors histogram)",
+        }
+    }
+
+    fn sort(self, stats: &mut [SpanStats]) {
+        match self {
+            SpanSort::P99 => stats.sort_unstable_by_key(|s| Reverse(s.p99)),
+            SpanSort::Count => stats.sort_unstable_by_key(|s| Reverse(s.count)),
+            SpanSort::Name => stats.sort_by(|a, b| (&a.name, &a.group).cmp(&(&b.name, &b.group))),
+        }
+    }
+}
+
+/// Latency percentiles of the spans of all the traces kept, by span name.
+pub struct SpansPageComponent {
+    id: ComponentId,
+    trace_graph: TraceGraphSnapshot,
+
+    group_by_bar: SearchBarComponent,
+    pub list: ListComponent<DynamicListViewModel<SpanStats>>,
+    details: DetailsComponent<SpanStats>,
+
+    /// The attribute spans are also grouped by, if any.
+    group_by: Option<String>,
+    sort: SpanSort,
+    /// The graph the list was last computed from, and when.
+    last_synced: Option<(Arc<TraceGraph>, Instant)>,
+
+    last_layout: RwLock<ComponentLayout>,
+    active_focus: RwLock<ComponentId>,
+}
+
+impl SpansPageComponent {
+    pub fn new(trace_graph: TraceGraphSnapshot) -> Self {
+        let sort = SpanSort::default();
+        Self {
+            id: ComponentId::SpansPage,
+            trace_graph,
+            group_by_bar: SearchBarComponent::with_title(
+                ComponentId::SearchBar,
+                "Group by attribute",
+            ),
+            list: ListComponent::new(
+                ComponentId::SpansList,
+                DynamicListViewModel::new(sort.title()),
+            ),
+            details: DetailsComponent::new(ComponentId::SpansDetails, "Span Stats"),
+            group_by: None,
+            sort,
+            last_synced: None,
+            last_layout: RwLock::new(HashMap::new()),
+            active_focus: RwLock::new(ComponentId::SpansList),
+        }
+    }
+
+    fn refresh(&mut self) {
+        let graph = self.trace_graph.load_full();
+        let mut stats = aggregate(&graph, self.group_by.as_deref());
+        self.sort.sort(&mut stats);
+        self.list.model.set_items(stats);
+        self.last_synced = Some((graph, Instant::now()));
+    }
+
+    fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.list.model.set_title(self.sort.title());
+        let mut stats = self.list.model.items().to_vec();
+        self.sort.sort(&mut stats);
+        self.list.model.set_items(stats);
+    }
+
+    /// Shows the trace of the slowest span of the selected stats.
+    fn show_slowest(&self) -> Vec<Action> {
+        let Some(stats) = self.list.model.selected_item() else {
+            return Vec::new();
+        };
+        let (trace_id, span_id) = stats.slowest;
+        vec![Action::ShowTrace(trace_id, Some(span_id))]
+    }
+
+    fn dispatch_to_child(
+        &mut self,
+        target_id: ComponentId,
+        event: &Event,
+        area: Rect,
+    ) -> Vec<Action> {
+        match target_id {
+            ComponentId::SearchBar => self.group_by_bar.handle_event(event, area),
+            ComponentId::SpansList => {
+                if let Event::Key(key) = event {
+                    match key.code {
+                        KeyCode::Char('s') => {
+                            self.cycle_sort();
+                            return Vec::new();
+                        }
+                        KeyCode::Enter => return self.show_slowest(),
+                        _ => {}
+                    }
+                }
+                self.list.model.set_height(area.height as usize);
+                self.list.handle_event(event, area)
+            }
+            ComponentId::SpansDetails => self.details.handle_event(event, area),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn handle_navigation(&mut self, direction: MoveFocus) -> Vec<Action> {
+        let layout = self.last_layout.read().unwrap();
+        let active_focus = *self.active_focus.read().unwrap();
+
+        if let Some(next) = find_next_focus(&layout, active_focus, direction) {
+            *self.active_focus.write().unwrap() = next;
+            return vec![Action::SetFocus(next)];
+        }
+
+        Vec::new()
+    }
+
+    pub fn calculate_layout(&self, area: Rect) -> ComponentLayout {
+        let spec = LayoutSpec {
+            direction: Direction::Vertical,
+            constraints: vec![
+                (Constraint::Length(3), Left(ComponentId::SearchBar)),
+                (
+                    Constraint::Fill(1),
+                    Right(LayoutSpec {
+                        direction: Direction::Horizontal,
+                        constraints: vec![
+                            (Constraint::Percentage(70), Left(ComponentId::SpansList)),
+                            (Constraint::Percentage(30), Left(ComponentId::SpansDetails)),
+                        ],
+                    }),
+                ),
+            ],
+        };
+
+        let mut layout = HashMap::new();
+        walk_layout(&mut layout, &spec, area);
+        layout
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let my_layout = self.calculate_layout(area);
+
+        {
+            let mut layout_guard = self.last_layout.write().unwrap();
+            *layout_guard = my_layout.clone();
+        }
+
+        let current_focus = *self.active_focus.read().unwrap();
+        if let Some(rect) = my_layout.get(&ComponentId::SearchBar) {
+            let is_focused = current_focus == ComponentId::SearchBar;
+            self.group_by_bar.render_focused(frame, *rect, is_focused);
+        }
+
+        if let Some(rect) = my_layout.get(&ComponentId::SpansList) {
+            let is_focused = current_focus == ComponentId::SpansList;
+            self.list.render_focused(frame, *rect, is_focused);
+        }
+
+        if let Some(rect) = my_layout.get(&ComponentId::SpansDetails) {
+            let is_focused = current_focus == ComponentId::SpansDetails;
+            self.details.render_with_data(
+                frame,
+                *rect,
+                is_focused,
+                self.list.model.selected_item(),
+            );
+        }
+    }
+}
+
+impl Component for SpansPageComponent {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn handle_event(&mut self, event: &Event, area: Rect) -> Vec<Action> {
+        let layout = self.last_layout.read().unwrap().clone();
+        let mut active_focus = *self.active_focus.read().unwrap();
+
+        let mut actions = handle_container_event(
+            &layout,
+            &mut active_focus,
+            event,
+            area,
+            |target_id, ev, child_area| self.dispatch_to_child(target_id, ev, child_area),
+        );
+
+        // Intercept SubmitSearch from the group by bar so it doesn't bubble to App
+        if let Some(pos) = actions
+            .iter()
+            .position(|a| matches!(a, Action::SubmitSearch(_)))
+            && let Action::SubmitSearch(key) = actions.remove(pos)
+        {
+            let key = key.trim();
+            self.group_by = (!key.is_empty()).then(|| key.to_string());
+            self.refresh();
+        }
+
+        *self.active_focus.write().unwrap() = active_focus;
+        actions
+    }
+
+    fn tick(&mut self) -> Vec<Action> {
+        let stale = match &self.last_synced {
+            Some((graph, at)) => {
+                !Arc::ptr_eq(graph, &self.trace_graph.load()) && at.elapsed() >= REFRESH_INTERVAL
+            }
+            None => true,
+        };
+        if stale {
+            self.refresh();
+        }
+        Vec::new()
+    }
+}
@@ -21,6 +21,7 @@ where
 {
     id: ComponentId,
     border: bool,
+    title: Option<String>,
     pub cursor: Cursor<T>,
     _phantom: PhantomData<T>,
 }
@@ -33,15 +34,27 @@ where
         Self {
             id,
             border,
+            title: None,
             cursor: Cursor::new(T::iter().collect()).expect("TabsComponent must have options"),
             _phantom: PhantomData,
         }
     }
 
+    /// Sets the title shown on the border, if any.
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+    }
+
     pub fn selected(&self) -> T {
         *self.cursor.current()
     }
 
+    pub fn select(&mut self, option: T) {
+        if let Some(index) = self.cursor.iter().position(|o| *o == option) {
+            self.cursor.select_index(index);
+        }
+    }
+
     /// Determines which tab was clicked based on the column and updates the
     /// cursor. Returns true if a tab was selected, false otherwise.
     pub fn select_by_column(&mut self, area: Rect, column: u16) -> bool {
@@ -87,6 +100,9 @@ where
         if self.border {
             block = block.borders(Borders::ALL);
         }
+        if let Some(title) = &self.title {
+            block = block.title(title.as_str());
+        }
 
         if is_focused {
             block = block
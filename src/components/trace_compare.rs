@@ -0,0 +1,242 @@
+use crate::{
+    components::Component,
+    otel::{
+        TreeBounds,
+        compare::{AlignedSpan, DurationDelta, align},
+        graph::TraceGraph,
+        id::TraceId,
+        span_ext::SpanExt,
+    },
+    states::{Action, ComponentId},
+    ui::format_duration,
+    view::span_bar::SpanBarRenderer,
+};
+use crossterm::event::{Event, KeyCode, MouseButton, MouseEventKind};
+use opentelemetry_proto::tonic::trace::v1::Span as OtelSpan;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+use std::{any::Any, sync::Arc};
+
+/// The width of the delta column.
+const DELTA_WIDTH: usize = 12;
+
+/// Two traces side by side, their spans aligned by place in the tree. Each
+/// row shows how much the duration of a span changed, or whether it is
+/// missing from the other trace or extra in it, next to the bars of both.
+pub struct TraceCompareComponent {
+    id: ComponentId,
+    /// The graph when the comparison was opened, so that it doesn't change
+    /// under the user.
+    graph: Arc<TraceGraph>,
+    baseline: TraceId,
+    other: TraceId,
+    rows: Vec<AlignedSpan>,
+    selected: usize,
+    offset: usize,
+}
+
+impl TraceCompareComponent {
+    pub fn new(id: ComponentId, graph: Arc<TraceGraph>, baseline: TraceId, other: TraceId) -> Self {
+        let rows = align(&graph, &baseline, &other);
+        Self {
+            id,
+            graph,
+            baseline,
+            other,
+            rows,
+            selected: 0,
+            offset: 0,
+        }
+    }
+
+    pub fn selected_row(&self) -> Option<&AlignedSpan> {
+        self.rows.get(self.selected)
+    }
+
+    /// Selects a row, scrolling it into a view of `height` rows.
+    fn select(&mut self, index: usize, height: usize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        self.selected = index.min(self.rows.len() - 1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+    }
+
+    pub fn render_focused(&self, f: &mut Frame, area: Rect, is_focused: bool) {
+        let mut block = Block::default()
+            .title("Comparison (baseline | other, d to close)")
+            .borders(Borders::ALL);
+        if is_focused {
+            block = block
+                .border_style(Style::default().fg(Color::Blue))
+                .title_style(Style::default().fg(Color::White));
+        }
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let [text_area, baseline_area, other_area] = Layout::horizontal([
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ])
+        .areas(inner);
+
+        let selected = self.selected_row();
+        let (baseline_bounds, other_bounds) =
+            shared_bounds(&self.graph, &self.baseline, &self.other);
+        let baseline_bars = baseline_bounds.as_ref().and_then(|bounds| {
+            let focused = selected.and_then(|row| row.baseline.as_ref());
+            SpanBarRenderer::new(
+                &self.graph,
+                focused.map(|span| span.span_id()),
+                bounds,
+                baseline_area.width.saturating_sub(1) as usize,
+                None,
+            )
+            .ok()
+        });
+        let other_bars = other_bounds.as_ref().and_then(|bounds| {
+            let focused = selected.and_then(|row| row.other.as_ref());
+            SpanBarRenderer::new(
+                &self.graph,
+                focused.map(|span| span.span_id()),
+                bounds,
+                other_area.width.saturating_sub(1) as usize,
+                None,
+            )
+            .ok()
+        });
+
+        let mut text_lines = Vec::new();
+        let mut baseline_lines = Vec::new();
+        let mut other_lines = Vec::new();
+        for (index, row) in self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(inner.height as usize)
+        {
+            let mut line = get_row_line(row, text_area.width as usize);
+            if index == self.selected {
+                line = line.patch_style(Style::default().bg(Color::DarkGray));
+            }
+            text_lines.push(line);
+            baseline_lines.push(get_bar_line(baseline_bars.as_ref(), row.baseline.as_ref()));
+            other_lines.push(get_bar_line(other_bars.as_ref(), row.other.as_ref()));
+        }
+
+        f.render_widget(Paragraph::new(text_lines), text_area);
+        f.render_widget(Paragraph::new(baseline_lines), baseline_area);
+        f.render_widget(Paragraph::new(other_lines), other_area);
+    }
+}
+
+impl Component for TraceCompareComponent {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn handle_event(&mut self, event: &Event, area: Rect) -> Vec<Action> {
+        let height = area.height.saturating_sub(2) as usize;
+        match event {
+            Event::Key(key) => match key.code {
+                KeyCode::Up => self.select(self.selected.saturating_sub(1), height),
+                KeyCode::Down => self.select(self.selected + 1, height),
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => self.select(self.selected.saturating_sub(1), height),
+                MouseEventKind::ScrollDown => self.select(self.selected + 1, height),
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let relative_row = mouse.row.saturating_sub(area.y + 1) as usize;
+                    if self.offset + relative_row < self.rows.len() {
+                        self.select(self.offset + relative_row, height);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        Vec::new()
+    }
+}
+
+fn trace_bounds(graph: &TraceGraph, trace_id: &TraceId) -> Option<TreeBounds> {
+    let meta = graph.traces.get(trace_id)?;
+    Some(TreeBounds {
+        start: meta.start_time()?,
+        end: meta.end_time(graph)?,
+    })
+}
+
+/// The bounds of both traces on one time scale, so that bars of the same
+/// length last as long: each starts with its trace and lasts as long as the
+/// longer one.
+fn shared_bounds(
+    graph: &TraceGraph,
+    baseline: &TraceId,
+    other: &TraceId,
+) -> (Option<TreeBounds>, Option<TreeBounds>) {
+    let baseline = trace_bounds(graph, baseline);
+    let other = trace_bounds(graph, other);
+    let duration = baseline
+        .iter()
+        .chain(&other)
+        .map(TreeBounds::duration)
+        .max()
+        .unwrap_or_default();
+    let scaled = |bounds: Option<TreeBounds>| {
+        bounds.map(|bounds| TreeBounds {
+            start: bounds.start,
+            end: bounds.start + duration,
+        })
+    };
+    (scaled(baseline), scaled(other))
+}
+
+/// Gets the indented name of a row and how its duration changed.
+fn get_row_line(row: &AlignedSpan, width: usize) -> Line<'static> {
+    let (delta, style) = match (row.delta(), &row.other) {
+        (Some(DurationDelta::Shorter(delta)), _) => (
+            format!("-{}", format_duration(delta)),
+            Style::default().fg(Color::Green),
+        ),
+        (Some(DurationDelta::Longer(delta)), _) => (
+            format!("+{}", format_duration(delta)),
+            Style::default().fg(Color::Red),
+        ),
+        (None, None) => ("missing".to_string(), Style::default().fg(Color::Yellow)),
+        (None, Some(_)) => ("extra".to_string(), Style::default().fg(Color::Cyan)),
+    };
+    let name_width = width.saturating_sub(DELTA_WIDTH + 1);
+    let name: String = format!("{}{}", "  ".repeat(row.depth), row.name)
+        .chars()
+        .take(name_width)
+        .collect();
+    Line::from(vec![
+        Span::raw(format!("{:<width$} ", name, width = name_width)),
+        Span::styled(format!("{:>width$}", delta, width = DELTA_WIDTH), style),
+    ])
+}
+
+/// Gets the bar of one side of a row, empty when the span is on the other
+/// side only.
+fn get_bar_line(renderer: Option<&SpanBarRenderer>, span: Option<&Arc<OtelSpan>>) -> Line<'static> {
+    renderer
+        .zip(span)
+        .and_then(|(renderer, span)| renderer.render(&span.span_id(), false).ok())
+        .unwrap_or_default()
+}
@@ -1,33 +1,196 @@
 use crate::{
     components::Component,
-    otel::id::TraceId,
+    otel::{TraceMeta, filter::TraceFilter, graph::TraceGraph, id::TraceId, summary::TraceSummary},
     states::{Action, ComponentId},
     viewmodel::dynamic_list::DynamicListViewModel,
 };
 use crossterm::event::{Event, KeyCode, MouseButton, MouseEventKind};
 use ratatui::{Frame, layout::Rect};
-use std::any::Any;
+use std::{any::Any, cmp::Reverse, collections::HashMap, sync::Arc};
+
+/// The order of the trace list, cycled with `s`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TraceSort {
+    #[default]
+    Newest,
+    Oldest,
+    Slowest,
+}
+
+impl TraceSort {
+    fn next(self) -> Self {
+        match self {
+            TraceSort::Newest => TraceSort::Oldest,
+            TraceSort::Oldest => TraceSort::Slowest,
+            TraceSort::Slowest => TraceSort::Newest,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            TraceSort::Newest => "Traces (newest first, s to sort, m to mark)",
+            TraceSort::Oldest => "Traces (oldest first, s to sort, m to mark)",
+            TraceSort::Slowest => "Traces (slowest first, s to sort, m to mark)",
+        }
+    }
+
+    fn sort(self, summaries: &mut [TraceSummary]) {
+        match self {
+            TraceSort::Newest => summaries.sort_unstable_by_key(|s| Reverse(s.start)),
+            TraceSort::Oldest => summaries.sort_unstable_by_key(|s| s.start),
+            TraceSort::Slowest => summaries.sort_unstable_by_key(|s| Reverse(s.duration)),
+        }
+    }
+}
+
+/// The summary of a trace and whether it matches the filter, kept until the
+/// trace changes.
+struct CachedSummary {
+    /// The graph replaces the TraceMeta of a trace whenever a span is added to
+    /// it, so the trace changed when this isn't the one in the graph anymore.
+    meta: Arc<TraceMeta>,
+    summary: TraceSummary,
+    matches: bool,
+}
 
 pub struct TraceListComponent {
     id: ComponentId,
     // TODO: Should this be a ListComponent?
-    list: DynamicListViewModel<TraceId>,
+    list: DynamicListViewModel<TraceSummary>,
+    filter: TraceFilter,
+    sort: TraceSort,
+    /// The traces marked for comparison with `m`, at most two.
+    marked: Vec<TraceId>,
+    summaries: HashMap<TraceId, CachedSummary>,
 }
 
 impl TraceListComponent {
     pub fn new(id: ComponentId) -> Self {
+        let sort = TraceSort::default();
         Self {
             id,
-            list: DynamicListViewModel::new("Traces"),
+            list: DynamicListViewModel::new(sort.title()),
+            filter: TraceFilter::default(),
+            sort,
+            marked: Vec::new(),
+            summaries: HashMap::new(),
         }
     }
 
-    pub fn sync_state(&mut self, new_trace_ids: Vec<TraceId>) {
-        self.list.set_items(new_trace_ids);
+    /// Lists the traces of the graph matching the filter, summarizing only
+    /// the traces that are new or changed since the last time.
+    pub fn sync_state(&mut self, graph: &TraceGraph) {
+        self.marked
+            .retain(|trace_id| graph.traces.contains_key(trace_id));
+        let mut cache = HashMap::with_capacity(graph.traces.len());
+        for (trace_id, meta) in graph.traces.iter() {
+            let cached = match self.summaries.remove(trace_id) {
+                Some(cached) if Arc::ptr_eq(&cached.meta, meta) => cached,
+                _ => {
+                    let Some(summary) = TraceSummary::new(graph, *trace_id) else {
+                        continue;
+                    };
+                    CachedSummary {
+                        meta: meta.clone(),
+                        matches: self.filter.matches(graph, &summary),
+                        summary,
+                    }
+                }
+            };
+            cache.insert(*trace_id, cached);
+        }
+        self.summaries = cache;
+
+        let mut summaries: Vec<_> = self
+            .summaries
+            .values()
+            .filter(|cached| cached.matches)
+            .map(|cached| TraceSummary {
+                marked: self.marked.contains(&cached.summary.trace_id),
+                ..cached.summary.clone()
+            })
+            .collect();
+        self.sort.sort(&mut summaries);
+        self.list.set_items(summaries);
+    }
+
+    pub fn set_filter(&mut self, filter: TraceFilter, graph: &TraceGraph) {
+        self.filter = filter;
+        // The traces that changed are filtered again by the sync
+        for cached in self.summaries.values_mut() {
+            cached.matches = self.filter.matches(graph, &cached.summary);
+        }
+        self.sync_state(graph);
+    }
+
+    fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.list.set_title(self.sort.title());
+        let mut summaries = self.list.items().to_vec();
+        self.sort.sort(&mut summaries);
+        self.list.set_items(summaries);
+    }
+
+    /// Marks the selected trace for comparison, or unmarks it. Marking a
+    /// third trace unmarks the first one.
+    fn toggle_mark(&mut self) {
+        let Some(trace_id) = self.selected_item().copied() else {
+            return;
+        };
+        if let Some(index) = self.marked.iter().position(|id| *id == trace_id) {
+            self.marked.remove(index);
+        } else {
+            if self.marked.len() == 2 {
+                self.marked.remove(0);
+            }
+            self.marked.push(trace_id);
+        }
+        let summaries = self
+            .list
+            .items()
+            .iter()
+            .map(|summary| TraceSummary {
+                marked: self.marked.contains(&summary.trace_id),
+                ..summary.clone()
+            })
+            .collect();
+        self.list.set_items(summaries);
+    }
+
+    /// The traces marked for comparison, once there are two.
+    pub fn marked_pair(&self) -> Option<(TraceId, TraceId)> {
+        match self.marked[..] {
+            [baseline, other] => Some((baseline, other)),
+            _ => None,
+        }
+    }
+
+    /// The trace of the list that started last, whatever the order.
+    pub fn newest(&self) -> Option<TraceId> {
+        self.list
+            .items()
+            .iter()
+            .max_by_key(|summary| summary.start)
+            .map(|summary| summary.trace_id)
     }
 
     pub fn selected_item(&self) -> Option<&TraceId> {
-        self.list.selected_item()
+        self.list.selected_item().map(|summary| &summary.trace_id)
+    }
+
+    pub fn select(&mut self, trace_id: &TraceId) -> bool {
+        match self
+            .list
+            .items()
+            .iter()
+            .position(|summary| summary.trace_id == *trace_id)
+        {
+            Some(index) => {
+                self.list.select_index(index);
+                true
+            }
+            None => false,
+        }
     }
 
     pub fn handle_click(&mut self, area: Rect, row: u16, _col: u16) -> Vec<Action> {
@@ -63,6 +226,12 @@ impl Component for TraceListComponent {
                 KeyCode::Down => {
                     self.list.cursor_next();
                 }
+                KeyCode::Char('s') => {
+                    self.cycle_sort();
+                }
+                KeyCode::Char('m') => {
+                    self.toggle_mark();
+                }
                 _ => {}
             },
 
@@ -1,6 +1,12 @@
 #![allow(dead_code)] // Remove this once you start using the code
 
-use std::{collections::HashMap, env, path::PathBuf};
+use std::{
+    collections::HashMap,
+    env,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    time::Duration,
+};
 
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -11,7 +17,7 @@ use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, de::Deserializer};
 use tracing::error;
 
-use crate::{app::Mode, states::Action};
+use crate::{app::Mode, cli::CollectorArgs, states::Action};
 
 const CONFIG: &str = include_str!("../.config/config.json5");
 
@@ -31,6 +37,111 @@ pub struct Config {
     pub keybindings: KeyBindings,
     #[serde(default)]
     pub styles: Styles,
+    #[serde(default)]
+    pub collector: CollectorConfig,
+}
+
+/// Where the OTEL receivers listen, and how much trace data is kept. Both the
+/// gRPC and the HTTP receivers accept traces and metrics.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CollectorConfig {
+    /// Binds the receivers to 127.0.0.1 rather than to all interfaces.
+    pub localhost_only: bool,
+    /// Whether the OTLP/gRPC receiver is started.
+    pub grpc: bool,
+    pub grpc_port: u16,
+    /// Whether the OTLP/HTTP receiver is started.
+    pub http: bool,
+    pub http_port: u16,
+    /// How long a trace is kept after it started.
+    pub trace_retention_secs: u64,
+    /// How many span batches can wait to be processed.
+    pub trace_queue_capacity: usize,
+    /// The oldest traces and orphans are evicted past this many spans.
+    pub max_spans: usize,
+    /// The oldest log records are dropped past this many.
+    pub max_logs: usize,
+}
+
+impl Default for CollectorConfig {
+    fn default() -> Self {
+        Self {
+            localhost_only: false,
+            grpc: true,
+            grpc_port: 4317,
+            http: true,
+            http_port: 4318,
+            trace_retention_secs: 10 * 60,
+            trace_queue_capacity: 10_000,
+            max_spans: 1_000_000,
+            max_logs: 10_000,
+        }
+    }
+}
+
+impl CollectorConfig {
+    /// Loads the collector section of the config, overridden by flags and
+    /// env vars.
+    pub fn load(args: &CollectorArgs) -> Result<Self> {
+        let mut config = Config::new()?.collector;
+        config.apply(args);
+        Ok(config)
+    }
+
+    fn apply(&mut self, args: &CollectorArgs) {
+        if let Some(localhost_only) = args.localhost_only() {
+            self.localhost_only = localhost_only;
+        }
+        if let Some(grpc) = args.grpc() {
+            self.grpc = grpc;
+        }
+        if let Some(http) = args.http() {
+            self.http = http;
+        }
+        if let Some(port) = args.grpc_port {
+            self.grpc_port = port;
+        }
+        if let Some(port) = args.http_port {
+            self.http_port = port;
+        }
+        if let Some(secs) = args.trace_retention_secs {
+            self.trace_retention_secs = secs;
+        }
+        if let Some(capacity) = args.trace_queue_capacity {
+            self.trace_queue_capacity = capacity;
+        }
+        if let Some(max_spans) = args.max_spans {
+            self.max_spans = max_spans;
+        }
+        if let Some(max_logs) = args.max_logs {
+            self.max_logs = max_logs;
+        }
+    }
+
+    fn ip(&self) -> IpAddr {
+        if self.localhost_only {
+            Ipv4Addr::LOCALHOST.into()
+        } else {
+            Ipv4Addr::UNSPECIFIED.into()
+        }
+    }
+
+    /// The gRPC receiver address, `None` when it is disabled.
+    pub fn grpc_addr(&self) -> Option<SocketAddr> {
+        self.grpc
+            .then(|| SocketAddr::new(self.ip(), self.grpc_port))
+    }
+
+    /// The HTTP receiver address, `None` when it is disabled.
+    pub fn http_addr(&self) -> Option<SocketAddr> {
+        self.http
+            .then(|| SocketAddr::new(self.ip(), self.http_port))
+    }
+
+    pub fn trace_retention(&self) -> Duration {
+        Duration::from_secs(self.trace_retention_secs)
+    }
 }
 
 lazy_static! {
@@ -449,6 +560,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::{export::ExportScope, states::ExportFormat};
 
     #[test]
     fn test_parse_style_default() {
@@ -525,6 +637,59 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_config_export() -> Result<()> {
+        let c = Config::new()?;
+        assert_eq!(
+            c.keybindings
+                .get(&Mode::Home)
+                .unwrap()
+                .get(&parse_key_sequence("<Alt-e>").unwrap_or_default())
+                .unwrap(),
+            &Action::ExportSelected(ExportFormat::Cbor)
+        );
+        assert_eq!(
+            c.keybindings
+                .get(&Mode::Home)
+                .unwrap()
+                .get(&parse_key_sequence("<Alt-t>").unwrap_or_default())
+                .unwrap(),
+            &Action::ExportChromeTrace(ExportScope::List)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collector_overrides() {
+        let mut config: CollectorConfig =
+            json5::from_str(r#"{ "grpc_port": 14317, "http": false }"#).unwrap();
+        assert_eq!(config.http_addr(), None);
+
+        config.apply(&CollectorArgs {
+            localhost_only: true,
+            max_spans: Some(10),
+            ..Default::default()
+        });
+        assert_eq!(
+            config.grpc_addr(),
+            Some(SocketAddr::from(([127, 0, 0, 1], 14317)))
+        );
+        assert_eq!(config.max_spans, 10);
+        assert_eq!(config.trace_queue_capacity, 10_000);
+
+        config.apply(&CollectorArgs {
+            no_localhost_only: true,
+            http: true,
+            no_grpc: true,
+            ..Default::default()
+        });
+        assert_eq!(config.grpc_addr(), None);
+        assert_eq!(
+            config.http_addr(),
+            Some(SocketAddr::from(([0, 0, 0, 0], 4318)))
+        );
+    }
+
     #[test]
     fn test_simple_keys() {
         assert_eq!(
@@ -0,0 +1,191 @@
+use crate::{
+    config::get_data_dir,
+    json::{ToJson, chrome_trace::chrome_trace},
+    model::ledger_diff::DiffEntry,
+    otel::{graph::TraceGraph, id::TraceId},
+    states::ExportFormat,
+};
+use amaru_consensus::BlockHeader;
+use amaru_kernel::RawBlock;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use opentelemetry_proto::tonic::trace::v1::Span;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Borrow,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+/// The CBOR counterpart of `ToJson`: the bytes of an entity as amaru stores
+/// them, for entities that have such a form. Ledger entries are read as they
+/// are stored instead, see `RawLedger`.
+pub trait ToCbor {
+    fn to_cbor(&self) -> Result<Vec<u8>>;
+}
+
+impl ToCbor for BlockHeader {
+    fn to_cbor(&self) -> Result<Vec<u8>> {
+        minicbor::to_vec(self).map_err(|e| anyhow!("Can't encode header: {}", e))
+    }
+}
+
+impl ToCbor for RawBlock {
+    fn to_cbor(&self) -> Result<Vec<u8>> {
+        Ok(self.as_ref().to_vec())
+    }
+}
+
+impl ToCbor for DiffEntry {
+    fn to_cbor(&self) -> Result<Vec<u8>> {
+        Err(anyhow!("Ledger changes can only be exported as JSON"))
+    }
+}
+
+impl ToCbor for Span {
+    fn to_cbor(&self) -> Result<Vec<u8>> {
+        Err(anyhow!("Spans can only be exported as JSON"))
+    }
+}
+
+/// What to export from the focused part of a page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportScope {
+    Selected,
+    List,
+}
+
+/// Writes the selected item, or the whole list, to a new file in the
+/// `exports` dir of the data dir and returns its path.
+///
+/// The items of the list are only gathered when the list is exported, and
+/// written as they come: as a JSON array, or as a CBOR sequence (RFC 8742).
+pub fn export<T, I>(
+    name: &str,
+    scope: ExportScope,
+    format: ExportFormat,
+    selected: Option<&T>,
+    items: impl FnOnce() -> I,
+) -> Result<PathBuf>
+where
+    T: ToJson + ToCbor,
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+{
+    match format {
+        ExportFormat::Json => export_json(name, scope, selected, items),
+        ExportFormat::Cbor => export_cbor(
+            name,
+            scope,
+            || {
+                selected
+                    .ok_or_else(|| anyhow!("Nothing selected"))?
+                    .to_cbor()
+            },
+            || items().into_iter().map(|item| item.borrow().to_cbor()),
+        ),
+    }
+}
+
+/// Exports as JSON, see `export`.
+pub fn export_json<T, I>(
+    name: &str,
+    scope: ExportScope,
+    selected: Option<&T>,
+    items: impl FnOnce() -> I,
+) -> Result<PathBuf>
+where
+    T: ToJson,
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+{
+    match scope {
+        ExportScope::Selected => {
+            let item = selected.ok_or_else(|| anyhow!("Nothing selected"))?;
+            write_export(name, scope, "json", |out| {
+                serde_json::to_writer_pretty(out, &item.to_json()).map_err(Into::into)
+            })
+        }
+        ExportScope::List => write_export(name, scope, "json", |out| {
+            out.write_all(b"[")?;
+            for (i, item) in items().into_iter().enumerate() {
+                let separator: &[u8] = if i == 0 { b"\n  " } else { b",\n  " };
+                out.write_all(separator)?;
+                serde_json::to_writer(&mut *out, &item.borrow().to_json())?;
+            }
+            out.write_all(b"\n]\n")?;
+            Ok(())
+        }),
+    }
+}
+
+/// Exports CBOR items as they are, see `export`.
+pub fn export_cbor<I>(
+    name: &str,
+    scope: ExportScope,
+    selected: impl FnOnce() -> Result<Vec<u8>>,
+    items: impl FnOnce() -> I,
+) -> Result<PathBuf>
+where
+    I: IntoIterator<Item = Result<Vec<u8>>>,
+{
+    match scope {
+        ExportScope::Selected => {
+            let bytes = selected()?;
+            write_export(name, scope, "cbor", |out| Ok(out.write_all(&bytes)?))
+        }
+        ExportScope::List => write_export(name, scope, "cbor", |out| {
+            for item in items() {
+                out.write_all(&item?)?;
+            }
+            Ok(())
+        }),
+    }
+}
+
+/// Writes the selected trace, or all of them, in the Chrome Trace Event
+/// format, see `export`.
+pub fn export_chrome_trace(
+    scope: ExportScope,
+    graph: &TraceGraph,
+    trace_ids: &[TraceId],
+) -> Result<PathBuf> {
+    if trace_ids.is_empty() {
+        return Err(anyhow!("No trace to export"));
+    }
+    write_export("chrome-trace", scope, "json", |out| {
+        serde_json::to_writer(out, &chrome_trace(graph, trace_ids)).map_err(Into::into)
+    })
+}
+
+/// Creates the export file and writes it with `write`, removing it if that
+/// fails half way.
+fn write_export(
+    name: &str,
+    scope: ExportScope,
+    extension: &str,
+    write: impl FnOnce(&mut dyn Write) -> Result<()>,
+) -> Result<PathBuf> {
+    let dir = get_data_dir().join("exports");
+    fs::create_dir_all(&dir)?;
+    let kind = match scope {
+        ExportScope::Selected => "item",
+        ExportScope::List => "list",
+    };
+    let path = dir.join(format!(
+        "{}-{}-{}.{}",
+        name,
+        kind,
+        Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+        extension
+    ));
+    let mut out = BufWriter::new(File::create(&path)?);
+    let res = write(&mut out).and_then(|_| out.flush().map_err(Into::into));
+    if let Err(e) = res {
+        drop(out);
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+    Ok(path)
+}
@@ -0,0 +1,23 @@
+use crate::{
+    cli::LedgerEntity,
+    headless::write_json_lines,
+    store::owned_iter::{
+        OwnedAccountIter, OwnedBlockIssuerIter, OwnedDRepIter, OwnedPoolIter, OwnedProposalIter,
+        OwnedUtxoIter,
+    },
+};
+use amaru_stores::rocksdb::ReadOnlyRocksDB;
+use anyhow::Result;
+use std::sync::Arc;
+
+/// Streams every entry of a ledger table to stdout.
+pub fn dump(db: Arc<ReadOnlyRocksDB>, entity: LedgerEntity, limit: Option<usize>) -> Result<()> {
+    match entity {
+        LedgerEntity::Accounts => write_json_lines(OwnedAccountIter::new(db), limit),
+        LedgerEntity::BlockIssuers => write_json_lines(OwnedBlockIssuerIter::new(db), limit),
+        LedgerEntity::Dreps => write_json_lines(OwnedDRepIter::new(db), limit),
+        LedgerEntity::Pools => write_json_lines(OwnedPoolIter::new(db), limit),
+        LedgerEntity::Proposals => write_json_lines(OwnedProposalIter::new(db), limit),
+        LedgerEntity::Utxos => write_json_lines(OwnedUtxoIter::new(db), limit),
+    }
+}
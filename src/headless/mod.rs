@@ -0,0 +1,75 @@
+use crate::{
+    cli::{Cli, Command},
+    json::ToJson,
+    ledger_db_path,
+    model::ledger_diff::diff_snapshots,
+    open_ledger_db, open_ledger_snapshot,
+    store::utxo_index::UtxoIndex,
+};
+use amaru_kernel::Epoch;
+use anyhow::{Result, anyhow};
+use std::{
+    io::{self, BufWriter, ErrorKind, Write},
+    sync::{Arc, mpsc},
+    thread,
+};
+
+pub mod ledger;
+pub mod traces;
+
+/// Runs a headless (non-TUI) command.
+pub fn run(args: &Cli, command: &Command) -> Result<()> {
+    match command {
+        Command::Ledger { entity, limit } => {
+            let db = Arc::new(open_ledger_db(&args.ledger_db, &args.network)?);
+            ledger::dump(db, *entity, *limit)
+        }
+        Command::UtxoIndex => {
+            let db = open_ledger_db(&args.ledger_db, &args.network)?;
+            let dir = UtxoIndex::build(&db)?;
+            println!("{}", dir.display());
+            Ok(())
+        }
+        Command::LedgerDiff { from, to, limit } => {
+            let path = ledger_db_path(&args.ledger_db, &args.network);
+            let before = open_ledger_snapshot(&path, Epoch::from(*from))?;
+            let after = open_ledger_snapshot(&path, Epoch::from(*to))?;
+
+            // Entries are written as they are found; once stdout is done the
+            // receiver is dropped, which stops the diff
+            let (tx, rx) = mpsc::sync_channel(100);
+            let diff = thread::spawn(move || {
+                diff_snapshots(&before, &after, |entry| tx.send(entry).is_ok())
+            });
+            write_json_lines(rx.into_iter(), *limit)?;
+            diff.join().map_err(|_| anyhow!("Snapshot diff panicked"))?
+        }
+        Command::ChromeTrace { recording, filter } => {
+            traces::chrome_trace(recording, filter.as_deref())
+        }
+    }
+}
+
+/// Writes each item as a single JSON line to stdout.
+///
+/// A closed stdout (e.g. piping into `head`) is not treated as an error.
+pub(crate) fn write_json_lines<T: ToJson>(
+    items: impl Iterator<Item = T>,
+    limit: Option<usize>,
+) -> Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let res = items
+        .take(limit.unwrap_or(usize::MAX))
+        .try_for_each(|item| -> io::Result<()> {
+            serde_json::to_writer(&mut out, &item.to_json())?;
+            out.write_all(b"\n")
+        })
+        .and_then(|_| out.flush());
+
+    match res {
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        res => res.map_err(Into::into),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    json::chrome_trace,
+    otel::{
+        TraceRetention, filter::TraceFilter, recording::read_recording, store::TraceStore,
+        summary::TraceSummary,
+    },
+};
+use anyhow::Result;
+use std::{
+    io::{self, BufWriter, ErrorKind, Write},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+use tracing::warn;
+
+/// Writes the traces of a recording matching `filter` to stdout, in the
+/// Chrome Trace Event format and by start time. Spans whose parent isn't in
+/// the recording are left out.
+pub fn chrome_trace(recording: &Path, filter: Option<&str>) -> Result<()> {
+    let filter = filter
+        .map(TraceFilter::from_str)
+        .transpose()?
+        .unwrap_or_default();
+
+    let mut store = TraceStore::new(TraceRetention {
+        expire_after: Duration::MAX,
+        max_spans: usize::MAX,
+    });
+    for request in read_recording(recording)? {
+        store.add_spans(
+            request
+                .resource_spans
+                .into_iter()
+                .flat_map(|r_spans| r_spans.scope_spans)
+                .flat_map(|s_spans| s_spans.spans)
+                .collect(),
+        );
+    }
+    let orphans: usize = store
+        .orphanage()
+        .groups()
+        .iter()
+        .map(|group| group.span_count)
+        .sum();
+    if orphans > 0 {
+        warn!(
+            "Leaving out {} spans whose parent isn't in the recording",
+            orphans
+        );
+    }
+
+    let graph = store.graph();
+    let mut summaries: Vec<TraceSummary> = graph
+        .traces
+        .keys()
+        .filter_map(|trace_id| TraceSummary::new(graph, *trace_id))
+        .filter(|summary| filter.matches(graph, summary))
+        .collect();
+    summaries.sort_unstable_by_key(|summary| summary.start);
+    let trace_ids: Vec<_> = summaries.iter().map(|summary| summary.trace_id).collect();
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let res = serde_json::to_writer(&mut out, &chrome_trace::chrome_trace(graph, &trace_ids))
+        .map_err(io::Error::from)
+        .and_then(|_| out.flush());
+    match res {
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        res => res.map_err(Into::into),
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    json::ToJson,
+    ui::{to_list_item::AccountItem, to_rich::account::StakeCredentialDisplay},
+};
+use amaru_kernel::DRep;
+use serde_json::{Value, json};
+
+impl ToJson for AccountItem {
+    fn to_json(&self) -> Value {
+        let (credential, row) = self;
+        json!({
+            "credential": StakeCredentialDisplay(credential).to_string(),
+            "pool": row.pool.as_ref().map(|(pool_id, ptr)| json!({
+                "id": pool_id.to_string(),
+                "pointer": ptr.to_json(),
+            })),
+            "deposit": row.deposit,
+            "drep": row.drep.as_ref().map(|(drep, ptr)| json!({
+                "drep": drep.to_json(),
+                "pointer": ptr.to_json(),
+            })),
+            "rewards": row.rewards,
+        })
+    }
+}
+
+impl ToJson for DRep {
+    fn to_json(&self) -> Value {
+        match self {
+            DRep::Key(h) => json!({ "key": h.to_string() }),
+            DRep::Script(h) => json!({ "script": h.to_string() }),
+            DRep::Abstain => json!("abstain"),
+            DRep::NoConfidence => json!("no_confidence"),
+        }
+    }
+}
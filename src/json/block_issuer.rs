@@ -0,0 +1,14 @@
+use crate::{
+    json::ToJson,
+    ui::{to_list_item::BlockIssuerItem, to_rich::pool::PoolIdDisplay},
+};
+use serde_json::{Value, json};
+
+impl ToJson for BlockIssuerItem {
+    fn to_json(&self) -> Value {
+        json!({
+            "slot": self.0.to_string(),
+            "slot_leader": PoolIdDisplay(self.1.slot_leader).to_string(),
+        })
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    json::{ToJson, proposal::gov_action_type},
+    model::block_body::{BlockTransaction, WitnessCounts},
+    ui::to_rich::{
+        account::StakeCredentialDisplay, block::VoterDisplay, proposal::ProposalIdDisplay,
+        utxo::TransactionInputDisplay,
+    },
+};
+use amaru_consensus::{BlockHeader, IsHeader};
+use amaru_kernel::{RawBlock, StakeCredential, TransactionInput};
+use pallas_primitives::conway::{Certificate, Vote};
+use serde_json::{Value, json};
+
+fn inputs_json(inputs: &[TransactionInput]) -> Value {
+    inputs
+        .iter()
+        .map(|input| json!(TransactionInputDisplay(input).to_string()))
+        .collect()
+}
+
+fn credential_json(credential: &StakeCredential) -> Value {
+    json!(StakeCredentialDisplay(credential).to_string())
+}
+
+impl ToJson for Certificate {
+    fn to_json(&self) -> Value {
+        match self {
+            Certificate::StakeRegistration(credential) => json!({
+                "type": "stake_registration",
+                "credential": credential_json(credential),
+            }),
+            Certificate::StakeDeregistration(credential) => json!({
+                "type": "stake_deregistration",
+                "credential": credential_json(credential),
+            }),
+            Certificate::StakeDelegation(credential, pool) => json!({
+                "type": "stake_delegation",
+                "credential": credential_json(credential),
+                "pool": pool.to_string(),
+            }),
+            Certificate::PoolRegistration {
+                operator,
+                pledge,
+                cost,
+                reward_account,
+                ..
+            } => json!({
+                "type": "pool_registration",
+                "pool": operator.to_string(),
+                "pledge": pledge,
+                "cost": cost,
+                "reward_account": reward_account.to_string(),
+            }),
+            Certificate::PoolRetirement(pool, epoch) => json!({
+                "type": "pool_retirement",
+                "pool": pool.to_string(),
+                "epoch": epoch,
+            }),
+            Certificate::Reg(credential, deposit) => json!({
+                "type": "stake_registration",
+                "credential": credential_json(credential),
+                "deposit": deposit,
+            }),
+            Certificate::UnReg(credential, refund) => json!({
+                "type": "stake_deregistration",
+                "credential": credential_json(credential),
+                "refund": refund,
+            }),
+            Certificate::VoteDeleg(credential, drep) => json!({
+                "type": "vote_delegation",
+                "credential": credential_json(credential),
+                "drep": drep.to_json(),
+            }),
+            Certificate::StakeVoteDeleg(credential, pool, drep) => json!({
+                "type": "stake_vote_delegation",
+                "credential": credential_json(credential),
+                "pool": pool.to_string(),
+                "drep": drep.to_json(),
+            }),
+            Certificate::StakeRegDeleg(credential, pool, deposit) => json!({
+                "type": "stake_registration_delegation",
+                "credential": credential_json(credential),
+                "pool": pool.to_string(),
+                "deposit": deposit,
+            }),
+            Certificate::VoteRegDeleg(credential, drep, deposit) => json!({
+                "type": "vote_registration_delegation",
+                "credential": credential_json(credential),
+                "drep": drep.to_json(),
+                "deposit": deposit,
+            }),
+            Certificate::StakeVoteRegDeleg(credential, pool, drep, deposit) => json!({
+                "type": "stake_vote_registration_delegation",
+                "credential": credential_json(credential),
+                "pool": pool.to_string(),
+                "drep": drep.to_json(),
+                "deposit": deposit,
+            }),
+            Certificate::AuthCommitteeHot(cold, hot) => json!({
+                "type": "committee_hot_authorization",
+                "cold_credential": credential_json(cold),
+                "hot_credential": credential_json(hot),
+            }),
+            Certificate::ResignCommitteeCold(cold, anchor) => json!({
+                "type": "committee_resignation",
+                "cold_credential": credential_json(cold),
+                "anchor": anchor.to_json(),
+            }),
+            Certificate::RegDRepCert(credential, deposit, anchor) => json!({
+                "type": "drep_registration",
+                "credential": credential_json(credential),
+                "deposit": deposit,
+                "anchor": anchor.to_json(),
+            }),
+            Certificate::UnRegDRepCert(credential, refund) => json!({
+                "type": "drep_retirement",
+                "credential": credential_json(credential),
+                "refund": refund,
+            }),
+            Certificate::UpdateDRepCert(credential, anchor) => json!({
+                "type": "drep_update",
+                "credential": credential_json(credential),
+                "anchor": anchor.to_json(),
+            }),
+        }
+    }
+}
+
+impl ToJson for Vote {
+    fn to_json(&self) -> Value {
+        match self {
+            Vote::Yes => json!("yes"),
+            Vote::No => json!("no"),
+            Vote::Abstain => json!("abstain"),
+        }
+    }
+}
+
+impl ToJson for BlockHeader {
+    fn to_json(&self) -> Value {
+        json!({
+            "hash": self.hash().to_string(),
+            "parent": self.parent().map(|hash| hash.to_string()),
+            "block_height": self.block_height(),
+            "slot": self.slot(),
+        })
+    }
+}
+
+impl ToJson for RawBlock {
+    fn to_json(&self) -> Value {
+        json!({ "cbor": hex::encode::<&[u8]>(self.as_ref()) })
+    }
+}
+
+impl ToJson for BlockTransaction {
+    fn to_json(&self) -> Value {
+        json!({
+            "index": self.index,
+            "id": self.id.to_string(),
+            "is_valid": self.is_valid,
+            "fee": self.fee,
+            "validity_start": self.validity_start,
+            "ttl": self.ttl,
+            "inputs": inputs_json(&self.inputs),
+            "reference_inputs": inputs_json(&self.reference_inputs),
+            "collateral": inputs_json(&self.collateral),
+            "outputs": self.outputs.iter().map(ToJson::to_json).collect::<Vec<_>>(),
+            "certificates": self
+                .certificates
+                .iter()
+                .map(ToJson::to_json)
+                .collect::<Vec<_>>(),
+            "withdrawals": self
+                .withdrawals
+                .iter()
+                .map(|(account, coin)| json!({ "account": account.to_string(), "amount": coin }))
+                .collect::<Vec<_>>(),
+            "mint": self
+                .mint
+                .iter()
+                .map(|(policy, name, amount)| json!({
+                    "policy": policy.to_string(),
+                    "name": name.to_string(),
+                    "amount": amount,
+                }))
+                .collect::<Vec<_>>(),
+            "votes": self
+                .votes
+                .iter()
+                .map(|(voter, proposal, vote)| json!({
+                    "voter": VoterDisplay(voter).to_string(),
+                    "proposal": ProposalIdDisplay(proposal).to_string(),
+                    "vote": vote.to_json(),
+                }))
+                .collect::<Vec<_>>(),
+            "proposals": self
+                .proposals
+                .iter()
+                .map(|proposal| json!({
+                    "deposit": proposal.deposit,
+                    "reward_account": proposal.reward_account.to_string(),
+                    "gov_action": gov_action_type(&proposal.gov_action),
+                    "anchor": proposal.anchor.to_json(),
+                }))
+                .collect::<Vec<_>>(),
+            "witnesses": self.witnesses.to_json(),
+            "has_auxiliary_data": self.has_auxiliary_data,
+        })
+    }
+}
+
+impl ToJson for WitnessCounts {
+    fn to_json(&self) -> Value {
+        json!({
+            "vkeys": self.vkeys,
+            "bootstraps": self.bootstraps,
+            "native_scripts": self.native_scripts,
+            "plutus_v1_scripts": self.plutus_v1_scripts,
+            "plutus_v2_scripts": self.plutus_v2_scripts,
+            "plutus_v3_scripts": self.plutus_v3_scripts,
+            "plutus_data": self.plutus_data,
+            "redeemers": self.redeemers,
+        })
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    json::span::attributes_json,
+    otel::{graph::TraceGraph, id::TraceId, span_ext::SpanExt, summary::TraceSummary},
+};
+use opentelemetry_proto::tonic::trace::v1::Span;
+use serde_json::{Value, json};
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
+
+/// Converts traces to the Chrome Trace Event format, as opened by Perfetto
+/// and `chrome://tracing`.
+///
+/// Each trace is a process. Its spans are complete events spread over
+/// threads, as the events of a thread must either nest or follow each other:
+/// a span stays on the thread of its parent unless it runs concurrently with
+/// a sibling there. Span events are instant events on the thread of their
+/// span.
+pub fn chrome_trace(graph: &TraceGraph, trace_ids: &[TraceId]) -> Value {
+    let mut events = Vec::new();
+    for (pid, trace_id) in (1..).zip(trace_ids) {
+        let Some(summary) = TraceSummary::new(graph, *trace_id) else {
+            continue;
+        };
+        events.push(json!({
+            "name": "process_name",
+            "ph": "M",
+            "pid": pid,
+            "args": { "name": format!("{} {}", summary.root_name, trace_id) },
+        }));
+
+        let mut spans: Vec<&Arc<Span>> = graph
+            .trace_iter(trace_id)
+            .filter_map(|span_id| graph.spans.get(&span_id))
+            .collect();
+        // Parents come before the children they enclose
+        spans.sort_by_key(|span| (span.start_time(), Reverse(span.end_time())));
+
+        // The spans still open on each thread, innermost last
+        let mut threads: Vec<Vec<&Span>> = Vec::new();
+        let mut tids = HashMap::new();
+        for span in spans {
+            let parent_tid = span.parent_id().and_then(|id| tids.get(&id).copied());
+            let tid = parent_tid
+                .filter(|tid| fits(&mut threads[*tid], span))
+                .or_else(|| threads.iter_mut().position(|open| fits(open, span)))
+                .unwrap_or_else(|| {
+                    threads.push(Vec::new());
+                    threads.len() - 1
+                });
+            threads[tid].push(span);
+            tids.insert(span.span_id(), tid);
+            events.extend(span_events(span, pid, tid));
+        }
+    }
+
+    json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+    })
+}
+
+/// Whether `span` nests in the innermost span still open on a thread, closing
+/// the ones that ended before it started.
+fn fits(open: &mut Vec<&Span>, span: &Span) -> bool {
+    open.retain(|other| other.end_time() > span.start_time());
+    open.last()
+        .is_none_or(|innermost| innermost.end_time() >= span.end_time())
+}
+
+fn span_events(span: &Span, pid: usize, tid: usize) -> Vec<Value> {
+    let mut args = attributes_json(&span.attributes);
+    args["span_id"] = json!(span.span_id().to_string());
+    if let Some(parent_id) = span.parent_id() {
+        args["parent_id"] = json!(parent_id.to_string());
+    }
+    if let Some(status) = span.status.as_ref().filter(|_| span.is_error()) {
+        args["error"] = json!(status.message);
+    }
+
+    let mut events = vec![json!({
+        "name": span.name,
+        "cat": "span",
+        "ph": "X",
+        "ts": micros(span.start_time_unix_nano),
+        "dur": micros(span.end_time_unix_nano.saturating_sub(span.start_time_unix_nano)),
+        "pid": pid,
+        "tid": tid,
+        "args": args,
+    })];
+    events.extend(span.events.iter().map(|event| {
+        json!({
+            "name": event.name,
+            "cat": "event",
+            "ph": "i",
+            "s": "t",
+            "ts": micros(event.time_unix_nano),
+            "pid": pid,
+            "tid": tid,
+            "args": attributes_json(&event.attributes),
+        })
+    }));
+    events
+}
+
+/// Timestamps are in microseconds.
+fn micros(nanos: u64) -> f64 {
+    nanos as f64 / 1_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::{id::Id, test_span::span};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_chrome_trace() {
+        let mut graph = TraceGraph::default();
+        graph.insert_root_span(span(1).end_ms(100).build());
+        graph.insert_child_span(span(2).parent(1).end_ms(60).build());
+        graph.insert_child_span(span(3).parent(1).start_ms(10).end_ms(90).build());
+        graph.insert_child_span(span(4).parent(3).start_ms(70).end_ms(80).build());
+
+        let trace = chrome_trace(&graph, &[Id([1; 16])]);
+        let threads: Vec<_> = trace["traceEvents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|event| event["ph"] == "X")
+            .map(|event| {
+                (
+                    event["name"].as_str().unwrap(),
+                    event["tid"].as_u64().unwrap(),
+                    event["dur"].as_f64().unwrap(),
+                )
+            })
+            .collect();
+        // 3 runs concurrently with 2, which it doesn't nest in, and 4 follows
+        // its parent
+        assert_eq!(
+            threads,
+            vec![
+                ("span1", 0, 100_000.0),
+                ("span2", 0, 60_000.0),
+                ("span3", 1, 80_000.0),
+                ("span4", 1, 10_000.0),
+            ]
+        );
+    }
+}
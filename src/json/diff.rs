@@ -0,0 +1,48 @@
+use crate::{
+    json::ToJson,
+    model::ledger_diff::{Change, DiffEntry, UtxoStats},
+};
+use serde_json::{Value, json};
+
+impl<T: ToJson> ToJson for Change<T> {
+    fn to_json(&self) -> Value {
+        json!({
+            "change": self.kind(),
+            "before": self.before().map(ToJson::to_json),
+            "after": self.after().map(ToJson::to_json),
+        })
+    }
+}
+
+impl ToJson for UtxoStats {
+    fn to_json(&self) -> Value {
+        json!({
+            "count": self.count,
+            "lovelace": self.lovelace,
+        })
+    }
+}
+
+impl ToJson for DiffEntry {
+    fn to_json(&self) -> Value {
+        let mut value = match self {
+            DiffEntry::Account(change) => change.to_json(),
+            DiffEntry::Pool(change) => change.to_json(),
+            DiffEntry::DRep(change) => change.to_json(),
+            DiffEntry::Proposal(change) => change.to_json(),
+            DiffEntry::Utxos {
+                before,
+                after,
+                added,
+                removed,
+            } => json!({
+                "before": before.to_json(),
+                "after": after.to_json(),
+                "added": added,
+                "removed": removed,
+            }),
+        };
+        value["entity"] = json!(self.entity());
+        value
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{
+    json::ToJson,
+    ui::{to_list_item::DRepItem, to_rich::account::StakeCredentialDisplay},
+};
+use amaru_kernel::Anchor;
+use serde_json::{Value, json};
+
+impl ToJson for DRepItem {
+    fn to_json(&self) -> Value {
+        let (credential, row) = self;
+        json!({
+            "credential": StakeCredentialDisplay(credential).to_string(),
+            "deposit": row.deposit,
+            "anchor": row.anchor.to_json(),
+            "registered_at": row.registered_at.to_json(),
+            "valid_until": row.valid_until.to_string(),
+            "previous_deregistration": row.previous_deregistration.to_json(),
+        })
+    }
+}
+
+impl ToJson for Anchor {
+    fn to_json(&self) -> Value {
+        json!({
+            "url": self.url,
+            "content_hash": self.content_hash.to_string(),
+        })
+    }
+}
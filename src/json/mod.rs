@@ -0,0 +1,44 @@
+use amaru_kernel::{CertificatePointer, Nullable};
+use serde_json::{Value, json};
+
+pub mod account;
+pub mod block_issuer;
+pub mod chain;
+pub mod chrome_trace;
+pub mod diff;
+pub mod drep;
+pub mod pool;
+pub mod proposal;
+pub mod span;
+pub mod utxo;
+
+/// The JSON counterpart of `ToRichText`, used when data leaves the TUI
+/// (headless commands, exports).
+pub trait ToJson {
+    fn to_json(&self) -> Value;
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Value {
+        self.as_ref().map_or(Value::Null, ToJson::to_json)
+    }
+}
+
+impl<T: Clone + ToJson> ToJson for Nullable<T> {
+    fn to_json(&self) -> Value {
+        match self {
+            Nullable::Some(v) => v.to_json(),
+            Nullable::Null | Nullable::Undefined => Value::Null,
+        }
+    }
+}
+
+impl ToJson for CertificatePointer {
+    fn to_json(&self) -> Value {
+        json!({
+            "slot": self.transaction.slot.to_string(),
+            "transaction_index": self.transaction.transaction_index,
+            "certificate_index": self.certificate_index,
+        })
+    }
+}
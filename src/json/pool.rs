@@ -0,0 +1,80 @@
+use crate::{
+    json::ToJson,
+    ui::{RationalNumberDisplay, to_list_item::PoolItem},
+};
+use amaru_kernel::{Nullable, PoolMetadata, PoolParams, Relay};
+use serde_json::{Value, json};
+
+impl ToJson for PoolItem {
+    fn to_json(&self) -> Value {
+        let (id, row) = self;
+        json!({
+            "id": id.to_string(),
+            "current_params": row.current_params.to_json(),
+            "future_params": row
+                .future_params
+                .iter()
+                .map(|(params, epoch)| json!({
+                    "epoch": epoch.to_string(),
+                    "params": params.as_ref().map(ToJson::to_json),
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl ToJson for PoolParams {
+    fn to_json(&self) -> Value {
+        json!({
+            "vrf": self.vrf.to_string(),
+            "pledge": self.pledge,
+            "cost": self.cost,
+            "margin": RationalNumberDisplay(&self.margin).to_string(),
+            "reward_account": self.reward_account.to_string(),
+            "owners": self.owners.iter().map(|o| o.to_string()).collect::<Vec<_>>(),
+            "relays": self.relays.iter().map(ToJson::to_json).collect::<Vec<_>>(),
+            "metadata": self.metadata.to_json(),
+        })
+    }
+}
+
+impl ToJson for PoolMetadata {
+    fn to_json(&self) -> Value {
+        json!({
+            "url": self.url,
+            "hash": self.hash.to_string(),
+        })
+    }
+}
+
+impl ToJson for Relay {
+    fn to_json(&self) -> Value {
+        let port = |p: &Nullable<u32>| match p {
+            Nullable::Some(p) => json!(p),
+            _ => Value::Null,
+        };
+        match self {
+            Relay::SingleHostAddr(p, ipv4, ipv6) => json!({
+                "type": "single_host_addr",
+                "port": port(p),
+                "ipv4": match ipv4 {
+                    Nullable::Some(ip) => json!(ip.iter().map(u8::to_string).collect::<Vec<_>>().join(".")),
+                    _ => Value::Null,
+                },
+                "ipv6": match ipv6 {
+                    Nullable::Some(ip) => json!(ip.to_string()),
+                    _ => Value::Null,
+                },
+            }),
+            Relay::SingleHostName(p, hostname) => json!({
+                "type": "single_host_name",
+                "port": port(p),
+                "hostname": hostname,
+            }),
+            Relay::MultiHostName(hostname) => json!({
+                "type": "multi_host_name",
+                "hostname": hostname,
+            }),
+        }
+    }
+}
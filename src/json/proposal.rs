@@ -0,0 +1,45 @@
+use crate::{
+    json::ToJson,
+    ui::{to_list_item::ProposalItem, to_rich::proposal::ComparableProposalIdDisplay},
+};
+use amaru_kernel::{GovAction, ProposalPointer};
+use serde_json::{Value, json};
+
+impl ToJson for ProposalItem {
+    fn to_json(&self) -> Value {
+        let (id, row) = self;
+        json!({
+            "id": ComparableProposalIdDisplay(id).to_string(),
+            "proposed_in": row.proposed_in.to_json(),
+            "valid_until": row.valid_until.to_string(),
+            "deposit": row.proposal.deposit,
+            "reward_account": row.proposal.reward_account.to_string(),
+            "gov_action": gov_action_type(&row.proposal.gov_action),
+            "anchor": row.proposal.anchor.to_json(),
+        })
+    }
+}
+
+impl ToJson for ProposalPointer {
+    fn to_json(&self) -> Value {
+        json!({
+            "slot": self.transaction.slot.to_string(),
+            "transaction_index": self.transaction.transaction_index,
+            "proposal_index": self.proposal_index,
+        })
+    }
+}
+
+/// The name of the governance action, the full content is available through
+/// the TUI.
+pub fn gov_action_type(action: &GovAction) -> &'static str {
+    match action {
+        GovAction::ParameterChange(..) => "ParameterChange",
+        GovAction::HardForkInitiation(..) => "HardForkInitiation",
+        GovAction::TreasuryWithdrawals(..) => "TreasuryWithdrawals",
+        GovAction::NoConfidence(..) => "NoConfidence",
+        GovAction::UpdateCommittee(..) => "UpdateCommittee",
+        GovAction::NewConstitution(..) => "NewConstitution",
+        GovAction::Information => "Information",
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{json::ToJson, otel::span_ext::SpanExt};
+use opentelemetry_proto::tonic::{
+    common::v1::{AnyValue, KeyValue, any_value::Value as OtelValue},
+    trace::v1::{Span, span::Event},
+};
+use serde_json::{Map, Value, json};
+
+pub(crate) fn attributes_json(attributes: &[KeyValue]) -> Value {
+    let mut map = Map::new();
+    for attribute in attributes {
+        map.insert(attribute.key.clone(), any_value_json(&attribute.value));
+    }
+    Value::Object(map)
+}
+
+fn any_value_json(value: &Option<AnyValue>) -> Value {
+    let Some(value) = value.as_ref().and_then(|v| v.value.as_ref()) else {
+        return Value::Null;
+    };
+    match value {
+        OtelValue::StringValue(s) => json!(s),
+        OtelValue::BoolValue(b) => json!(b),
+        OtelValue::IntValue(i) => json!(i),
+        OtelValue::DoubleValue(d) => json!(d),
+        OtelValue::ArrayValue(a) => a
+            .values
+            .iter()
+            .map(|v| any_value_json(&Some(v.clone())))
+            .collect(),
+        OtelValue::KvlistValue(k) => attributes_json(&k.values),
+        OtelValue::BytesValue(b) => json!(hex::encode(b)),
+    }
+}
+
+impl ToJson for Span {
+    fn to_json(&self) -> Value {
+        json!({
+            "trace_id": self.trace_id().to_string(),
+            "span_id": self.span_id().to_string(),
+            "parent_id": self.parent_id().map(|id| id.to_string()),
+            "name": self.name,
+            "kind": self.kind,
+            "start_time_unix_nano": self.start_time_unix_nano,
+            "end_time_unix_nano": self.end_time_unix_nano,
+            "attributes": attributes_json(&self.attributes),
+            "events": self.events.iter().map(ToJson::to_json).collect::<Vec<_>>(),
+            "status": self.status.as_ref().map(|s| json!({
+                "code": s.code,
+                "message": s.message,
+            })),
+        })
+    }
+}
+
+impl ToJson for Event {
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "time_unix_nano": self.time_unix_nano,
+            "attributes": attributes_json(&self.attributes),
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    json::ToJson,
+    ui::{to_list_item::UtxoItem, to_rich::utxo::TransactionInputDisplay},
+};
+use amaru_kernel::{MemoizedDatum, MemoizedTransactionOutput, PseudoScript, Value as KernelValue};
+use serde_json::{Map, Value, json};
+
+impl ToJson for UtxoItem {
+    fn to_json(&self) -> Value {
+        let (input, output) = self;
+        let mut value = output.to_json();
+        if let Value::Object(fields) = &mut value {
+            fields.insert(
+                "input".to_string(),
+                json!(TransactionInputDisplay(input).to_string()),
+            );
+        }
+        value
+    }
+}
+
+impl ToJson for MemoizedTransactionOutput {
+    fn to_json(&self) -> Value {
+        json!({
+            "address": self.address.to_string(),
+            "value": self.value.to_json(),
+            "datum": self.datum.to_json(),
+            "script": self.script.as_ref().map(|s| match s {
+                PseudoScript::NativeScript(s) => json!({ "native": hex::encode(s.original_bytes()) }),
+                PseudoScript::PlutusV1Script(s) => json!({ "plutus_v1": hex::encode(&*s.0) }),
+                PseudoScript::PlutusV2Script(s) => json!({ "plutus_v2": hex::encode(&*s.0) }),
+                PseudoScript::PlutusV3Script(s) => json!({ "plutus_v3": hex::encode(&*s.0) }),
+            }),
+        })
+    }
+}
+
+impl ToJson for KernelValue {
+    fn to_json(&self) -> Value {
+        match self {
+            KernelValue::Coin(c) => json!({ "coin": c }),
+            KernelValue::Multiasset(coin, assets) => {
+                let mut policies = Map::new();
+                for (pid, aset) in assets.iter() {
+                    let mut names = Map::new();
+                    for (aname, amount) in aset.iter() {
+                        names.insert(aname.to_string(), json!(u64::from(*amount)));
+                    }
+                    policies.insert(pid.to_string(), Value::Object(names));
+                }
+                json!({ "coin": coin, "assets": policies })
+            }
+        }
+    }
+}
+
+impl ToJson for MemoizedDatum {
+    fn to_json(&self) -> Value {
+        match self {
+            MemoizedDatum::None => Value::Null,
+            MemoizedDatum::Hash(d) => json!({ "hash": hex::encode(d) }),
+            MemoizedDatum::Inline(d) => json!({ "inline": hex::encode(d.original_bytes()) }),
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use std::path::PathBuf;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::detection::{AMARU_CHAIN_DB_ENV, AMARU_LEDGER_DB_ENV, detect_amaru_process};
-use amaru_kernel::network::NetworkName;
+use amaru_kernel::{Epoch, network::NetworkName};
 use amaru_stores::rocksdb::{
-    ReadOnlyRocksDB, RocksDbConfig,
+    ReadOnlyRocksDB, RocksDBHistoricalStores, RocksDbConfig,
     consensus::{ReadOnlyChainDB, RocksDBStore},
 };
 use anyhow::Result;
@@ -14,10 +17,15 @@ pub mod components;
 mod config;
 pub mod controller;
 pub mod detection;
+mod export;
+pub mod headless;
+mod json;
 pub mod logging;
+pub mod logs;
 pub mod metrics;
 pub mod model;
 pub mod otel;
+pub mod receiver;
 mod states;
 mod store;
 pub mod tui;
@@ -25,6 +33,8 @@ mod ui;
 mod view;
 pub mod viewmodel;
 
+pub use config::CollectorConfig;
+
 fn default_db_name(name: &str, network: &NetworkName) -> String {
     format!("{}.{}.db", name, network)
 }
@@ -35,25 +45,51 @@ fn prepend_path(opt_base: Option<PathBuf>, name: &str) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from(name))
 }
 
-pub fn open_ledger_db(
-    ledger_db: &Option<PathBuf>,
-    network: &NetworkName,
-) -> Result<ReadOnlyRocksDB> {
+/// Resolves the ledger db directory, either from args or from the environment
+/// of a running amaru process.
+pub fn ledger_db_path(ledger_db: &Option<PathBuf>, network: &NetworkName) -> PathBuf {
     if let Some(path) = ledger_db {
-        ReadOnlyRocksDB::new(RocksDbConfig::new(path.into())).map_err(Into::into)
+        path.clone()
     } else {
         if let Some((cwd, envs)) = detect_amaru_process() {
             let path = envs
                 .get(AMARU_LEDGER_DB_ENV)
                 .cloned()
                 .unwrap_or_else(|| default_db_name("ledger", network));
-            return ReadOnlyRocksDB::new(RocksDbConfig::new(prepend_path(cwd, &path)))
-                .map_err(Into::into);
+            return prepend_path(cwd, &path);
         }
         panic!("No ledger db provided, either through env or args");
     }
 }
 
+pub fn open_ledger_db(
+    ledger_db: &Option<PathBuf>,
+    network: &NetworkName,
+) -> Result<ReadOnlyRocksDB> {
+    open_ledger_db_at(&ledger_db_path(ledger_db, network))
+}
+
+pub fn open_ledger_db_at(path: &Path) -> Result<ReadOnlyRocksDB> {
+    ReadOnlyRocksDB::new(RocksDbConfig::new(path.into())).map_err(Into::into)
+}
+
+/// Lists the epochs of the snapshots amaru keeps next to the live ledger,
+/// each in a directory named after its epoch, oldest first.
+pub fn list_ledger_snapshots(path: &Path) -> Result<Vec<Epoch>> {
+    let mut epochs: Vec<u64> = fs::read_dir(path)?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+    epochs.sort_unstable();
+    Ok(epochs.into_iter().map(Epoch::from).collect())
+}
+
+pub fn open_ledger_snapshot(path: &Path, epoch: Epoch) -> Result<ReadOnlyRocksDB> {
+    RocksDBHistoricalStores::for_epoch_with(&RocksDbConfig::new(path.into()), epoch)
+        .map_err(Into::into)
+}
+
 pub fn open_chain_db(chain_db: &Option<PathBuf>, network: &NetworkName) -> Result<ReadOnlyChainDB> {
     if let Some(path) = chain_db {
         RocksDBStore::open_for_readonly(RocksDbConfig::new(path.into())).map_err(Into::into)
@@ -0,0 +1,224 @@
+use crate::{
+    otel::id::{SpanId, TraceId},
+    ui::to_rich::span::format_any_value,
+};
+use opentelemetry_proto::tonic::{common::v1::KeyValue, logs::v1::LogRecord};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, RwLock},
+};
+use strum::Display;
+
+/// The severity of a log record, grouping the OTEL `SeverityNumber`s.
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum Severity {
+    Unspecified,
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl Severity {
+    /// See `SeverityNumber` in otel's `logs.proto`.
+    pub fn from_number(number: i32) -> Self {
+        match number {
+            1..=4 => Severity::Trace,
+            5..=8 => Severity::Debug,
+            9..=12 => Severity::Info,
+            13..=16 => Severity::Warn,
+            17..=20 => Severity::Error,
+            21..=24 => Severity::Fatal,
+            _ => Severity::Unspecified,
+        }
+    }
+
+    /// For exporters that only set the severity text.
+    pub fn from_text(text: &str) -> Self {
+        match text.to_ascii_lowercase().as_str() {
+            "trace" => Severity::Trace,
+            "debug" => Severity::Debug,
+            "info" => Severity::Info,
+            "warn" | "warning" => Severity::Warn,
+            "error" => Severity::Error,
+            "fatal" | "critical" => Severity::Fatal,
+            _ => Severity::Unspecified,
+        }
+    }
+}
+
+/// A log record received from amaru.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    pub time_unix_nano: u64,
+    pub severity: Severity,
+    pub severity_text: String,
+    pub body: String,
+    /// The instrumentation scope, usually the module that logged.
+    pub scope: String,
+    pub attributes: Vec<KeyValue>,
+    pub trace_id: Option<TraceId>,
+    pub span_id: Option<SpanId>,
+    /// The body, scope and attributes in lowercase, one per line, for
+    /// `matches` not to lowercase them on every search.
+    search_text: String,
+}
+
+impl LogEntry {
+    pub fn new(scope: &str, record: LogRecord) -> Self {
+        let time_unix_nano = if record.time_unix_nano != 0 {
+            record.time_unix_nano
+        } else {
+            record.observed_time_unix_nano
+        };
+        let severity = match Severity::from_number(record.severity_number) {
+            Severity::Unspecified => Severity::from_text(&record.severity_text),
+            severity => severity,
+        };
+        let body = format_any_value(&record.body);
+        let mut search_text = vec![body.to_lowercase(), scope.to_lowercase()];
+        for attr in &record.attributes {
+            search_text.push(attr.key.to_lowercase());
+            search_text.push(format_any_value(&attr.value).to_lowercase());
+        }
+        Self {
+            time_unix_nano,
+            severity,
+            body,
+            severity_text: record.severity_text,
+            scope: scope.to_string(),
+            attributes: record.attributes,
+            // Records logged outside of a span have empty ids
+            trace_id: TraceId::try_from(record.trace_id).ok(),
+            span_id: SpanId::try_from(record.span_id).ok(),
+            search_text: search_text.join("\n"),
+        }
+    }
+
+    /// Whether the record is at least `min` severe and mentions `query`, in
+    /// its body, scope or attributes. `query` is expected in lowercase.
+    pub fn matches(&self, min: Severity, query: &str) -> bool {
+        if self.severity < min {
+            return false;
+        }
+        query.is_empty() || self.search_text.contains(query)
+    }
+}
+
+struct LogBuffer {
+    entries: VecDeque<Arc<LogEntry>>,
+    capacity: usize,
+    received: u64,
+}
+
+/// The last log records received, shared by the receivers and the Logs page.
+/// The oldest records are dropped past its capacity.
+#[derive(Clone)]
+pub struct LogStore(Arc<RwLock<LogBuffer>>);
+
+impl LogStore {
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(RwLock::new(LogBuffer {
+            entries: VecDeque::new(),
+            capacity,
+            received: 0,
+        })))
+    }
+
+    pub fn push(&self, entries: Vec<LogEntry>) {
+        let mut buffer = self.0.write().unwrap();
+        buffer.received += entries.len() as u64;
+        buffer.entries.extend(entries.into_iter().map(Arc::new));
+        let excess = buffer.entries.len().saturating_sub(buffer.capacity);
+        buffer.entries.drain(..excess);
+    }
+
+    /// How many records were received so far, to tell when the store
+    /// changed.
+    pub fn received(&self) -> u64 {
+        self.0.read().unwrap().received
+    }
+
+    /// The kept records, oldest first.
+    pub fn entries(&self) -> Vec<Arc<LogEntry>> {
+        self.0.read().unwrap().entries.iter().cloned().collect()
+    }
+
+    /// The kept records received after the first `received` ones, oldest
+    /// first, each with its rank among all the records received so far. Also
+    /// returns the rank of the oldest record kept, the ones before having been
+    /// dropped.
+    pub fn entries_since(&self, received: u64) -> (Vec<(u64, Arc<LogEntry>)>, u64) {
+        let buffer = self.0.read().unwrap();
+        let first_kept = buffer.received - buffer.entries.len() as u64;
+        let from = received.max(first_kept);
+        let entries = buffer
+            .entries
+            .iter()
+            .zip(first_kept..)
+            .skip((from - first_kept) as usize)
+            .map(|(entry, rank)| (rank, entry.clone()))
+            .collect();
+        (entries, first_kept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_proto::tonic::common::v1::{AnyValue, any_value::Value};
+    use pretty_assertions::assert_eq;
+
+    fn entry(severity_number: i32, body: &str) -> LogEntry {
+        LogEntry::new(
+            "amaru::consensus",
+            LogRecord {
+                severity_number,
+                body: Some(AnyValue {
+                    value: Some(Value::StringValue(body.to_string())),
+                }),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_matches() {
+        let warn = entry(13, "Header rejected");
+        assert_eq!(warn.severity, Severity::Warn);
+        assert_eq!(warn.trace_id, None);
+        assert!(warn.matches(Severity::Info, ""));
+        assert!(!warn.matches(Severity::Error, ""));
+        assert!(warn.matches(Severity::Warn, "rejected"));
+        assert!(warn.matches(Severity::Warn, "consensus"));
+        assert!(!warn.matches(Severity::Warn, "ledger"));
+    }
+
+    #[test]
+    fn test_store_capacity() {
+        let store = LogStore::new(2);
+        store.push(vec![entry(9, "a"), entry(9, "b"), entry(9, "c")]);
+        let bodies: Vec<_> = store.entries().iter().map(|e| e.body.clone()).collect();
+        assert_eq!(bodies, vec!["b", "c"]);
+        assert_eq!(store.received(), 3);
+    }
+
+    #[test]
+    fn test_entries_since() {
+        let store = LogStore::new(3);
+        store.push(vec![entry(9, "a"), entry(9, "b")]);
+        store.push(vec![entry(9, "c"), entry(9, "d")]);
+        let (entries, first_kept) = store.entries_since(2);
+        let ranks: Vec<_> = entries
+            .iter()
+            .map(|(rank, e)| (*rank, e.body.as_str()))
+            .collect();
+        assert_eq!(ranks, vec![(2, "c"), (3, "d")]);
+        assert_eq!(first_kept, 1);
+        assert_eq!(store.entries_since(0).0.len(), 3);
+        assert_eq!(store.entries_since(4).0.len(), 0);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{
+    components::{
+        Component, ComponentLayout, details::DetailsComponent, handle_container_event,
+        list::ListComponent, search_bar::SearchBarComponent, tabs::TabsComponent,
+    },
+    controller::{LayoutSpec, MoveFocus, find_next_focus, walk_layout},
+    logs::model::{LogEntry, LogStore},
+    otel::TraceGraphSnapshot,
+    receiver::ReceiverHealth,
+    states::{Action, ComponentId, SeverityFilter},
+    view::receiver_status::{draw_receiver_status, split_status},
+    viewmodel::dynamic_list::DynamicListViewModel,
+};
+use crossterm::event::{Event, KeyCode};
+use either::Either::{Left, Right};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    sync::RwLock,
+};
+use tracing::debug;
+
+pub struct LogsPageComponent {
+    id: ComponentId,
+    store: LogStore,
+    trace_graph: TraceGraphSnapshot,
+
+    pub severity_tabs: TabsComponent<SeverityFilter>,
+    search_bar: SearchBarComponent,
+    pub list: ListComponent<DynamicListViewModel<Arc<LogEntry>>>,
+    details: DetailsComponent<LogEntry>,
+    health: Vec<ReceiverHealth>,
+
+    /// The last search, in lowercase.
+    query: String,
+    /// Keeps the newest record selected as records come in. Moving the
+    /// selection stops following.
+    follow: bool,
+    /// The records matching the filters with their rank in the store, oldest
+    /// first, so that only the records received since are filtered on tick.
+    matched: VecDeque<(u64, Arc<LogEntry>)>,
+    /// The `LogStore::received` count the records were last filtered at.
+    received: u64,
+
+    last_layout: RwLock<ComponentLayout>,
+    active_focus: RwLock<ComponentId>,
+}
+
+impl LogsPageComponent {
+    pub fn new(
+        store: LogStore,
+        trace_graph: TraceGraphSnapshot,
+        health: Vec<ReceiverHealth>,
+    ) -> Self {
+        Self {
+            id: ComponentId::LogsPage,
+            store,
+            trace_graph,
+            severity_tabs: TabsComponent::new(ComponentId::LogsSeverityTabs, true),
+            search_bar: SearchBarComponent::new(ComponentId::SearchBar),
+            list: ListComponent::new(ComponentId::LogsList, DynamicListViewModel::new("Logs")),
+            details: DetailsComponent::new(ComponentId::LogsDetails, "Log Details"),
+            health,
+            query: String::new(),
+            follow: true,
+            matched: VecDeque::new(),
+            received: 0,
+            last_layout: RwLock::new(HashMap::new()),
+            active_focus: RwLock::new(ComponentId::LogsList),
+        }
+    }
+
+    /// Filters all the kept records again, after the filters changed.
+    fn refresh(&mut self) {
+        self.matched.clear();
+        self.received = 0;
+        self.update();
+    }
+
+    /// Filters the records received since the last update, drops the ones
+    /// the store dropped, and lists the matching ones newest first.
+    fn update(&mut self) {
+        let (entries, first_kept) = self.store.entries_since(self.received);
+        if let Some((rank, _)) = entries.last() {
+            self.received = rank + 1;
+        }
+        let min_severity = self.severity_tabs.selected().min_severity();
+        self.matched.extend(
+            entries
+                .into_iter()
+                .filter(|(_, entry)| entry.matches(min_severity, &self.query)),
+        );
+        while self
+            .matched
+            .front()
+            .is_some_and(|(rank, _)| *rank < first_kept)
+        {
+            self.matched.pop_front();
+        }
+        let items = self
+            .matched
+            .iter()
+            .rev()
+            .map(|(_, entry)| entry.clone())
+            .collect();
+        self.list.model.set_items(items);
+        if self.follow {
+            self.list.model.select_index(0);
+        }
+    }
+
+    fn toggle_follow(&mut self) {
+        self.follow = !self.follow;
+        if self.follow {
+            self.list.model.select_index(0);
+        }
+    }
+
+    /// Shows the trace the selected record was emitted in, if it is still
+    /// part of the trace graph.
+    fn show_trace(&self) -> Vec<Action> {
+        let Some(entry) = self.list.model.selected_item() else {
+            return Vec::new();
+        };
+        let Some(trace_id) = entry.trace_id else {
+            return Vec::new();
+        };
+        let graph = self.trace_graph.load();
+        if !graph.traces.contains_key(&trace_id) {
+            debug!("Trace {} isn't in the trace graph", trace_id);
+            return Vec::new();
+        }
+        let span_id = entry.span_id.filter(|id| graph.spans.contains_key(id));
+        vec![Action::ShowTrace(trace_id, span_id)]
+    }
+
+    fn dispatch_to_child(
+        &mut self,
+        target_id: ComponentId,
+        event: &Event,
+        area: Rect,
+    ) -> Vec<Action> {
+        match target_id {
+            ComponentId::LogsSeverityTabs => {
+                let old_filter = self.severity_tabs.selected();
+                let actions = self.severity_tabs.handle_event(event, area);
+                if self.severity_tabs.selected() != old_filter {
+                    self.refresh();
+                }
+                actions
+            }
+            ComponentId::SearchBar => self.search_bar.handle_event(event, area),
+            ComponentId::LogsList => {
+                if let Event::Key(key) = event {
+                    match key.code {
+                        KeyCode::Char('f') => {
+                            self.toggle_follow();
+                            return Vec::new();
+                        }
+                        KeyCode::Enter => return self.show_trace(),
+                        _ => {}
+                    }
+                }
+                self.list.model.set_height(area.height as usize);
+                let actions = self.list.handle_event(event, area);
+                if self.follow && self.list.model.selected_item() != self.list.model.items().first()
+                {
+                    self.follow = false;
+                }
+                actions
+            }
+            ComponentId::LogsDetails => self.details.handle_event(event, area),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn handle_navigation(&mut self, direction: MoveFocus) -> Vec<Action> {
+        let layout = self.last_layout.read().unwrap();
+        let active_focus = *self.active_focus.read().unwrap();
+
+        if let Some(next) = find_next_focus(&layout, active_focus, direction) {
+            *self.active_focus.write().unwrap() = next;
+            return vec![Action::SetFocus(next)];
+        }
+
+        Vec::new()
+    }
+
+    pub fn calculate_layout(&self, area: Rect) -> ComponentLayout {
+        let [area, _] = split_status(area);
+        let spec = LayoutSpec {
+            direction: Direction::Vertical,
+            constraints: vec![
+                (
+                    Constraint::Length(3),
+                    Right(LayoutSpec {
+                        direction: Direction::Horizontal,
+                        constraints: vec![
+                            (Constraint::Length(40), Left(ComponentId::LogsSeverityTabs)),
+                            (Constraint::Fill(1), Left(ComponentId::SearchBar)),
+                        ],
+                    }),
+                ),
+                (
+                    Constraint::Fill(1),
+                    Right(LayoutSpec {
+                        direction: Direction::Horizontal,
+                        constraints: vec![
+                            (Constraint::Percentage(60), Left(ComponentId::LogsList)),
+                            (Constraint::Percentage(40), Left(ComponentId::LogsDetails)),
+                        ],
+                    }),
+                ),
+            ],
+        };
+
+        let mut layout = HashMap::new();
+        walk_layout(&mut layout, &spec, area);
+        layout
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let my_layout = self.calculate_layout(area);
+        let status_area = split_status(area)[1];
+        draw_receiver_status(frame, status_area, &self.health);
+        let (follow, color) = if self.follow {
+            ("following (f)", Color::Green)
+        } else {
+            ("paused (f to follow)", Color::Gray)
+        };
+        // Only the text is styled, not to recolor the receiver status under it
+        frame.render_widget(
+            Line::from(Span::styled(follow, Style::default().fg(color)))
+                .alignment(Alignment::Right),
+            status_area,
+        );
+
+        {
+            let mut layout_guard = self.last_layout.write().unwrap();
+            *layout_guard = my_layout.clone();
+        }
+
+        let current_focus = *self.active_focus.read().unwrap();
+        if let Some(rect) = my_layout.get(&ComponentId::LogsSeverityTabs) {
+            let is_focused = current_focus == ComponentId::LogsSeverityTabs;
+            self.severity_tabs.render_focused(frame, *rect, is_focused);
+        }
+
+        if let Some(rect) = my_layout.get(&ComponentId::SearchBar) {
+            let is_focused = current_focus == ComponentId::SearchBar;
+            self.search_bar.render_focused(frame, *rect, is_focused);
+        }
+
+        if let Some(rect) = my_layout.get(&ComponentId::LogsList) {
+            let is_focused = current_focus == ComponentId::LogsList;
+            self.list.render_focused(frame, *rect, is_focused);
+        }
+
+        if let Some(rect) = my_layout.get(&ComponentId::LogsDetails) {
+            let is_focused = current_focus == ComponentId::LogsDetails;
+            self.details.render_with_data(
+                frame,
+                *rect,
+                is_focused,
+                self.list.model.selected_item().map(|entry| &**entry),
+            );
+        }
+    }
+}
+
+impl Component for LogsPageComponent {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn handle_event(&mut self, event: &Event, area: Rect) -> Vec<Action> {
+        let layout = self.last_layout.read().unwrap().clone();
+        let mut active_focus = *self.active_focus.read().unwrap();
+
+        let mut actions = handle_container_event(
+            &layout,
+            &mut active_focus,
+            event,
+            area,
+            |target_id, ev, child_area| self.dispatch_to_child(target_id, ev, child_area),
+        );
+
+        // Intercept SubmitSearch from the search bar so it doesn't bubble to App
+        if let Some(pos) = actions
+            .iter()
+            .position(|a| matches!(a, Action::SubmitSearch(_)))
+            && let Action::SubmitSearch(query) = actions.remove(pos)
+        {
+            self.query = query.to_lowercase();
+            self.refresh();
+        }
+
+        *self.active_focus.write().unwrap() = active_focus;
+        actions
+    }
+
+    fn tick(&mut self) -> Vec<Action> {
+        if self.received != self.store.received() {
+            self.update();
+        }
+        Vec::new()
+    }
+}
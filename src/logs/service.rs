@@ -0,0 +1,50 @@
+use crate::{
+    logs::model::{LogEntry, LogStore},
+    receiver::ReceiverHealth,
+};
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse, logs_service_server::LogsService,
+};
+use tonic::{Request, Response, Status};
+
+/// Keeps the records of a request, whatever the transport it was received
+/// on.
+pub fn ingest_logs(store: &LogStore, req: ExportLogsServiceRequest) {
+    let entries = req
+        .resource_logs
+        .into_iter()
+        .flat_map(|resource_logs| resource_logs.scope_logs)
+        .flat_map(|scope_logs| {
+            let scope = scope_logs.scope.map(|scope| scope.name).unwrap_or_default();
+            scope_logs
+                .log_records
+                .into_iter()
+                .map(move |record| LogEntry::new(&scope, record))
+        })
+        .collect();
+    store.push(entries);
+}
+
+/// Accepts amaru OTEL logs over gRPC.
+pub struct AmaruLogsService {
+    store: LogStore,
+    health: ReceiverHealth,
+}
+
+impl AmaruLogsService {
+    pub fn new(store: LogStore, health: ReceiverHealth) -> Self {
+        Self { store, health }
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for AmaruLogsService {
+    async fn export(
+        &self,
+        req: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        self.health.batch_received();
+        ingest_logs(&self.store, req.into_inner());
+        Ok(Response::new(ExportLogsServiceResponse::default()))
+    }
+}
@@ -1,6 +1,15 @@
 use amaru_doctor::{
-    app::App, cli::Cli, model::button::InputEvent, open_chain_db, open_ledger_db,
-    otel::service::OtelCollectorService, tui::Tui,
+    CollectorConfig,
+    app::App,
+    cli::Cli,
+    headless, ledger_db_path,
+    model::button::InputEvent,
+    open_chain_db, open_ledger_db_at,
+    otel::{
+        recording::{TraceRecorder, recording_path},
+        service::OtelCollectorService,
+    },
+    tui::Tui,
 };
 use anyhow::Result;
 use clap::Parser;
@@ -10,17 +19,32 @@ use std::sync::mpsc;
 async fn main() -> Result<()> {
     amaru_doctor::logging::init()?;
 
-    let otel_service = OtelCollectorService::new("0.0.0.0:4317");
-    let otel_handle = otel_service.start();
-
     let args = Cli::parse();
+    if let Some(command) = &args.command {
+        return headless::run(&args, command);
+    }
+
+    let collector = CollectorConfig::load(&args.collector)?;
+    let otel_handle = match &args.replay {
+        Some(path) => OtelCollectorService::replay(path, collector.max_spans)?,
+        None => {
+            let mut otel_service = OtelCollectorService::new(collector);
+            if args.record {
+                otel_service = otel_service.with_recorder(TraceRecorder::create(recording_path())?);
+            }
+            otel_service.start()
+        }
+    };
+
     let mut tui = Tui::default().mouse(true);
     let (_, dummy_input_events) = mpsc::channel::<InputEvent>();
 
+    let ledger_path = ledger_db_path(&args.ledger_db, &args.network);
     let mut app: App = App::new(
-        open_ledger_db(&args.ledger_db, &args.network)?,
+        open_ledger_db_at(&ledger_path)?,
+        ledger_path,
         open_chain_db(&args.chain_db, &args.network)?,
-        otel_handle.snapshot,
+        otel_handle,
         dummy_input_events,
         tui.get_frame().area(),
     )?;
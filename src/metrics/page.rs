@@ -4,9 +4,10 @@ use crate::{
     metrics::{
         charts::{ChartDatasetConfig, render_chart},
         model::{AmaruMetric, MetricUpdate, NodeMetrics},
-        service,
     },
+    receiver::ReceiverHealth,
     states::{Action, ComponentId},
+    view::receiver_status::{draw_receiver_status, split_status},
 };
 use either::Either::Left;
 use ratatui::{
@@ -15,33 +16,29 @@ use ratatui::{
     style::Color,
 };
 use std::{any::Any, collections::HashMap, sync::RwLock};
-use tokio::sync::mpsc::{self, Receiver};
+use tokio::sync::mpsc::Receiver;
 
 pub struct MetricsPageComponent {
     id: ComponentId,
     pub metrics: NodeMetrics,
     update_rx: Receiver<MetricUpdate>,
+    health: Vec<ReceiverHealth>,
     last_layout: RwLock<ComponentLayout>,
     active_focus: RwLock<ComponentId>,
 }
 
 impl MetricsPageComponent {
-    pub fn new(update_rx: Receiver<MetricUpdate>) -> Self {
+    pub fn new(update_rx: Receiver<MetricUpdate>, health: Vec<ReceiverHealth>) -> Self {
         Self {
             id: ComponentId::MetricsPage,
             metrics: NodeMetrics::default(),
             update_rx,
+            health,
             last_layout: RwLock::new(HashMap::new()),
             active_focus: RwLock::new(ComponentId::MetricsPage),
         }
     }
 
-    pub fn new_with_service() -> Self {
-        let (tx, rx) = mpsc::channel(100);
-        service::start(tx);
-        Self::new(rx)
-    }
-
     fn process_update(&mut self, update: MetricUpdate) {
         self.metrics.handle_update(update);
     }
@@ -59,6 +56,7 @@ impl MetricsPageComponent {
     }
 
     pub fn calculate_layout(&self, area: Rect) -> ComponentLayout {
+        let [area, _] = split_status(area);
         let spec = LayoutSpec {
             direction: Direction::Vertical,
             constraints: vec![(Constraint::Fill(1), Left(ComponentId::Metrics))],
@@ -70,6 +68,9 @@ impl MetricsPageComponent {
     }
 
     pub fn render(&self, f: &mut Frame, area: Rect) {
+        let [area, status_area] = split_status(area);
+        draw_receiver_status(f, status_area, &self.health);
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -1,20 +1,17 @@
-use crate::metrics::model::{AmaruMetric, MetricUpdate};
-use axum::{
-    Router,
-    extract::State,
-    http::{HeaderMap, StatusCode},
-    routing::post,
+use crate::{
+    metrics::model::{AmaruMetric, MetricUpdate},
+    receiver::ReceiverHealth,
 };
-use bytes::Bytes;
 use opentelemetry_proto::tonic::{
-    collector::metrics::v1::ExportMetricsServiceRequest,
+    collector::metrics::v1::{
+        ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        metrics_service_server::MetricsService,
+    },
     metrics::v1::{Metric as OtlpMetric, NumberDataPoint, metric::Data, number_data_point::Value},
 };
-use prost::Message;
-use std::net::SocketAddr;
-use std::sync::Arc;
-use tokio::{net::TcpListener, sync::mpsc::Sender};
-use tracing::{error, info, warn};
+use tokio::sync::mpsc::Sender;
+use tonic::{Request, Response, Status};
+use tracing::warn;
 
 fn get_value(dp: &NumberDataPoint) -> Option<f64> {
     match dp.value {
@@ -60,7 +57,7 @@ impl TryFrom<OtlpMetricWrapper<'_>> for MetricUpdate {
     }
 }
 
-async fn process_metric(tx: &Arc<Sender<MetricUpdate>>, metric: OtlpMetric) {
+async fn process_metric(tx: &Sender<MetricUpdate>, metric: OtlpMetric) {
     match MetricUpdate::try_from(OtlpMetricWrapper(&metric)) {
         Ok(update) => {
             if tx.send(update).await.is_err() {
@@ -73,39 +70,38 @@ async fn process_metric(tx: &Arc<Sender<MetricUpdate>>, metric: OtlpMetric) {
     }
 }
 
-async fn handle_metrics(
-    State(tx): State<Arc<Sender<MetricUpdate>>>,
-    _headers: HeaderMap,
-    body: Bytes,
-) -> StatusCode {
-    match ExportMetricsServiceRequest::decode(body.as_ref()) {
-        Ok(req) => {
-            for resource_metrics in req.resource_metrics {
-                for scope_metrics in resource_metrics.scope_metrics {
-                    for metric in scope_metrics.metrics {
-                        process_metric(&tx, metric).await;
-                    }
-                }
+/// Sends the supported metrics of a request to the TUI, whatever the
+/// transport it was received on.
+pub async fn ingest_metrics(tx: &Sender<MetricUpdate>, req: ExportMetricsServiceRequest) {
+    for resource_metrics in req.resource_metrics {
+        for scope_metrics in resource_metrics.scope_metrics {
+            for metric in scope_metrics.metrics {
+                process_metric(tx, metric).await;
             }
-            StatusCode::OK
         }
-        Err(_) => StatusCode::BAD_REQUEST,
     }
 }
 
-pub fn start(tx: Sender<MetricUpdate>) {
-    let shared_tx = Arc::new(tx);
+/// Accepts amaru OTEL metrics over gRPC.
+pub struct AmaruMetricsService {
+    tx: Sender<MetricUpdate>,
+    health: ReceiverHealth,
+}
 
-    tokio::spawn(async move {
-        let app = Router::new()
-            .route("/v1/metrics", post(handle_metrics))
-            .with_state(shared_tx);
-        let addr = SocketAddr::from(([0, 0, 0, 0], 4318));
-        info!("Listening on {}", addr);
+impl AmaruMetricsService {
+    pub fn new(tx: Sender<MetricUpdate>, health: ReceiverHealth) -> Self {
+        Self { tx, health }
+    }
+}
 
-        match axum::serve(TcpListener::bind(addr).await.unwrap(), app).await {
-            Ok(_) => info!("Server exited normally."),
-            Err(e) => error!("Server exited with error: {}", e),
-        }
-    });
+#[tonic::async_trait]
+impl MetricsService for AmaruMetricsService {
+    async fn export(
+        &self,
+        req: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        self.health.batch_received();
+        ingest_metrics(&self.tx, req.into_inner()).await;
+        Ok(Response::new(ExportMetricsServiceResponse::default()))
+    }
 }
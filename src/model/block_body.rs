@@ -0,0 +1,285 @@
+use amaru_kernel::{
+    Hash, Hasher, MemoizedTransactionOutput, Proposal, ProposalId, RawBlock, TransactionInput,
+};
+use anyhow::{Result, anyhow};
+use pallas_primitives::conway::{
+    AssetName, Certificate, Coin, MintedBlock, MintedTransactionOutput, MintedWitnessSet, PolicyId,
+    Redeemers, RewardAccount, Vote, Voter,
+};
+
+/// An owned, decoded view of a block's transactions, kept alongside the raw
+/// block so that the Chain page doesn't re-decode it on every frame.
+#[derive(Clone, Debug)]
+pub struct BlockBody {
+    pub transactions: Vec<BlockTransaction>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockTransaction {
+    pub index: usize,
+    pub id: Hash<32>,
+    /// False when the block lists this transaction as failing phase-2
+    /// validation, in which case only its collateral is consumed.
+    pub is_valid: bool,
+    pub fee: Coin,
+    pub validity_start: Option<u64>,
+    pub ttl: Option<u64>,
+    pub inputs: Vec<TransactionInput>,
+    pub reference_inputs: Vec<TransactionInput>,
+    pub collateral: Vec<TransactionInput>,
+    pub outputs: Vec<MemoizedTransactionOutput>,
+    pub certificates: Vec<Certificate>,
+    pub withdrawals: Vec<(RewardAccount, Coin)>,
+    pub mint: Vec<(PolicyId, AssetName, i64)>,
+    pub votes: Vec<(Voter, ProposalId, Vote)>,
+    pub proposals: Vec<Proposal>,
+    pub witnesses: WitnessCounts,
+    pub has_auxiliary_data: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WitnessCounts {
+    pub vkeys: usize,
+    pub bootstraps: usize,
+    pub native_scripts: usize,
+    pub plutus_v1_scripts: usize,
+    pub plutus_v2_scripts: usize,
+    pub plutus_v3_scripts: usize,
+    pub plutus_data: usize,
+    pub redeemers: usize,
+}
+
+impl TryFrom<&RawBlock> for BlockBody {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: &RawBlock) -> Result<Self> {
+        let bytes: &[u8] = raw.as_ref();
+        // Blocks are stored era-tagged, as they are received from upstream
+        // peers, but accept an untagged block too.
+        let block: MintedBlock<'_> = match minicbor::decode::<(u16, MintedBlock<'_>)>(bytes) {
+            Ok((_era, block)) => block,
+            Err(_) => minicbor::decode(bytes).map_err(|e| anyhow!("Can't decode block: {}", e))?,
+        };
+
+        let invalid: Vec<u32> = block
+            .invalid_transactions
+            .as_ref()
+            .map(|ixs| ixs.iter().copied().collect())
+            .unwrap_or_default();
+
+        let transactions = block
+            .transaction_bodies
+            .iter()
+            .zip(block.transaction_witness_sets.iter())
+            .enumerate()
+            .map(|(index, (body, witness_set))| {
+                let tx_index = index as u32;
+                Ok(BlockTransaction {
+                    index,
+                    id: Hasher::<256>::hash(body.raw_cbor()),
+                    is_valid: !invalid.contains(&tx_index),
+                    fee: body.fee,
+                    validity_start: body.validity_interval_start,
+                    ttl: body.ttl,
+                    inputs: body.inputs.iter().cloned().collect(),
+                    reference_inputs: body
+                        .reference_inputs
+                        .iter()
+                        .flat_map(|set| set.iter().cloned())
+                        .collect(),
+                    collateral: body
+                        .collateral
+                        .iter()
+                        .flat_map(|set| set.iter().cloned())
+                        .collect(),
+                    outputs: body
+                        .outputs
+                        .iter()
+                        .map(memoize_output)
+                        .collect::<Result<_>>()?,
+                    certificates: body
+                        .certificates
+                        .iter()
+                        .flat_map(|set| set.iter().cloned())
+                        .collect(),
+                    withdrawals: body
+                        .withdrawals
+                        .iter()
+                        .flat_map(|pairs| pairs.iter().cloned())
+                        .collect(),
+                    mint: body
+                        .mint
+                        .iter()
+                        .flat_map(|policies| policies.iter())
+                        .flat_map(|(policy, assets)| {
+                            assets
+                                .iter()
+                                .map(|(name, amount)| (*policy, name.clone(), i64::from(*amount)))
+                        })
+                        .collect(),
+                    votes: body
+                        .voting_procedures
+                        .iter()
+                        .flat_map(|voters| voters.iter())
+                        .flat_map(|(voter, votes)| {
+                            votes.iter().map(|(proposal, procedure)| {
+                                (voter.clone(), proposal.clone(), procedure.vote.clone())
+                            })
+                        })
+                        .collect(),
+                    proposals: body
+                        .proposal_procedures
+                        .iter()
+                        .flat_map(|set| set.iter().cloned())
+                        .collect(),
+                    witnesses: WitnessCounts::from(&**witness_set),
+                    has_auxiliary_data: block
+                        .auxiliary_data_set
+                        .iter()
+                        .any(|(ix, _)| *ix == tx_index),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { transactions })
+    }
+}
+
+/// Converts an output through its CBOR encoding, which is how the ledger
+/// stores it, so that the existing UTxO renderer can be reused as is.
+fn memoize_output(output: &MintedTransactionOutput<'_>) -> Result<MemoizedTransactionOutput> {
+    let bytes = minicbor::to_vec(output).map_err(|e| anyhow!("Can't encode output: {}", e))?;
+    minicbor::decode(&bytes).map_err(|e| anyhow!("Can't decode output: {}", e))
+}
+
+impl From<&MintedWitnessSet<'_>> for WitnessCounts {
+    fn from(ws: &MintedWitnessSet<'_>) -> Self {
+        Self {
+            vkeys: ws.vkeywitness.as_ref().map_or(0, |s| s.len()),
+            bootstraps: ws.bootstrap_witness.as_ref().map_or(0, |s| s.len()),
+            native_scripts: ws.native_script.as_ref().map_or(0, |s| s.len()),
+            plutus_v1_scripts: ws.plutus_v1_script.as_ref().map_or(0, |s| s.len()),
+            plutus_v2_scripts: ws.plutus_v2_script.as_ref().map_or(0, |s| s.len()),
+            plutus_v3_scripts: ws.plutus_v3_script.as_ref().map_or(0, |s| s.len()),
+            plutus_data: ws.plutus_data.as_ref().map_or(0, |s| s.len()),
+            redeemers: ws.redeemer.as_ref().map_or(0, |r| match &**r {
+                Redeemers::List(list) => list.len(),
+                Redeemers::Map(map) => map.len(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amaru_kernel::StakeCredential;
+    use minicbor::{Encoder, encode};
+    use pretty_assertions::assert_eq;
+    use std::convert::Infallible;
+
+    fn cbor(
+        f: impl FnOnce(&mut Encoder<&mut Vec<u8>>) -> Result<(), encode::Error<Infallible>>,
+    ) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        f(&mut Encoder::new(&mut bytes)).unwrap();
+        bytes
+    }
+
+    fn header() -> Vec<u8> {
+        cbor(|e| {
+            e.array(2)?.array(10)?;
+            e.u64(1)?.u64(42)?.null()?;
+            e.bytes(&[0; 32])?.bytes(&[0; 32])?;
+            e.array(2)?.bytes(&[0; 64])?.bytes(&[0; 80])?;
+            e.u64(0)?.bytes(&[0; 32])?;
+            e.array(4)?
+                .bytes(&[0; 32])?
+                .u64(0)?
+                .u64(0)?
+                .bytes(&[0; 64])?;
+            e.array(2)?.u64(10)?.u64(0)?;
+            e.bytes(&[0; 64])?;
+            Ok(())
+        })
+    }
+
+    /// A transaction with an input, an output, a stake registration and a
+    /// vote.
+    fn transaction() -> Vec<u8> {
+        cbor(|e| {
+            e.map(5)?;
+            e.u8(0)?.array(1)?.array(2)?.bytes(&[1; 32])?.u64(0)?;
+            // An enterprise address
+            let address = [[0x61].as_slice(), &[2; 28]].concat();
+            e.u8(1)?
+                .array(1)?
+                .array(2)?
+                .bytes(&address)?
+                .u64(1_000_000)?;
+            e.u8(2)?.u64(200)?;
+            e.u8(4)?
+                .array(1)?
+                .array(2)?
+                .u8(0)?
+                .array(2)?
+                .u8(0)?
+                .bytes(&[3; 28])?;
+            // A DRep voting yes on an action
+            e.u8(19)?.map(1)?.array(2)?.u8(2)?.bytes(&[4; 28])?;
+            e.map(1)?.array(2)?.bytes(&[5; 32])?.u32(0)?;
+            e.array(2)?.u8(1)?.null()?;
+            Ok(())
+        })
+    }
+
+    fn failed_transaction() -> Vec<u8> {
+        cbor(|e| {
+            e.map(3)?;
+            e.u8(0)?.array(1)?.array(2)?.bytes(&[6; 32])?.u64(1)?;
+            e.u8(1)?.array(0)?;
+            e.u8(2)?.u64(300)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_decode_block_body() {
+        // Era-tagged, with the second transaction failing phase-2 validation
+        let bytes = [
+            [0x82, 0x07, 0x85].as_slice(),
+            &header(),
+            &[0x82],
+            &transaction(),
+            &failed_transaction(),
+            // Empty witness sets and no auxiliary data
+            &[0x82, 0xa0, 0xa0, 0xa0],
+            &[0x81, 0x01],
+        ]
+        .concat();
+        let body = BlockBody::try_from(&RawBlock::from(bytes.as_slice())).unwrap();
+        assert_eq!(body.transactions.len(), 2);
+
+        let tx = &body.transactions[0];
+        assert_eq!(tx.id, Hasher::<256>::hash(&transaction()));
+        assert!(tx.is_valid);
+        assert_eq!(tx.fee, 200);
+        assert_eq!(tx.inputs.len(), 1);
+        assert_eq!(tx.outputs.len(), 1);
+        assert_eq!(
+            tx.certificates,
+            vec![Certificate::StakeRegistration(
+                StakeCredential::AddrKeyhash(Hash::new([3; 28]))
+            )]
+        );
+        assert_eq!(tx.votes.len(), 1);
+        assert_eq!(tx.votes[0].0, Voter::DRepKey(Hash::new([4; 28])));
+        assert_eq!(tx.votes[0].2, Vote::Yes);
+        assert!(!tx.has_auxiliary_data);
+
+        let failed = &body.transactions[1];
+        assert!(!failed.is_valid);
+        assert_eq!(failed.fee, 300);
+        assert!(failed.outputs.is_empty());
+    }
+}
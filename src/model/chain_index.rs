@@ -0,0 +1,192 @@
+use amaru_consensus::{BlockHeader, IsHeader, ReadOnlyChainStore};
+use amaru_kernel::Hash;
+use amaru_stores::rocksdb::consensus::ReadOnlyChainDB;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+use tracing::{debug, info};
+
+/// A query understood by the Chain page search bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChainQuery {
+    Hash(Hash<32>),
+    Slot(u64),
+    Height(u64),
+    Tip,
+}
+
+impl FromStr for ChainQuery {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let parse_number = |n: &str| {
+            n.trim()
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid number '{}'", n.trim()))
+        };
+
+        if s.eq_ignore_ascii_case("tip") {
+            Ok(ChainQuery::Tip)
+        } else if let Some(n) = s.strip_prefix("slot:") {
+            parse_number(n).map(ChainQuery::Slot)
+        } else if let Some(n) = s.strip_prefix("height:") {
+            parse_number(n).map(ChainQuery::Height)
+        } else {
+            Hash::<32>::from_str(s).map(ChainQuery::Hash).map_err(|_| {
+                format!(
+                    "Invalid query '{}': expected a block hash, slot:<n>, height:<n> or tip",
+                    s
+                )
+            })
+        }
+    }
+}
+
+impl fmt::Display for ChainQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainQuery::Hash(hash) => write!(f, "{}", hash),
+            ChainQuery::Slot(slot) => write!(f, "slot:{}", slot),
+            ChainQuery::Height(height) => write!(f, "height:{}", height),
+            ChainQuery::Tip => write!(f, "tip"),
+        }
+    }
+}
+
+/// The outcome of resolving a `ChainQuery` against the index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lookup {
+    Found(Hash<32>),
+    NotFound,
+    /// The index hasn't reached far enough back yet to answer.
+    Pending,
+}
+
+#[derive(Default)]
+struct IndexState {
+    by_slot: BTreeMap<u64, Hash<32>>,
+    by_height: BTreeMap<u64, Hash<32>>,
+    complete: bool,
+}
+
+/// Maps slots and block heights of the best chain to header hashes.
+///
+/// The index is built lazily, on the first slot or height lookup, by walking
+/// parents from the tip in a background thread. Since the walk goes backwards,
+/// a lookup can be answered as soon as the walk has gone past the requested
+/// slot or height, without waiting for the whole chain to be indexed.
+pub struct ChainIndex {
+    db: Arc<ReadOnlyChainDB>,
+    state: Arc<RwLock<IndexState>>,
+    started: bool,
+}
+
+impl ChainIndex {
+    pub fn new(db: Arc<ReadOnlyChainDB>) -> Self {
+        Self {
+            db,
+            state: Arc::new(RwLock::new(IndexState::default())),
+            started: false,
+        }
+    }
+
+    /// Number of headers indexed so far.
+    pub fn len(&self) -> usize {
+        self.state.read().unwrap().by_height.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn resolve(&mut self, query: &ChainQuery) -> Lookup {
+        match query {
+            ChainQuery::Hash(hash) => match self.db.load_header(hash) {
+                Some(_) => Lookup::Found(*hash),
+                None => Lookup::NotFound,
+            },
+            ChainQuery::Tip => Lookup::Found(
+                ReadOnlyChainStore::<BlockHeader>::get_best_chain_hash(&*self.db),
+            ),
+            ChainQuery::Slot(slot) => {
+                self.ensure_started();
+                let state = self.state.read().unwrap();
+                Self::lookup(&state.by_slot, *slot, state.complete)
+            }
+            ChainQuery::Height(height) => {
+                self.ensure_started();
+                let state = self.state.read().unwrap();
+                Self::lookup(&state.by_height, *height, state.complete)
+            }
+        }
+    }
+
+    fn lookup(map: &BTreeMap<u64, Hash<32>>, key: u64, complete: bool) -> Lookup {
+        if let Some(hash) = map.get(&key) {
+            return Lookup::Found(*hash);
+        }
+        let walked_past = map
+            .first_key_value()
+            .is_some_and(|(lowest, _)| *lowest <= key);
+        if complete || walked_past {
+            Lookup::NotFound
+        } else {
+            Lookup::Pending
+        }
+    }
+
+    fn ensure_started(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+
+        let db = self.db.clone();
+        let state = self.state.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut next = Some(ReadOnlyChainStore::<BlockHeader>::get_best_chain_hash(&*db));
+            while let Some(hash) = next {
+                let Some(header) = db.load_header(&hash) else {
+                    debug!("Header {} not found, stopping chain index walk", hash);
+                    break;
+                };
+                let mut state = state.write().unwrap();
+                state.by_slot.insert(header.slot(), hash);
+                state.by_height.insert(header.block_height(), hash);
+                next = header.parent();
+            }
+
+            let mut state = state.write().unwrap();
+            state.complete = true;
+            info!("Chain index built with {} headers", state.by_height.len());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_chain_query() {
+        assert_eq!(ChainQuery::from_str("tip"), Ok(ChainQuery::Tip));
+        assert_eq!(ChainQuery::from_str("slot:42"), Ok(ChainQuery::Slot(42)));
+        assert_eq!(
+            ChainQuery::from_str(" height:1000 "),
+            Ok(ChainQuery::Height(1000))
+        );
+        assert!(ChainQuery::from_str("slot:abc").is_err());
+        assert!(ChainQuery::from_str("not a hash").is_err());
+
+        let hash = "0".repeat(64);
+        assert_eq!(
+            ChainQuery::from_str(&hash),
+            Ok(ChainQuery::Hash(Hash::from_str(&hash).unwrap()))
+        );
+    }
+}
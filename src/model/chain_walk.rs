@@ -0,0 +1,373 @@
+use crate::{components::list::ListModel, ui::to_list_item::ToListItem, view::list::ListViewState};
+use amaru_consensus::{BlockHeader, IsHeader, ReadOnlyChainStore};
+use amaru_kernel::Hash;
+use amaru_stores::rocksdb::consensus::ReadOnlyChainDB;
+use ratatui::{Frame, layout::Rect};
+use std::{iter, sync::Arc};
+use tracing::{debug, warn};
+
+/// What the walker needs of a header.
+pub trait ChainHeader: Clone {
+    fn hash(&self) -> Hash<32>;
+    fn parent(&self) -> Option<Hash<32>>;
+    fn block_height(&self) -> u64;
+}
+
+impl ChainHeader for BlockHeader {
+    fn hash(&self) -> Hash<32> {
+        IsHeader::hash(self)
+    }
+
+    fn parent(&self) -> Option<Hash<32>> {
+        IsHeader::parent(self)
+    }
+
+    fn block_height(&self) -> u64 {
+        IsHeader::block_height(self)
+    }
+}
+
+/// What the walker reads of the chain store.
+pub trait HeaderStore {
+    type Header: ChainHeader;
+
+    /// The hash of the tip of the best chain.
+    fn tip(&self) -> Hash<32>;
+    fn header(&self, hash: &Hash<32>) -> Option<Self::Header>;
+    fn children(&self, hash: &Hash<32>) -> Vec<Hash<32>>;
+}
+
+impl HeaderStore for ReadOnlyChainDB {
+    type Header = BlockHeader;
+
+    fn tip(&self) -> Hash<32> {
+        ReadOnlyChainStore::<BlockHeader>::get_best_chain_hash(self)
+    }
+
+    fn header(&self, hash: &Hash<32>) -> Option<BlockHeader> {
+        self.load_header(hash)
+    }
+
+    fn children(&self, hash: &Hash<32>) -> Vec<Hash<32>> {
+        ReadOnlyChainStore::<BlockHeader>::get_children(self, hash)
+    }
+}
+
+/// A browsable window over the header chain, ordered from the newest (top) to
+/// the oldest (bottom) header.
+///
+/// Older headers are loaded lazily by following `BlockHeader::parent()` as the
+/// view scrolls down, newer ones by following the children of the top-most
+/// header when scrolling up past it. Where the chain forks, the child on the
+/// best chain is followed.
+pub struct ChainWalkModel<S: HeaderStore = ReadOnlyChainDB> {
+    db: Arc<S>,
+    headers: Vec<S::Header>,
+    /// Set once the oldest loaded header has no (known) parent.
+    exhausted: bool,
+    view: ListViewState,
+}
+
+impl<S: HeaderStore> ChainWalkModel<S> {
+    /// Creates a walker anchored at the tip of the best chain.
+    pub fn from_tip(db: Arc<S>) -> Self {
+        let tip = db.tip();
+        let mut model = Self {
+            db,
+            headers: Vec::new(),
+            exhausted: false,
+            view: ListViewState::new("Headers"),
+        };
+        match model.db.header(&tip) {
+            Some(header) => model.headers.push(header),
+            None => {
+                warn!("Tip header {} not found in chain db", tip);
+                model.exhausted = true;
+            }
+        }
+        model
+    }
+
+    /// Re-anchors the walker at the given header, discarding what was loaded.
+    pub fn anchor_at(&mut self, header: S::Header) {
+        self.headers = vec![header];
+        self.exhausted = false;
+        self.view.select(0, 1);
+        self.load_older(self.view.max_visible_index());
+    }
+
+    /// The headers of the list, from the newest loaded one down to the
+    /// oldest of the chain, loaded as the iterator goes.
+    pub fn walk(&self) -> impl Iterator<Item = S::Header> {
+        let db = self.db.clone();
+        iter::successors(self.headers.first().cloned(), move |header| {
+            header.parent().and_then(|hash| db.header(&hash))
+        })
+    }
+
+    pub fn selected_hash(&self) -> Option<Hash<32>> {
+        self.headers
+            .get(self.view.selected_index())
+            .map(|h| h.hash())
+    }
+
+    /// Follows parents until `index` is loaded or the chain is exhausted.
+    fn load_older(&mut self, index: usize) {
+        while !self.exhausted && self.headers.len() <= index {
+            let parent = self
+                .headers
+                .last()
+                .and_then(|h| h.parent())
+                .and_then(|hash| self.db.header(&hash));
+            match parent {
+                Some(header) => self.headers.push(header),
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    /// Prepends a child of the newest loaded header, if there is one.
+    /// Returns whether a header was added.
+    fn load_newer(&mut self) -> bool {
+        let Some(newest) = self.headers.first() else {
+            return false;
+        };
+        match self.newer_child(newest) {
+            Some(child) => {
+                self.headers.insert(0, child);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The child of a header on the best chain, found by walking back from
+    /// the tip to the height of the children. Forks are close to the tip, so
+    /// the walk is short where there is more than one child.
+    fn newer_child(&self, header: &S::Header) -> Option<S::Header> {
+        let children = self.db.children(&header.hash());
+        if children.len() > 1 {
+            let height = header.block_height() + 1;
+            let on_best_chain = iter::successors(self.db.header(&self.db.tip()), |h| {
+                h.parent().and_then(|hash| self.db.header(&hash))
+            })
+            .take_while(|h| h.block_height() >= height)
+            .find(|h| h.block_height() == height)
+            .filter(|h| children.contains(&h.hash()));
+            if on_best_chain.is_some() {
+                return on_best_chain;
+            }
+            debug!(
+                "Header {} has {} children off the best chain, following the first one",
+                header.hash(),
+                children.len()
+            );
+        }
+        children.first().and_then(|hash| self.db.header(hash))
+    }
+
+    fn total_len(&self) -> Option<usize> {
+        self.exhausted.then_some(self.headers.len())
+    }
+}
+
+impl<S> ListModel for ChainWalkModel<S>
+where
+    S: HeaderStore + Send + Sync + 'static,
+    S::Header: ToListItem + Send + Sync + 'static,
+{
+    type Item = S::Header;
+
+    fn draw(&self, f: &mut Frame, area: Rect, is_focused: bool) {
+        self.view.draw(f, area, &self.headers, is_focused);
+    }
+
+    fn selected_item(&self) -> Option<&S::Header> {
+        self.headers.get(self.view.selected_index())
+    }
+
+    fn items(&self) -> &[S::Header] {
+        &self.headers
+    }
+
+    fn select_index_by_row(&mut self, relative_row: usize) {
+        self.view
+            .select_index_by_row(relative_row, self.headers.len());
+    }
+
+    fn cursor_back(&mut self) {
+        if self.view.selected_index() == 0 {
+            // Walk forward: the newly loaded child becomes the selection
+            self.load_newer();
+            return;
+        }
+        self.view.cursor_back();
+    }
+
+    fn cursor_next(&mut self) {
+        self.load_older(self.view.selected_index() + 1);
+        self.view.cursor_next(Some(self.headers.len()));
+        self.load_older(self.view.max_visible_index());
+    }
+
+    fn retreat_window(&mut self) {
+        if self.view.offset() == 0 && self.load_newer() {
+            // Keep the same header selected now that everything shifted down
+            self.view
+                .select(self.view.selected_index() + 1, self.headers.len());
+            return;
+        }
+        self.view.retreat_window();
+    }
+
+    fn advance_window(&mut self) {
+        self.load_older(self.view.max_visible_index() + 1);
+        self.view.advance_window(self.total_len());
+    }
+
+    fn set_height(&mut self, height: usize) {
+        self.view.set_height(height);
+        self.load_older(self.view.max_visible_index());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::widgets::ListItem;
+    use std::collections::HashMap;
+
+    #[derive(Clone, Debug)]
+    struct Header {
+        hash: Hash<32>,
+        parent: Option<Hash<32>>,
+        height: u64,
+    }
+
+    impl ChainHeader for Header {
+        fn hash(&self) -> Hash<32> {
+            self.hash
+        }
+
+        fn parent(&self) -> Option<Hash<32>> {
+            self.parent
+        }
+
+        fn block_height(&self) -> u64 {
+            self.height
+        }
+    }
+
+    impl ToListItem for Header {
+        fn to_list_item(&self) -> ListItem<'static> {
+            ListItem::new(self.height.to_string())
+        }
+    }
+
+    struct Store {
+        headers: HashMap<Hash<32>, Header>,
+        tip: Hash<32>,
+    }
+
+    impl HeaderStore for Store {
+        type Header = Header;
+
+        fn tip(&self) -> Hash<32> {
+            self.tip
+        }
+
+        fn header(&self, hash: &Hash<32>) -> Option<Header> {
+            self.headers.get(hash).cloned()
+        }
+
+        fn children(&self, hash: &Hash<32>) -> Vec<Hash<32>> {
+            let mut children: Vec<_> = self
+                .headers
+                .values()
+                .filter(|header| header.parent == Some(*hash))
+                .map(|header| header.hash)
+                .collect();
+            children.sort();
+            children
+        }
+    }
+
+    fn hash(n: u8) -> Hash<32> {
+        Hash::new([n; 32])
+    }
+
+    /// The best chain 10 <- 20 <- 30 <- 40, and a fork 20 <- 5 whose hash
+    /// sorts before the child on the best chain.
+    fn store() -> Arc<Store> {
+        let headers = [
+            (10, None, 0),
+            (20, Some(10), 1),
+            (30, Some(20), 2),
+            (40, Some(30), 3),
+        ]
+        .into_iter()
+        .chain([(5, Some(20), 2)])
+        .map(|(n, parent, height)| {
+            let header = Header {
+                hash: hash(n),
+                parent: parent.map(hash),
+                height,
+            };
+            (header.hash, header)
+        })
+        .collect();
+        Arc::new(Store {
+            headers,
+            tip: hash(40),
+        })
+    }
+
+    fn heights(model: &ChainWalkModel<Store>) -> Vec<u64> {
+        model.items().iter().map(|h| h.height).collect()
+    }
+
+    #[test]
+    fn test_load_older() {
+        let mut model = ChainWalkModel::from_tip(store());
+        assert_eq!(heights(&model), vec![3]);
+        model.set_height(2);
+        assert_eq!(heights(&model), vec![3, 2, 1]);
+        model.set_height(10);
+        assert_eq!(heights(&model), vec![3, 2, 1, 0]);
+        assert_eq!(model.total_len(), Some(4));
+
+        let walked: Vec<_> = model.walk().map(|h| h.hash).collect();
+        assert_eq!(walked, vec![hash(40), hash(30), hash(20), hash(10)]);
+    }
+
+    #[test]
+    fn test_load_newer_follows_best_chain() {
+        let store = store();
+        let mut model = ChainWalkModel::from_tip(store.clone());
+        model.set_height(5);
+        model.anchor_at(store.header(&hash(20)).unwrap());
+        assert_eq!(heights(&model), vec![1, 0]);
+        assert_eq!(model.selected_hash(), Some(hash(20)));
+
+        model.cursor_back();
+        assert_eq!(model.selected_hash(), Some(hash(30)));
+        model.cursor_back();
+        assert_eq!(model.selected_hash(), Some(hash(40)));
+        model.cursor_back();
+        assert_eq!(model.selected_hash(), Some(hash(40)));
+        assert_eq!(heights(&model), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_anchor_at_fork() {
+        let store = store();
+        let mut model = ChainWalkModel::from_tip(store.clone());
+        model.anchor_at(store.header(&hash(5)).unwrap());
+        model.cursor_back();
+        assert_eq!(model.selected_hash(), Some(hash(5)));
+
+        let walked: Vec<_> = model.walk().map(|h| h.hash).collect();
+        assert_eq!(walked, vec![hash(5), hash(20), hash(10)]);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    components::{async_list::AsyncListModel, search_list::SearchProvider},
+    model::async_provider::AsyncProvider,
+    open_ledger_snapshot,
+    ui::to_list_item::{AccountItem, DRepItem, PoolItem, ProposalItem, UtxoItem},
+};
+use amaru_kernel::{Epoch, MemoizedTransactionOutput, Value};
+use amaru_ledger::store::ReadStore;
+use amaru_stores::rocksdb::ReadOnlyRocksDB;
+use anyhow::{Result, anyhow};
+use minicbor::Encode;
+use std::{cmp::Ordering, fmt, path::PathBuf, str::FromStr};
+use tracing::warn;
+
+/// The two epochs whose snapshots are compared, given as `<from>..<to>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DiffQuery {
+    pub from: Epoch,
+    pub to: Epoch,
+}
+
+impl FromStr for DiffQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (from, to) = s
+            .trim()
+            .split_once("..")
+            .ok_or_else(|| anyhow!("Expected <from epoch>..<to epoch>, got '{}'", s))?;
+        Ok(Self {
+            from: Epoch::from(from.trim().parse::<u64>()?),
+            to: Epoch::from(to.trim().parse::<u64>()?),
+        })
+    }
+}
+
+impl fmt::Display for DiffQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.from, self.to)
+    }
+}
+
+/// How an entry differs between the two snapshots.
+#[derive(Clone, Debug)]
+pub enum Change<T> {
+    Added(T),
+    Removed(T),
+    Changed(T, T),
+}
+
+impl<T> Change<T> {
+    pub fn before(&self) -> Option<&T> {
+        match self {
+            Change::Added(_) => None,
+            Change::Removed(before) | Change::Changed(before, _) => Some(before),
+        }
+    }
+
+    pub fn after(&self) -> Option<&T> {
+        match self {
+            Change::Removed(_) => None,
+            Change::Added(after) | Change::Changed(_, after) => Some(after),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Change::Added(_) => "added",
+            Change::Removed(_) => "removed",
+            Change::Changed(..) => "changed",
+        }
+    }
+}
+
+/// Totals over a snapshot's whole UTxO set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UtxoStats {
+    pub count: u64,
+    pub lovelace: u64,
+}
+
+/// One difference between two ledger snapshots.
+#[derive(Clone, Debug)]
+pub enum DiffEntry {
+    Account(Change<AccountItem>),
+    Pool(Change<PoolItem>),
+    DRep(Change<DRepItem>),
+    Proposal(Change<ProposalItem>),
+    /// UTxOs are too many to be listed one by one, so only their totals are
+    /// compared, along with how many were added and removed.
+    Utxos {
+        before: UtxoStats,
+        after: UtxoStats,
+        added: u64,
+        removed: u64,
+    },
+}
+
+impl DiffEntry {
+    pub fn entity(&self) -> &'static str {
+        match self {
+            DiffEntry::Account(_) => "account",
+            DiffEntry::Pool(_) => "pool",
+            DiffEntry::DRep(_) => "drep",
+            DiffEntry::Proposal(_) => "proposal",
+            DiffEntry::Utxos { .. } => "utxos",
+        }
+    }
+}
+
+/// Compares two snapshots and hands each difference to `emit` as soon as it
+/// is found, stopping early when `emit` returns false.
+///
+/// The store iterates each column in the order of its encoded keys, so both
+/// sides are walked in lockstep and nothing but the current entries is kept
+/// in memory.
+pub fn diff_snapshots(
+    before: &ReadOnlyRocksDB,
+    after: &ReadOnlyRocksDB,
+    mut emit: impl FnMut(DiffEntry) -> bool,
+) -> Result<()> {
+    let store_err = |e| anyhow!("{:?}", e);
+
+    let keep_going = diff_column(
+        ReadStore::iter_accounts(before).map_err(store_err)?,
+        ReadStore::iter_accounts(after).map_err(store_err)?,
+        |change| emit(DiffEntry::Account(change)),
+    )? && diff_column(
+        ReadStore::iter_pools(before).map_err(store_err)?,
+        ReadStore::iter_pools(after).map_err(store_err)?,
+        |change| emit(DiffEntry::Pool(change)),
+    )? && diff_column(
+        ReadStore::iter_dreps(before).map_err(store_err)?,
+        ReadStore::iter_dreps(after).map_err(store_err)?,
+        |change| emit(DiffEntry::DRep(change)),
+    )? && diff_column(
+        ReadStore::iter_proposals(before).map_err(store_err)?,
+        ReadStore::iter_proposals(after).map_err(store_err)?,
+        |change| emit(DiffEntry::Proposal(change)),
+    )?;
+    if !keep_going {
+        return Ok(());
+    }
+
+    // Totals are tallied as both sides stream by, unchanged UTxOs included
+    let (mut before_stats, mut after_stats) = (UtxoStats::default(), UtxoStats::default());
+    let (mut added, mut removed) = (0, 0);
+    diff_column(
+        ReadStore::iter_utxos(before)
+            .map_err(store_err)?
+            .inspect(|(_, output)| before_stats.add(output)),
+        ReadStore::iter_utxos(after)
+            .map_err(store_err)?
+            .inspect(|(_, output)| after_stats.add(output)),
+        |change: Change<UtxoItem>| {
+            match change {
+                Change::Added(_) => added += 1,
+                Change::Removed(_) => removed += 1,
+                Change::Changed(..) => {}
+            }
+            true
+        },
+    )?;
+    emit(DiffEntry::Utxos {
+        before: before_stats,
+        after: after_stats,
+        added,
+        removed,
+    });
+    Ok(())
+}
+
+/// Opens both snapshots of a query in the background and streams their
+/// differences.
+pub struct LedgerDiffProvider {
+    pub ledger_path: PathBuf,
+}
+
+impl SearchProvider<DiffQuery, DiffEntry> for LedgerDiffProvider {
+    fn search(&self, query: &DiffQuery) -> Option<AsyncListModel<DiffEntry>> {
+        let path = self.ledger_path.clone();
+        let query = *query;
+        let provider = AsyncProvider::new(move |tx| {
+            let result = open_ledger_snapshot(&path, query.from).and_then(|before| {
+                let after = open_ledger_snapshot(&path, query.to)?;
+                diff_snapshots(&before, &after, |entry| tx.blocking_send(entry).is_ok())
+            });
+            if let Err(e) = result {
+                warn!("Can't diff snapshots {}: {}", query, e);
+            }
+        });
+        Some(AsyncListModel::new("Changes", provider))
+    }
+}
+
+impl UtxoStats {
+    fn add(&mut self, output: &MemoizedTransactionOutput) {
+        self.count += 1;
+        self.lovelace += lovelace(&output.value);
+    }
+}
+
+/// The ADA part of a value, leaving native assets out.
+pub fn lovelace(value: &Value) -> u64 {
+    match value {
+        Value::Coin(coin) | Value::Multiasset(coin, _) => *coin,
+    }
+}
+
+fn encode<T: Encode<()>>(value: &T) -> Result<Vec<u8>> {
+    minicbor::to_vec(value).map_err(|e| anyhow!("Can't encode ledger entry: {}", e))
+}
+
+/// The next entry of a column, along with its encoded key.
+fn next_keyed<K: Encode<()>, V>(
+    iter: &mut impl Iterator<Item = (K, V)>,
+) -> Result<Option<(Vec<u8>, (K, V))>> {
+    iter.next()
+        .map(|(k, v)| Ok((encode(&k)?, (k, v))))
+        .transpose()
+}
+
+/// Merges two columns sorted by encoded key, reporting the entries only on
+/// one side and those whose encoded value differs. Returns false when
+/// `on_change` asked to stop, and fails when an entry can't be encoded.
+fn diff_column<K, V>(
+    mut before: impl Iterator<Item = (K, V)>,
+    mut after: impl Iterator<Item = (K, V)>,
+    mut on_change: impl FnMut(Change<(K, V)>) -> bool,
+) -> Result<bool>
+where
+    K: Encode<()>,
+    V: Encode<()>,
+{
+    let mut before_head = next_keyed(&mut before)?;
+    let mut after_head = next_keyed(&mut after)?;
+
+    loop {
+        let order = match (&before_head, &after_head) {
+            (None, None) => return Ok(true),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some((b, _)), Some((a, _))) => b.cmp(a),
+        };
+        let change = match order {
+            Ordering::Less => {
+                let (_, item) = before_head.take().expect("peeked");
+                before_head = next_keyed(&mut before)?;
+                Some(Change::Removed(item))
+            }
+            Ordering::Greater => {
+                let (_, item) = after_head.take().expect("peeked");
+                after_head = next_keyed(&mut after)?;
+                Some(Change::Added(item))
+            }
+            Ordering::Equal => {
+                let (_, b) = before_head.take().expect("peeked");
+                let (_, a) = after_head.take().expect("peeked");
+                before_head = next_keyed(&mut before)?;
+                after_head = next_keyed(&mut after)?;
+                (encode(&b.1)? != encode(&a.1)?).then_some(Change::Changed(b, a))
+            }
+        };
+        if let Some(change) = change
+            && !on_change(change)
+        {
+            return Ok(false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn changes(before: Vec<(u8, u8)>, after: Vec<(u8, u8)>) -> Vec<String> {
+        let mut changes = Vec::new();
+        diff_column(before.into_iter(), after.into_iter(), |change| {
+            changes.push(match change {
+                Change::Added((k, _)) => format!("+{}", k),
+                Change::Removed((k, _)) => format!("-{}", k),
+                Change::Changed((k, _), _) => format!("~{}", k),
+            });
+            true
+        })
+        .unwrap();
+        changes
+    }
+
+    #[test]
+    fn test_diff_column() {
+        assert_eq!(
+            changes(
+                vec![(1, 0), (2, 0), (4, 0)],
+                vec![(2, 1), (3, 0), (4, 0), (5, 0)]
+            ),
+            vec!["-1", "~2", "+3", "+5"]
+        );
+        assert_eq!(changes(vec![(1, 0)], vec![]), vec!["-1"]);
+    }
+
+    #[test]
+    fn test_parse_diff_query() {
+        let query: DiffQuery = "5..6".parse().unwrap();
+        assert_eq!(query.to_string(), "5..6");
+        assert!("5".parse::<DiffQuery>().is_err());
+    }
+}
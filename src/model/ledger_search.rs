@@ -1,25 +1,254 @@
 use crate::{
     components::{async_list::AsyncListModel, search_list::SearchProvider},
     model::async_provider::AsyncProvider,
-    ui::to_list_item::UtxoItem,
+    store::{
+        raw_ledger::{LedgerColumn, RawLedger},
+        utxo_index::SharedUtxoIndex,
+    },
+    ui::to_list_item::{AccountItem, DRepItem, PoolItem, ProposalItem, ToListItem, UtxoItem},
 };
-use amaru_kernel::Address;
-use amaru_ledger::store::ReadStore;
+use amaru_kernel::{Address, Hash, PoolId, ProposalId, StakeCredential, TransactionInput};
+use amaru_ledger::store::{ReadStore, columns::proposals};
 use amaru_stores::rocksdb::ReadOnlyRocksDB;
-use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use minicbor::{Decode, Encode};
+use pallas_addresses::{ShelleyDelegationPart, ShelleyPaymentPart};
+use std::{str::FromStr, sync::Arc};
+use tracing::warn;
 
+/// Decodes an id given either as bech32, with one of the accepted human
+/// readable parts, or as hex. Returns the matched human readable part, if any.
+fn decode_id<'a>(s: &str, hrps: &[&'a str]) -> Result<(Option<&'a str>, Vec<u8>)> {
+    let s = s.trim();
+    if let Ok((hrp, data)) = bech32::decode(s) {
+        return hrps
+            .iter()
+            .find(|expected| hrp.as_str() == **expected)
+            .map(|expected| (Some(*expected), data))
+            .ok_or_else(|| anyhow!("Unexpected bech32 prefix '{}'", hrp));
+    }
+    let bytes = hex::decode(s).map_err(|_| anyhow!("'{}' is neither bech32 nor hex", s))?;
+    Ok((None, bytes))
+}
+
+fn hash28(bytes: &[u8]) -> Result<Hash<28>> {
+    <[u8; 28]>::try_from(bytes)
+        .map(Hash::from)
+        .map_err(|_| anyhow!("Expected 28 bytes, got {}", bytes.len()))
+}
+
+/// A stake credential, given as a reward address (`stake1...`, or its hex
+/// bytes) or as a bare 28 bytes key hash in hex.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StakeCredentialQuery(pub StakeCredential);
+
+impl FromStr for StakeCredentialQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (_, bytes) = decode_id(s, &["stake", "stake_test"])?;
+        let credential = match bytes.as_slice() {
+            // Reward address header: bit 4 tells a script from a key hash
+            [header, hash @ ..] if hash.len() == 28 => {
+                if header & 0x10 == 0 {
+                    StakeCredential::AddrKeyhash(hash28(hash)?)
+                } else {
+                    StakeCredential::ScriptHash(hash28(hash)?)
+                }
+            }
+            hash => StakeCredential::AddrKeyhash(hash28(hash)?),
+        };
+        Ok(Self(credential))
+    }
+}
+
+/// A pool id, given as `pool1...` or hex.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PoolIdQuery(pub PoolId);
+
+impl FromStr for PoolIdQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (_, bytes) = decode_id(s, &["pool"])?;
+        Ok(Self(hash28(&bytes)?))
+    }
+}
+
+/// A DRep id, given as a CIP-129 `drep1...` (with a header byte), a CIP-105
+/// `drep1...` / `drep_script1...` (without), or the hex of either.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DRepIdQuery(pub StakeCredential);
+
+impl FromStr for DRepIdQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (hrp, bytes) = decode_id(s, &["drep", "drep_script"])?;
+        let credential = match (hrp, bytes.as_slice()) {
+            (Some("drep_script"), hash) => StakeCredential::ScriptHash(hash28(hash)?),
+            // CIP-129 header: the low nibble tells a script from a key hash
+            (_, [header, hash @ ..]) if hash.len() == 28 => match header & 0x0f {
+                0x02 => StakeCredential::AddrKeyhash(hash28(hash)?),
+                0x03 => StakeCredential::ScriptHash(hash28(hash)?),
+                _ => return Err(anyhow!("Unexpected DRep header byte {:#04x}", header)),
+            },
+            (_, hash) => StakeCredential::AddrKeyhash(hash28(hash)?),
+        };
+        Ok(Self(credential))
+    }
+}
+
+/// A governance action id, given as `<tx id>#<index>`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProposalIdQuery {
+    pub transaction_id: Hash<32>,
+    pub action_index: u32,
+}
+
+impl FromStr for ProposalIdQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (tx_id, index) = s
+            .trim()
+            .split_once('#')
+            .ok_or_else(|| anyhow!("Expected <tx id>#<index>, got '{}'", s))?;
+        Ok(Self {
+            transaction_id: Hash::from_str(tx_id).map_err(|_| anyhow!("Invalid tx id"))?,
+            action_index: index.parse()?,
+        })
+    }
+}
+
+/// What UTxOs are searched by: a full address, or either of its credentials
+/// so that all the base addresses of a wallet are found at once, or the
+/// transaction that produced them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UtxoQuery {
+    Address(Address),
+    /// Given as `payment:` followed by a hex key hash, `addr_vkh1...` or
+    /// `script1...`.
+    PaymentCredential(StakeCredential),
+    /// Given as `stake:` followed by a hex key hash, or as a `stake1...`
+    /// reward address.
+    StakeCredential(StakeCredential),
+    /// Given as `tx:` followed by a hex transaction id.
+    TransactionId(Hash<32>),
+}
+
+impl FromStr for UtxoQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(credential) = s.strip_prefix("payment:") {
+            let (hrp, bytes) = decode_id(credential, &["addr_vkh", "script"])?;
+            let hash = hash28(&bytes)?;
+            return Ok(UtxoQuery::PaymentCredential(match hrp {
+                Some("script") => StakeCredential::ScriptHash(hash),
+                _ => StakeCredential::AddrKeyhash(hash),
+            }));
+        }
+        if let Some(credential) = s.strip_prefix("stake:") {
+            return StakeCredentialQuery::from_str(credential)
+                .map(|q| UtxoQuery::StakeCredential(q.0));
+        }
+        if let Some(tx_id) = s.strip_prefix("tx:") {
+            return Hash::from_str(tx_id.trim())
+                .map(UtxoQuery::TransactionId)
+                .map_err(|_| anyhow!("Invalid tx id '{}'", tx_id));
+        }
+        if s.starts_with("stake") {
+            return StakeCredentialQuery::from_str(s).map(|q| UtxoQuery::StakeCredential(q.0));
+        }
+        Address::from_str(s)
+            .map(UtxoQuery::Address)
+            .map_err(|e| anyhow!("Invalid address '{}': {:?}", s, e))
+    }
+}
+
+impl UtxoQuery {
+    pub fn matches(&self, input: &TransactionInput, address: &Address) -> bool {
+        match self {
+            UtxoQuery::Address(expected) => expected == address,
+            UtxoQuery::PaymentCredential(expected) => {
+                payment_credential(address).as_ref() == Some(expected)
+            }
+            UtxoQuery::StakeCredential(expected) => {
+                stake_credential(address).as_ref() == Some(expected)
+            }
+            UtxoQuery::TransactionId(expected) => input.transaction_id == *expected,
+        }
+    }
+
+    /// Whether the UTxO index can answer the query, rather than a scan.
+    pub fn is_indexed(&self) -> bool {
+        !matches!(self, UtxoQuery::TransactionId(_))
+    }
+}
+
+pub fn payment_credential(address: &Address) -> Option<StakeCredential> {
+    match address {
+        Address::Shelley(shelley) => match shelley.payment() {
+            ShelleyPaymentPart::Key(hash) => Some(StakeCredential::AddrKeyhash(*hash)),
+            ShelleyPaymentPart::Script(hash) => Some(StakeCredential::ScriptHash(*hash)),
+        },
+        _ => None,
+    }
+}
+
+pub fn stake_credential(address: &Address) -> Option<StakeCredential> {
+    match address {
+        Address::Shelley(shelley) => match shelley.delegation() {
+            ShelleyDelegationPart::Key(hash) => Some(StakeCredential::AddrKeyhash(*hash)),
+            ShelleyDelegationPart::Script(hash) => Some(StakeCredential::ScriptHash(*hash)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Searches UTxOs through the on-disk index when one was built for the
+/// ledger's current tip, and by scanning the whole UTxO set otherwise.
 pub struct LedgerUtxoProvider {
-    pub db: Arc<ReadOnlyRocksDB>,
+    db: Arc<ReadOnlyRocksDB>,
+    index: SharedUtxoIndex,
+}
+
+impl LedgerUtxoProvider {
+    pub fn new(db: Arc<ReadOnlyRocksDB>, index: SharedUtxoIndex) -> Self {
+        Self { db, index }
+    }
 }
 
-impl SearchProvider<Address, UtxoItem> for LedgerUtxoProvider {
-    fn search(&self, address: &Address) -> Option<AsyncListModel<UtxoItem>> {
+impl SearchProvider<UtxoQuery, UtxoItem> for LedgerUtxoProvider {
+    fn search(&self, query: &UtxoQuery) -> Option<AsyncListModel<UtxoItem>> {
         let db = self.db.clone();
-        let owned_addr = address.clone();
+        let index = self.index.clone();
+        let query = query.clone();
 
         let provider = AsyncProvider::new(move |tx| {
+            let index = query.is_indexed().then(|| index.get()).flatten();
+            if let Some(index) = index {
+                match index.lookup(&query) {
+                    Ok(inputs) => {
+                        for input in inputs {
+                            let Ok(Some(output)) = ReadStore::utxo(&*db, &input) else {
+                                continue;
+                            };
+                            if tx.blocking_send((input, output)).is_err() {
+                                break;
+                            }
+                        }
+                        return;
+                    }
+                    Err(e) => warn!("UTxO index lookup failed, falling back to a scan: {}", e),
+                }
+            }
+
             if let Ok(iter) = ReadStore::iter_utxos(&*db) {
-                let filtered = iter.filter(move |(_, out)| out.address == owned_addr);
+                let filtered = iter.filter(move |(input, out)| query.matches(input, &out.address));
                 for item in filtered {
                     if tx.blocking_send(item).is_err() {
                         break;
@@ -30,3 +259,162 @@ impl SearchProvider<Address, UtxoItem> for LedgerUtxoProvider {
         Some(AsyncListModel::new("Utxos by Addr", provider))
     }
 }
+
+/// Sends the single result of a lookup, if any, to an async list.
+fn lookup_list<T, F>(title: &'static str, lookup: F) -> AsyncListModel<T>
+where
+    T: ToListItem + Send + Sync + 'static,
+    F: FnOnce() -> Option<T> + Send + 'static,
+{
+    let provider = AsyncProvider::new(move |tx| {
+        if let Some(item) = lookup() {
+            let _ = tx.blocking_send(item);
+        }
+    });
+    AsyncListModel::new(title, provider)
+}
+
+pub struct LedgerAccountProvider {
+    pub db: Arc<ReadOnlyRocksDB>,
+}
+
+impl SearchProvider<StakeCredentialQuery, AccountItem> for LedgerAccountProvider {
+    fn search(&self, query: &StakeCredentialQuery) -> Option<AsyncListModel<AccountItem>> {
+        let db = self.db.clone();
+        let credential = query.0.clone();
+        Some(lookup_list("Account", move || {
+            let row = ReadStore::account(&*db, &credential).ok()??;
+            Some((credential, row))
+        }))
+    }
+}
+
+pub struct LedgerPoolProvider {
+    pub db: Arc<ReadOnlyRocksDB>,
+}
+
+impl SearchProvider<PoolIdQuery, PoolItem> for LedgerPoolProvider {
+    fn search(&self, query: &PoolIdQuery) -> Option<AsyncListModel<PoolItem>> {
+        let db = self.db.clone();
+        let pool = query.0;
+        Some(lookup_list("Pool", move || {
+            let row = ReadStore::pool(&*db, &pool).ok()??;
+            Some((pool, row))
+        }))
+    }
+}
+
+/// The store has no point lookup for DReps, so this gets the stored value of
+/// the key from the raw handle of the ledger, `None` when it can't be opened.
+pub struct LedgerDRepProvider {
+    pub raw: Option<Arc<RawLedger>>,
+}
+
+impl SearchProvider<DRepIdQuery, DRepItem> for LedgerDRepProvider {
+    fn search(&self, query: &DRepIdQuery) -> Option<AsyncListModel<DRepItem>> {
+        let raw = self.raw.clone()?;
+        let credential = query.0.clone();
+        Some(lookup_list("DRep", move || {
+            let row = raw_lookup(&raw, LedgerColumn::DReps, &credential)?;
+            Some((credential, row))
+        }))
+    }
+}
+
+/// Like DReps, proposals are looked up by their stored key.
+pub struct LedgerProposalProvider {
+    pub raw: Option<Arc<RawLedger>>,
+}
+
+impl SearchProvider<ProposalIdQuery, ProposalItem> for LedgerProposalProvider {
+    fn search(&self, query: &ProposalIdQuery) -> Option<AsyncListModel<ProposalItem>> {
+        let raw = self.raw.clone()?;
+        let key = proposals::Key {
+            inner: ProposalId {
+                transaction_id: query.transaction_id,
+                action_index: query.action_index,
+            },
+        };
+        Some(lookup_list("Proposal", move || {
+            let row = raw_lookup(&raw, LedgerColumn::Proposals, &key)?;
+            Some((key, row))
+        }))
+    }
+}
+
+/// Decodes the value of a key of the ledger, logging failures.
+fn raw_lookup<K, V>(raw: &RawLedger, column: LedgerColumn, key: &K) -> Option<V>
+where
+    K: Encode<()>,
+    V: for<'b> Decode<'b, ()>,
+{
+    raw.decode(column, key).unwrap_or_else(|e| {
+        warn!("Ledger lookup failed: {}", e);
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bech32::{Bech32, Hrp};
+    use pretty_assertions::assert_eq;
+
+    const HASH: &str = "0123456789abcdef0123456789abcdef0123456789abcdef01234567";
+
+    fn bech32(hrp: &str, bytes: &[u8]) -> String {
+        bech32::encode::<Bech32>(Hrp::parse(hrp).unwrap(), bytes).unwrap()
+    }
+
+    #[test]
+    fn test_parse_ids() {
+        let hash = Hash::<28>::from_str(HASH).unwrap();
+        let mut script_reward_account = vec![0xf1];
+        script_reward_account.extend_from_slice(hash.as_ref());
+        let mut cip129_drep = vec![0x22];
+        cip129_drep.extend_from_slice(hash.as_ref());
+
+        assert_eq!(
+            StakeCredentialQuery::from_str(HASH).unwrap().0,
+            StakeCredential::AddrKeyhash(hash)
+        );
+        assert_eq!(
+            StakeCredentialQuery::from_str(&bech32("stake", &script_reward_account))
+                .unwrap()
+                .0,
+            StakeCredential::ScriptHash(hash)
+        );
+        assert_eq!(
+            PoolIdQuery::from_str(&bech32("pool", hash.as_ref()))
+                .unwrap()
+                .0,
+            hash
+        );
+        assert_eq!(
+            DRepIdQuery::from_str(&bech32("drep", &cip129_drep))
+                .unwrap()
+                .0,
+            StakeCredential::AddrKeyhash(hash)
+        );
+        assert_eq!(
+            DRepIdQuery::from_str(&bech32("drep_script", hash.as_ref()))
+                .unwrap()
+                .0,
+            StakeCredential::ScriptHash(hash)
+        );
+        assert!(PoolIdQuery::from_str(&bech32("drep", hash.as_ref())).is_err());
+
+        let tx_id = "ab".repeat(32);
+        assert_eq!(
+            ProposalIdQuery::from_str(&format!("{}#3", tx_id)).unwrap(),
+            ProposalIdQuery {
+                transaction_id: Hash::from_str(&tx_id).unwrap(),
+                action_index: 3,
+            }
+        );
+        assert_eq!(
+            UtxoQuery::from_str(&format!("tx:{}", tx_id)).unwrap(),
+            UtxoQuery::TransactionId(Hash::from_str(&tx_id).unwrap())
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{
+    model::{ledger_diff::lovelace, ledger_search::stake_credential},
+    store::owned_iter::{
+        OwnedAccountIter, OwnedDRepIter, OwnedPoolIter, OwnedProposalIter, OwnedUtxoIter,
+    },
+};
+use amaru_kernel::{Address, DRep, Epoch, Hash, PoolId, StakeCredential, Value};
+use amaru_stores::rocksdb::ReadOnlyRocksDB;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt, mem,
+    sync::{
+        Arc, RwLock, RwLockReadGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+/// How many pools and DReps are kept in the stake distributions.
+const TOP_N: usize = 10;
+
+/// How many items are scanned between two updates of the shared stats.
+const PUBLISH_EVERY: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScanPhase {
+    #[default]
+    Accounts,
+    Pools,
+    DReps,
+    Proposals,
+    Utxos,
+    Done,
+}
+
+impl fmt::Display for ScanPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ScanPhase::Accounts => "accounts",
+            ScanPhase::Pools => "pools",
+            ScanPhase::DReps => "dreps",
+            ScanPhase::Proposals => "proposals",
+            ScanPhase::Utxos => "utxos",
+            ScanPhase::Done => "done",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Totals over a whole ledger, filled in as the scan progresses.
+#[derive(Clone, Debug, Default)]
+pub struct LedgerStats {
+    pub phase: ScanPhase,
+    /// Items scanned in the current phase.
+    pub scanned: u64,
+
+    pub accounts: u64,
+    pub delegated_accounts: u64,
+    pub pools: u64,
+    pub pools_retiring: u64,
+    pub dreps: u64,
+    pub proposals: u64,
+    /// Proposals still valid at the epoch of the ledger, when it is known.
+    pub active_proposals: Option<u64>,
+    pub utxos: u64,
+    pub lovelace: u64,
+    pub native_assets: u64,
+
+    /// Stake delegated to each pool, rewards included, highest first.
+    pub top_pools: Vec<(PoolId, u64)>,
+    /// Stake delegated to each DRep, rewards included, highest first.
+    pub top_dreps: Vec<(DRep, u64)>,
+}
+
+/// Computes `LedgerStats` on a background thread, started on first access.
+pub struct LedgerStatsModel {
+    db: Arc<ReadOnlyRocksDB>,
+    epoch: Option<Epoch>,
+    stats: Arc<RwLock<LedgerStats>>,
+    started: bool,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl LedgerStatsModel {
+    /// `epoch` is the epoch the ledger is at, used to tell active proposals
+    /// from expired ones.
+    pub fn new(db: Arc<ReadOnlyRocksDB>, epoch: Option<Epoch>) -> Self {
+        Self {
+            db,
+            epoch,
+            stats: Arc::new(RwLock::new(LedgerStats::default())),
+            started: false,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+
+        let scan = Scan {
+            db: self.db.clone(),
+            shared: self.stats.clone(),
+            cancelled: self.cancelled.clone(),
+            tally: Tally::new(self.epoch),
+        };
+        thread::spawn(move || scan.run());
+    }
+
+    pub fn stats(&self) -> RwLockReadGuard<'_, LedgerStats> {
+        self.stats.read().unwrap()
+    }
+}
+
+impl Drop for LedgerStatsModel {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+struct Scan {
+    db: Arc<ReadOnlyRocksDB>,
+    shared: Arc<RwLock<LedgerStats>>,
+    cancelled: Arc<AtomicBool>,
+    tally: Tally,
+}
+
+impl Scan {
+    fn publish(&self) {
+        *self.shared.write().unwrap() = self.tally.stats.clone();
+    }
+
+    /// Counts one more scanned item, publishing every so often. Returns false
+    /// once the model is gone.
+    fn tick(&mut self) -> bool {
+        self.tally.stats.scanned += 1;
+        if self.tally.stats.scanned.is_multiple_of(PUBLISH_EVERY) {
+            self.publish();
+        }
+        !self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn enter(&mut self, phase: ScanPhase) {
+        self.tally.stats.phase = phase;
+        self.tally.stats.scanned = 0;
+        self.publish();
+    }
+
+    fn run(mut self) {
+        self.enter(ScanPhase::Accounts);
+        for (credential, row) in OwnedAccountIter::new(self.db.clone()) {
+            self.tally.add_account(
+                credential,
+                row.pool.map(|(pool, _)| pool),
+                row.drep.map(|(drep, _)| drep),
+                row.rewards,
+            );
+            if !self.tick() {
+                return;
+            }
+        }
+
+        self.enter(ScanPhase::Pools);
+        for (_, row) in OwnedPoolIter::new(self.db.clone()) {
+            self.tally
+                .add_pool(row.future_params.iter().any(|(params, _)| params.is_none()));
+            if !self.tick() {
+                return;
+            }
+        }
+
+        self.enter(ScanPhase::DReps);
+        for _ in OwnedDRepIter::new(self.db.clone()) {
+            self.tally.add_drep();
+            if !self.tick() {
+                return;
+            }
+        }
+
+        self.enter(ScanPhase::Proposals);
+        for (_, row) in OwnedProposalIter::new(self.db.clone()) {
+            self.tally.add_proposal(row.valid_until);
+            if !self.tick() {
+                return;
+            }
+        }
+
+        self.enter(ScanPhase::Utxos);
+        for (_, output) in OwnedUtxoIter::new(self.db.clone()) {
+            self.tally.add_utxo(&output.address, &output.value);
+            if !self.tick() {
+                return;
+            }
+        }
+
+        self.tally.finish();
+        self.enter(ScanPhase::Done);
+    }
+}
+
+/// The stats and the stake distributions, as the items of each column are
+/// added.
+struct Tally {
+    epoch: Option<Epoch>,
+    stats: LedgerStats,
+    delegations: HashMap<StakeCredential, (Option<PoolId>, Option<DRep>)>,
+    pool_stake: BTreeMap<PoolId, u64>,
+    drep_stake: BTreeMap<DRep, u64>,
+    assets: HashSet<(Hash<28>, String)>,
+}
+
+impl Tally {
+    fn new(epoch: Option<Epoch>) -> Self {
+        Self {
+            epoch,
+            stats: LedgerStats {
+                active_proposals: epoch.map(|_| 0),
+                ..LedgerStats::default()
+            },
+            delegations: HashMap::new(),
+            pool_stake: BTreeMap::new(),
+            drep_stake: BTreeMap::new(),
+            assets: HashSet::new(),
+        }
+    }
+
+    fn add_account(
+        &mut self,
+        credential: StakeCredential,
+        pool: Option<PoolId>,
+        drep: Option<DRep>,
+        rewards: u64,
+    ) {
+        self.stats.accounts += 1;
+        if let Some(pool) = pool {
+            self.stats.delegated_accounts += 1;
+            *self.pool_stake.entry(pool).or_default() += rewards;
+        }
+        if let Some(drep) = &drep {
+            *self.drep_stake.entry(drep.clone()).or_default() += rewards;
+        }
+        if pool.is_some() || drep.is_some() {
+            self.delegations.insert(credential, (pool, drep));
+        }
+    }
+
+    fn add_pool(&mut self, retiring: bool) {
+        self.stats.pools += 1;
+        if retiring {
+            self.stats.pools_retiring += 1;
+        }
+    }
+
+    fn add_drep(&mut self) {
+        self.stats.dreps += 1;
+    }
+
+    fn add_proposal(&mut self, valid_until: Epoch) {
+        self.stats.proposals += 1;
+        if let (Some(epoch), Some(active)) = (self.epoch, &mut self.stats.active_proposals)
+            && valid_until >= epoch
+        {
+            *active += 1;
+        }
+    }
+
+    /// Counts a UTxO, adding its coins to the stake of the pool and the DRep
+    /// its stake credential delegates to.
+    fn add_utxo(&mut self, address: &Address, value: &Value) {
+        let coin = lovelace(value);
+        self.stats.utxos += 1;
+        self.stats.lovelace += coin;
+        if let Value::Multiasset(_, multiasset) = value {
+            for (policy, names) in multiasset.iter() {
+                for (name, _) in names.iter() {
+                    self.assets.insert((*policy, name.to_string()));
+                }
+            }
+            self.stats.native_assets = self.assets.len() as u64;
+        }
+        if let Some((pool, drep)) = stake_credential(address).and_then(|c| self.delegations.get(&c))
+        {
+            if let Some(pool) = pool {
+                *self.pool_stake.entry(*pool).or_default() += coin;
+            }
+            if let Some(drep) = drep {
+                *self.drep_stake.entry(drep.clone()).or_default() += coin;
+            }
+        }
+    }
+
+    /// Fills in the stake distributions, once all the items are added.
+    fn finish(&mut self) {
+        self.stats.top_pools = top(mem::take(&mut self.pool_stake));
+        self.stats.top_dreps = top(mem::take(&mut self.drep_stake));
+    }
+}
+
+fn top<K>(stake: BTreeMap<K, u64>) -> Vec<(K, u64)> {
+    let mut stake: Vec<_> = stake.into_iter().collect();
+    stake.sort_by(|a, b| b.1.cmp(&a.1));
+    stake.truncate(TOP_N);
+    stake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// A base address, whose stake part is the key hash `stake`.
+    fn address(stake: u8) -> Address {
+        Address::from_bytes(&[[0x00].as_slice(), &[0; 28], &[stake; 28]].concat()).unwrap()
+    }
+
+    #[test]
+    fn test_tally() {
+        let (pool1, pool2) = (Hash::new([1; 28]), Hash::new([2; 28]));
+        let drep = DRep::Key(Hash::new([3; 28]));
+        let mut tally = Tally::new(Some(Epoch::from(10u64)));
+
+        tally.add_account(
+            StakeCredential::AddrKeyhash(Hash::new([4; 28])),
+            Some(pool1),
+            Some(drep.clone()),
+            5,
+        );
+        tally.add_account(
+            StakeCredential::AddrKeyhash(Hash::new([5; 28])),
+            Some(pool2),
+            None,
+            1,
+        );
+        tally.add_account(
+            StakeCredential::AddrKeyhash(Hash::new([6; 28])),
+            None,
+            None,
+            7,
+        );
+        tally.add_pool(false);
+        tally.add_pool(true);
+        tally.add_drep();
+        tally.add_proposal(Epoch::from(9u64));
+        tally.add_proposal(Epoch::from(10u64));
+        tally.add_utxo(&address(4), &Value::Coin(100));
+        tally.add_utxo(&address(6), &Value::Coin(50));
+        tally.finish();
+
+        let stats = tally.stats;
+        assert_eq!(stats.accounts, 3);
+        assert_eq!(stats.delegated_accounts, 2);
+        assert_eq!((stats.pools, stats.pools_retiring), (2, 1));
+        assert_eq!(stats.dreps, 1);
+        assert_eq!((stats.proposals, stats.active_proposals), (2, Some(1)));
+        assert_eq!((stats.utxos, stats.lovelace), (2, 150));
+        // Stake counts rewards and the coins of UTxOs at delegated addresses
+        assert_eq!(stats.top_pools, vec![(pool1, 105), (pool2, 1)]);
+        assert_eq!(stats.top_dreps, vec![(drep, 105)]);
+    }
+}
@@ -41,6 +41,19 @@ impl<T: ToListItem> ListModelView<T> {
         self.iter.buffer().get(index)
     }
 
+    /// Returns the items loaded so far.
+    pub fn items(&self) -> &[T] {
+        self.iter.buffer()
+    }
+
+    /// Selects the item at `index`, loading the items up to it.
+    pub fn select_index(&mut self, index: usize) {
+        self.iter.load_up_to(index);
+        self.view.select(index, self.iter.buffer().len());
+        let required_index = self.view.max_visible_index();
+        self.iter.load_up_to(required_index);
+    }
+
     /// Sets the selected index based on a row clicked within the visible
     /// window.
     pub fn select_index_by_row(&mut self, relative_row: usize) {
@@ -1,8 +1,13 @@
 pub mod async_provider;
+pub mod block_body;
 pub mod buffer_list;
 pub mod button;
+pub mod chain_index;
+pub mod chain_walk;
 pub mod cursor;
+pub mod ledger_diff;
 pub mod ledger_search;
+pub mod ledger_stats;
 pub mod list_view;
 pub mod min_max_window;
 pub mod otel_view;
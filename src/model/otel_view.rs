@@ -32,6 +32,9 @@ pub struct OtelViewState {
     pub selected_span: Option<Arc<Span>>,
 
     pub selected_trace_id: Option<TraceId>,
+
+    /// Whether the flame graph highlights the critical path of the trace.
+    pub show_critical_path: bool,
 }
 
 impl OtelViewState {
@@ -42,6 +45,7 @@ impl OtelViewState {
             focused_span: None,
             selected_span: None,
             selected_trace_id: None,
+            show_critical_path: false,
         }
     }
 
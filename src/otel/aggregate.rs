@@ -0,0 +1,155 @@
+use crate::{
+    otel::{
+        graph::TraceGraph,
+        id::{SpanId, TraceId},
+        span_ext::SpanExt,
+    },
+    ui::to_rich::span::format_any_value,
+};
+use std::{collections::HashMap, time::Duration};
+
+/// How many buckets `SpanStats::histogram` has.
+pub const HISTOGRAM_BUCKETS: usize = 8;
+
+/// The latencies of all the spans of a name, and of an attribute value when
+/// grouping by an attribute.
+#[derive(Clone, Debug)]
+pub struct SpanStats {
+    pub name: String,
+    /// The value of the grouping attribute, `None` for spans without it.
+    pub group: Option<String>,
+    pub count: usize,
+    pub errors: usize,
+    pub min: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    /// Span counts over `HISTOGRAM_BUCKETS` buckets splitting the range
+    /// between `min` and `max` logarithmically.
+    pub histogram: [usize; HISTOGRAM_BUCKETS],
+    pub slowest: (TraceId, SpanId),
+}
+
+impl SpanStats {
+    /// The upper bound of each bucket of the histogram.
+    pub fn bucket_bounds(&self) -> [Duration; HISTOGRAM_BUCKETS] {
+        let min = (self.min.as_nanos() as f64).max(1.0).ln();
+        let range = (self.max.as_nanos() as f64).max(1.0).ln() - min;
+        std::array::from_fn(|i| {
+            let bound = min + range * (i + 1) as f64 / HISTOGRAM_BUCKETS as f64;
+            Duration::from_nanos(bound.exp().round() as u64)
+        })
+    }
+}
+
+/// Stats of the same name and group are equal, so that the selection of the
+/// list survives them being recomputed.
+impl PartialEq for SpanStats {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.group == other.group
+    }
+}
+
+/// Groups the spans of the graph by name, and by the value of the
+/// `group_by` attribute if set.
+pub fn aggregate(graph: &TraceGraph, group_by: Option<&str>) -> Vec<SpanStats> {
+    struct Group {
+        durations: Vec<Duration>,
+        errors: usize,
+        slowest: (Duration, TraceId, SpanId),
+    }
+
+    let mut groups: HashMap<(&str, Option<String>), Group> = HashMap::new();
+    for span in graph.spans.values() {
+        let group = group_by.and_then(|key| {
+            span.attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| format_any_value(&attr.value))
+        });
+        let duration = span.duration();
+        let entry = groups
+            .entry((span.name.as_str(), group))
+            .or_insert_with(|| Group {
+                durations: Vec::new(),
+                errors: 0,
+                slowest: (duration, span.trace_id(), span.span_id()),
+            });
+        entry.durations.push(duration);
+        if span.is_error() {
+            entry.errors += 1;
+        }
+        if duration > entry.slowest.0 {
+            entry.slowest = (duration, span.trace_id(), span.span_id());
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((name, group), mut g)| {
+            g.durations.sort_unstable();
+            let (_, trace_id, span_id) = g.slowest;
+            SpanStats {
+                name: name.to_string(),
+                group,
+                count: g.durations.len(),
+                errors: g.errors,
+                min: g.durations[0],
+                p50: percentile(&g.durations, 50),
+                p90: percentile(&g.durations, 90),
+                p99: percentile(&g.durations, 99),
+                max: g.durations[g.durations.len() - 1],
+                histogram: histogram(&g.durations),
+                slowest: (trace_id, span_id),
+            }
+        })
+        .collect()
+}
+
+/// The nearest-rank percentile of sorted, non empty durations.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn histogram(sorted: &[Duration]) -> [usize; HISTOGRAM_BUCKETS] {
+    let mut buckets = [0; HISTOGRAM_BUCKETS];
+    // In log space, so that a long tail doesn't squash everything in the
+    // first bucket
+    let log = |d: &Duration| (d.as_nanos() as f64).max(1.0).ln();
+    let min = log(&sorted[0]);
+    let range = log(&sorted[sorted.len() - 1]) - min;
+    for duration in sorted {
+        let bucket = if range > 0.0 {
+            ((log(duration) - min) / range * HISTOGRAM_BUCKETS as f64) as usize
+        } else {
+            0
+        };
+        buckets[bucket.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_percentile() {
+        let durations: Vec<_> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&durations, 50), Duration::from_millis(50));
+        assert_eq!(percentile(&durations, 99), Duration::from_millis(99));
+        assert_eq!(
+            percentile(&[Duration::from_millis(7)], 90),
+            Duration::from_millis(7)
+        );
+    }
+
+    #[test]
+    fn test_histogram() {
+        let durations = [1, 1, 10, 100, 1000].map(Duration::from_micros);
+        assert_eq!(histogram(&durations), [2, 0, 1, 0, 0, 1, 0, 1]);
+    }
+}
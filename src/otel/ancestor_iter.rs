@@ -1,8 +1,8 @@
 use crate::otel::graph::TraceGraph;
 use crate::otel::id::SpanId;
 use crate::otel::span_ext::SpanExt;
+use imbl::HashMap;
 use opentelemetry_proto::tonic::trace::v1::Span;
-use std::collections::HashMap;
 use std::sync::Arc;
 
 /// An iterator that walks *up* the span's ancestor trace starting with its
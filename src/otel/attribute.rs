@@ -0,0 +1,112 @@
+use crate::states::Action;
+use amaru_kernel::Hash;
+use std::str::FromStr;
+
+/// An attribute amaru sets on its spans that can be followed to the Chain or
+/// Ledger page, or to the traces of the same peer, recognized by its full key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KnownAttribute {
+    BlockHash(Hash<32>),
+    Slot(u64),
+    /// The hash of the block a point is at, the origin not being followable.
+    Point(Hash<32>),
+    Peer,
+    TxId(Hash<32>),
+}
+
+impl KnownAttribute {
+    pub fn parse(key: &str, value: &str) -> Option<Self> {
+        let value = value.trim();
+        match key {
+            "header.hash" | "header.parent" | "block.hash" | "point.hash" | "tip.hash"
+            | "intersection.hash" => Hash::from_str(value).ok().map(KnownAttribute::BlockHash),
+            "slot" | "header.slot" | "block.slot" | "point.slot" | "tip.slot"
+            | "intersection.slot" => value.parse().ok().map(KnownAttribute::Slot),
+            // Either `<slot>.<hash>` or `Specific(<slot>, <hash>)`
+            "point" | "tip" | "intersection" => value
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .find_map(|token| Hash::from_str(token).ok())
+                .map(KnownAttribute::Point),
+            // A filter term can't hold whitespace
+            "peer" | "peer.id" | "peer.name" | "peer.addr"
+                if !value.is_empty() && !value.contains(char::is_whitespace) =>
+            {
+                Some(KnownAttribute::Peer)
+            }
+            "tx.id" | "tx.hash" | "transaction.id" | "transaction.hash" => {
+                Hash::from_str(value).ok().map(KnownAttribute::TxId)
+            }
+            _ => None,
+        }
+    }
+
+    /// The search on another page the attribute leads to, if any. Peers are
+    /// followed on the Otel page itself, see `filter`.
+    pub fn jump(&self) -> Option<Action> {
+        match self {
+            KnownAttribute::BlockHash(hash) | KnownAttribute::Point(hash) => {
+                Some(Action::SearchChain(hash.to_string()))
+            }
+            KnownAttribute::Slot(slot) => Some(Action::SearchChain(format!("slot:{}", slot))),
+            KnownAttribute::TxId(tx_id) => Some(Action::SearchUtxos(format!("tx:{}", tx_id))),
+            KnownAttribute::Peer => None,
+        }
+    }
+
+    /// The trace filter the attribute leads to, if any.
+    pub fn filter(&self, key: &str, value: &str) -> Option<String> {
+        match self {
+            KnownAttribute::Peer => Some(format!("attr.{}={}", key, value.trim())),
+            _ => None,
+        }
+    }
+
+    /// Where following the attribute leads, for hints.
+    pub fn target(&self) -> &'static str {
+        match self {
+            KnownAttribute::BlockHash(_) | KnownAttribute::Point(_) | KnownAttribute::Slot(_) => {
+                "Chain"
+            }
+            KnownAttribute::TxId(_) => "Ledger",
+            KnownAttribute::Peer => "traces of the peer",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let hash = "ab".repeat(32);
+        assert_eq!(
+            KnownAttribute::parse("header.hash", &hash),
+            Some(KnownAttribute::BlockHash(Hash::from_str(&hash).unwrap()))
+        );
+        assert_eq!(
+            KnownAttribute::parse("point", &format!("Specific(42, {})", hash))
+                .and_then(|attribute| attribute.jump()),
+            Some(Action::SearchChain(hash.clone()))
+        );
+        assert_eq!(
+            KnownAttribute::parse("tx.id", &hash).and_then(|attribute| attribute.jump()),
+            Some(Action::SearchUtxos(format!("tx:{}", hash)))
+        );
+        assert_eq!(
+            KnownAttribute::parse("slot", "1234").and_then(|attribute| attribute.jump()),
+            Some(Action::SearchChain("slot:1234".to_string()))
+        );
+        assert_eq!(
+            KnownAttribute::parse("peer", "127.0.0.1:3000")
+                .and_then(|attribute| attribute.filter("peer", "127.0.0.1:3000")),
+            Some("attr.peer=127.0.0.1:3000".to_string())
+        );
+        assert_eq!(KnownAttribute::parse("point", "Origin"), None);
+        assert_eq!(KnownAttribute::parse("header.hash", "not a hash"), None);
+        // Only the keys amaru sets are known, whatever their last segment
+        assert_eq!(KnownAttribute::parse("body.hash", &hash), None);
+        assert_eq!(KnownAttribute::parse("tx_id", &hash), None);
+    }
+}
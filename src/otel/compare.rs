@@ -0,0 +1,257 @@
+use crate::{
+    otel::{
+        graph::TraceGraph,
+        id::{SpanId, TraceId},
+        span_ext::SpanExt,
+    },
+    ui::to_rich::span::format_any_value,
+};
+use opentelemetry_proto::tonic::trace::v1::Span;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+/// A span of either compared trace, aligned with the span at the same place
+/// in the tree of the other trace: the child of the aligned parent with the
+/// same name, the nth one when there are several.
+#[derive(Clone, Debug)]
+pub struct AlignedSpan {
+    pub depth: usize,
+    pub name: String,
+    /// `None` for a span only in the other trace.
+    pub baseline: Option<Arc<Span>>,
+    /// `None` for a span missing from the other trace.
+    pub other: Option<Arc<Span>>,
+}
+
+/// An attribute set to different values, or on one side only.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeChange {
+    pub key: String,
+    pub baseline: Option<String>,
+    pub other: Option<String>,
+}
+
+/// How much the duration of a span changed in the other trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DurationDelta {
+    /// As long or longer, by that much.
+    Longer(Duration),
+    /// Shorter, by that much.
+    Shorter(Duration),
+}
+
+impl AlignedSpan {
+    /// How much the duration of the span changed in the other trace, when in
+    /// both.
+    pub fn delta(&self) -> Option<DurationDelta> {
+        let baseline = self.baseline.as_ref()?.duration();
+        let other = self.other.as_ref()?.duration();
+        Some(if other >= baseline {
+            DurationDelta::Longer(other - baseline)
+        } else {
+            DurationDelta::Shorter(baseline - other)
+        })
+    }
+
+    /// The attributes differing between both sides, by key.
+    pub fn attribute_changes(&self) -> Vec<AttributeChange> {
+        let attributes = |span: &Option<Arc<Span>>| -> HashMap<String, String> {
+            span.iter()
+                .flat_map(|span| &span.attributes)
+                .map(|attr| (attr.key.clone(), format_any_value(&attr.value)))
+                .collect()
+        };
+        let baseline = attributes(&self.baseline);
+        let mut other = attributes(&self.other);
+
+        let mut changes = BTreeMap::new();
+        for (key, value) in baseline {
+            let other_value = other.remove(&key);
+            if other_value.as_ref() != Some(&value) {
+                changes.insert(key, (Some(value), other_value));
+            }
+        }
+        for (key, value) in other {
+            changes.insert(key, (None, Some(value)));
+        }
+        changes
+            .into_iter()
+            .map(|(key, (baseline, other))| AttributeChange {
+                key,
+                baseline,
+                other,
+            })
+            .collect()
+    }
+}
+
+/// Aligns the spans of two traces, depth first in start time order, the
+/// spans of the other trace without a counterpart coming after their aligned
+/// siblings.
+pub fn align(graph: &TraceGraph, baseline: &TraceId, other: &TraceId) -> Vec<AlignedSpan> {
+    let roots = |trace_id: &TraceId| -> Vec<SpanId> {
+        graph
+            .traces
+            .get(trace_id)
+            .map(|meta| meta.roots().values().flatten().copied().collect())
+            .unwrap_or_default()
+    };
+    let mut aligned = Vec::new();
+    align_siblings(graph, roots(baseline), roots(other), 0, &mut aligned);
+    aligned
+}
+
+fn align_siblings(
+    graph: &TraceGraph,
+    baseline: Vec<SpanId>,
+    other: Vec<SpanId>,
+    depth: usize,
+    aligned: &mut Vec<AlignedSpan>,
+) {
+    let mut other = keyed(graph, other)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    for (key, span) in keyed(graph, baseline) {
+        let counterpart = other
+            .iter_mut()
+            .find(|entry| entry.as_ref().is_some_and(|(k, _)| *k == key))
+            .and_then(Option::take)
+            .map(|(_, span)| span);
+        align_span(graph, key.0, Some(span), counterpart, depth, aligned);
+    }
+    for ((name, _), span) in other.into_iter().flatten() {
+        align_span(graph, name, None, Some(span), depth, aligned);
+    }
+}
+
+fn align_span(
+    graph: &TraceGraph,
+    name: String,
+    baseline: Option<Arc<Span>>,
+    other: Option<Arc<Span>>,
+    depth: usize,
+    aligned: &mut Vec<AlignedSpan>,
+) {
+    let children = |span: &Option<Arc<Span>>| -> Vec<SpanId> {
+        span.as_ref()
+            .and_then(|span| graph.subtrees.get(&span.span_id()))
+            .map(|subtree| subtree.children().values().flatten().copied().collect())
+            .unwrap_or_default()
+    };
+    let (baseline_children, other_children) = (children(&baseline), children(&other));
+    aligned.push(AlignedSpan {
+        depth,
+        name,
+        baseline,
+        other,
+    });
+    align_siblings(graph, baseline_children, other_children, depth + 1, aligned);
+}
+
+/// Keys sibling spans by name and rank among the siblings of the same name.
+fn keyed(graph: &TraceGraph, siblings: Vec<SpanId>) -> Vec<((String, usize), Arc<Span>)> {
+    let mut ranks: HashMap<String, usize> = HashMap::new();
+    siblings
+        .into_iter()
+        .filter_map(|span_id| graph.spans.get(&span_id).cloned())
+        .map(|span| {
+            let rank = ranks.entry(span.name.clone()).or_default();
+            let key = (span.name.clone(), *rank);
+            *rank += 1;
+            (key, span)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::{id::Id, test_span::span};
+    use opentelemetry_proto::tonic::common::v1::any_value::Value;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_align() {
+        let mut graph = TraceGraph::default();
+        graph.insert_root_span(span(1).name("validate").end_ms(100).build());
+        graph.insert_child_span(span(2).parent(1).name("apply").end_ms(10).build());
+        graph.insert_child_span(span(3).parent(1).name("apply").end_ms(20).build());
+        graph.insert_root_span(span(11).trace(2).name("validate").end_ms(50).build());
+        graph.insert_child_span(
+            span(12)
+                .trace(2)
+                .parent(11)
+                .name("apply")
+                .end_ms(30)
+                .build(),
+        );
+        graph.insert_child_span(
+            span(13)
+                .trace(2)
+                .parent(11)
+                .name("store")
+                .end_ms(40)
+                .build(),
+        );
+
+        let aligned = align(&graph, &Id([1; 16]), &Id([2; 16]));
+        let rows: Vec<_> = aligned
+            .iter()
+            .map(|row| {
+                (
+                    row.depth,
+                    row.name.as_str(),
+                    row.baseline.is_some(),
+                    row.other.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (0, "validate", true, true),
+                (1, "apply", true, true),
+                (1, "apply", true, false),
+                (1, "store", false, true),
+            ]
+        );
+        assert_eq!(
+            aligned[0].delta(),
+            Some(DurationDelta::Shorter(Duration::from_millis(50)))
+        );
+    }
+
+    #[test]
+    fn test_attribute_changes() {
+        let string = |value: &str| Value::StringValue(value.to_string());
+        let baseline = span(1)
+            .name("validate")
+            .attribute("slot", string("1"))
+            .attribute("peer", string("a"))
+            .build();
+        let other = span(2)
+            .trace(2)
+            .name("validate")
+            .attribute("slot", string("2"))
+            .attribute("peer", string("a"))
+            .build();
+        let aligned = AlignedSpan {
+            depth: 0,
+            name: "validate".to_string(),
+            baseline: Some(Arc::new(baseline)),
+            other: Some(Arc::new(other)),
+        };
+        assert_eq!(
+            aligned.attribute_changes(),
+            vec![AttributeChange {
+                key: "slot".to_string(),
+                baseline: Some("1".to_string()),
+                other: Some("2".to_string()),
+            }]
+        );
+    }
+}
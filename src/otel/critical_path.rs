@@ -0,0 +1,119 @@
+use crate::otel::{
+    graph::TraceGraph,
+    id::{SpanId, TraceId},
+    span_ext::SpanExt,
+};
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    time::{Duration, SystemTime},
+};
+
+/// The spans that determine the end-to-end latency of a trace: starting from
+/// the root that ends last, the child that ends last before its parent does,
+/// then the child that ends last before that one started, and so on. Children
+/// running concurrently with one on the path are left out.
+pub fn critical_path(graph: &TraceGraph, trace_id: &TraceId) -> HashSet<SpanId> {
+    let mut path = HashSet::new();
+    let last_root = graph.traces.get(trace_id).and_then(|meta| {
+        meta.roots()
+            .values()
+            .flatten()
+            .filter_map(|root_id| graph.spans.get(root_id))
+            .max_by_key(|root| root.end_time())
+    });
+    if let Some(root) = last_root {
+        walk(graph, root.span_id(), root.end_time(), &mut path);
+    }
+    path
+}
+
+fn walk(graph: &TraceGraph, span_id: SpanId, end: SystemTime, path: &mut HashSet<SpanId>) {
+    path.insert(span_id);
+    let Some(subtree) = graph.subtrees.get(&span_id) else {
+        return;
+    };
+
+    // Children outliving their parent only count until it ended
+    let mut children: Vec<_> = subtree
+        .children()
+        .values()
+        .flatten()
+        .filter_map(|child_id| graph.spans.get(child_id))
+        .map(|child| {
+            (
+                child.span_id(),
+                child.start_time(),
+                child.end_time().min(end),
+            )
+        })
+        .collect();
+    children.sort_unstable_by_key(|(_, _, end)| Reverse(*end));
+
+    let mut cursor = end;
+    for (child_id, start, end) in children {
+        if end <= cursor && start < cursor {
+            walk(graph, child_id, end, path);
+            cursor = start;
+        }
+    }
+}
+
+/// The time a span spent outside of its children.
+pub fn self_time(graph: &TraceGraph, span_id: &SpanId) -> Option<Duration> {
+    let span = graph.spans.get(span_id)?;
+    let (start, end) = (span.start_time(), span.end_time());
+    let mut busy = Duration::ZERO;
+    let mut covered_until = start;
+    // Children are sorted by start time, so overlapping ones are merged as
+    // they come
+    for child in graph
+        .subtrees
+        .get(span_id)?
+        .children()
+        .values()
+        .flatten()
+        .filter_map(|child_id| graph.spans.get(child_id))
+    {
+        let child_start = child.start_time().max(covered_until);
+        let child_end = child.end_time().min(end);
+        if child_end > child_start {
+            busy += child_end.duration_since(child_start).unwrap_or_default();
+            covered_until = child_end;
+        }
+    }
+    Some(span.duration().saturating_sub(busy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::{id::Id, test_span::span};
+    use pretty_assertions::assert_eq;
+
+    const TRACE: [u8; 16] = [1; 16];
+
+    #[test]
+    fn test_critical_path() {
+        let mut graph = TraceGraph::default();
+        graph.insert_root_span(span(1).end_ms(100).build());
+        graph.insert_child_span(span(2).parent(1).end_ms(60).build());
+        graph.insert_child_span(span(3).parent(1).start_ms(10).end_ms(90).build());
+        graph.insert_child_span(span(4).parent(3).start_ms(20).end_ms(80).build());
+
+        // 2 runs concurrently with 3, which ends later
+        assert_eq!(
+            critical_path(&graph, &Id(TRACE)),
+            HashSet::from([Id([1; 8]), Id([3; 8]), Id([4; 8])])
+        );
+        // 1 is only busy with its children between 0 and 90
+        assert_eq!(
+            self_time(&graph, &Id([1; 8])),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(
+            self_time(&graph, &Id([3; 8])),
+            Some(Duration::from_millis(20))
+        );
+    }
+}
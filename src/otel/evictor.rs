@@ -67,6 +67,22 @@ impl Evictor {
         evicted_ids
     }
 
+    /// Removes the oldest traces from the graph until it holds at most
+    /// `max_spans` spans. Returns the list of evicted trace IDs.
+    pub fn evict_oldest(&mut self, graph: &mut TraceGraph, max_spans: usize) -> Vec<TraceId> {
+        let mut evicted_ids = Vec::new();
+        while graph.spans.len() > max_spans {
+            let Some((_, trace_ids)) = self.start_to_trace.pop_first() else {
+                break;
+            };
+            for trace_id in trace_ids {
+                graph.remove_trace(&trace_id);
+                evicted_ids.push(trace_id);
+            }
+        }
+        evicted_ids
+    }
+
     /// Starts tracking a trace.
     fn track(&mut self, trace_id: TraceId, start_time: SystemTime) {
         self.start_to_trace
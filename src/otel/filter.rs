@@ -0,0 +1,292 @@
+use crate::{
+    otel::{graph::TraceGraph, summary::TraceSummary},
+    ui::to_rich::span::format_any_value,
+};
+use anyhow::{Result, anyhow, bail};
+use opentelemetry_proto::tonic::trace::v1::Span;
+use std::{iter, str::FromStr, time::Duration};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Contains,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Op {
+    /// The operators, longest first so that `>=` isn't read as `>`.
+    const ALL: [(&'static str, Op); 7] = [
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        ("!=", Op::Ne),
+        ("=", Op::Eq),
+        ("~", Op::Contains),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+    ];
+
+    fn compare<T: PartialOrd>(self, actual: &T, expected: &T) -> bool {
+        match self {
+            Op::Eq | Op::Contains => actual == expected,
+            Op::Ne => actual != expected,
+            Op::Gt => actual > expected,
+            Op::Ge => actual >= expected,
+            Op::Lt => actual < expected,
+            Op::Le => actual <= expected,
+        }
+    }
+
+    /// Text terms hold for a trace when any of its spans matches, or, for
+    /// `!=`, when none is equal.
+    fn matches_any<S: AsRef<str>>(
+        self,
+        mut actual: impl Iterator<Item = S>,
+        expected: &str,
+    ) -> bool {
+        match self {
+            Op::Ne => !actual.any(|a| a.as_ref() == expected),
+            Op::Contains => actual.any(|a| a.as_ref().contains(expected)),
+            _ => actual.any(|a| a.as_ref() == expected),
+        }
+    }
+
+    fn is_ordering(self) -> bool {
+        matches!(self, Op::Gt | Op::Ge | Op::Lt | Op::Le)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Term {
+    Name(Op, String),
+    Root(Op, String),
+    Attribute(String, Op, String),
+    Duration(Op, Duration),
+    Spans(Op, usize),
+    Error(bool),
+}
+
+/// Narrows the trace list down, from space separated terms that all have to
+/// hold:
+/// - `name=validate_block`, `name~validate` or `name!=validate_block`, on
+///   the names of the spans of a trace. A bare word is read as `name~word`.
+/// - `root=validate_block`, `root~validate` or `root!=validate_block`, on the
+///   name of the root span of a trace.
+/// - `attr.slot=1234`, `attr.peer~127.0.0.1` or `attr.slot!=1234`, on the
+///   attributes of the spans of a trace.
+/// - `duration>50ms`, with `>`, `>=`, `<`, `<=`, and `ns`, `us`, `ms`, `s` or
+///   `m` units.
+/// - `spans>100`, on the span count.
+/// - `status=error` or `status=ok`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TraceFilter {
+    terms: Vec<Term>,
+}
+
+impl TraceFilter {
+    pub fn matches(&self, graph: &TraceGraph, summary: &TraceSummary) -> bool {
+        let spans = || {
+            graph
+                .trace_iter(&summary.trace_id)
+                .filter_map(|span_id| graph.spans.get(&span_id))
+        };
+        self.terms.iter().all(|term| match term {
+            Term::Name(op, name) => op.matches_any(spans().map(|span| span.name.as_str()), name),
+            Term::Root(op, name) => op.matches_any(iter::once(&summary.root_name), name),
+            Term::Attribute(key, op, value) => {
+                op.matches_any(spans().flat_map(|span| attribute_values(span, key)), value)
+            }
+            Term::Duration(op, duration) => op.compare(&summary.duration, duration),
+            Term::Spans(op, count) => op.compare(&summary.span_count, count),
+            Term::Error(has_error) => summary.has_error == *has_error,
+        })
+    }
+}
+
+fn attribute_values<'a>(span: &'a Span, key: &'a str) -> impl Iterator<Item = String> + 'a {
+    span.attributes
+        .iter()
+        .filter(move |attr| attr.key == key)
+        .map(|attr| format_any_value(&attr.value))
+}
+
+impl FromStr for TraceFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let terms = s
+            .split_whitespace()
+            .map(parse_term)
+            .collect::<Result<_>>()?;
+        Ok(Self { terms })
+    }
+}
+
+fn parse_term(term: &str) -> Result<Term> {
+    let Some((index, symbol, op)) = Op::ALL
+        .iter()
+        .filter_map(|(symbol, op)| term.find(symbol).map(|index| (index, *symbol, *op)))
+        .min_by_key(|(index, symbol, _)| (*index, usize::MAX - symbol.len()))
+    else {
+        return Ok(Term::Name(Op::Contains, term.to_string()));
+    };
+    let field = &term[..index];
+    let value = &term[index + symbol.len()..];
+    if value.is_empty() {
+        bail!("Missing value in `{}`", term);
+    }
+
+    let text_op = || {
+        if op.is_ordering() {
+            Err(anyhow!("`{}` can't be compared with {}", field, symbol))
+        } else {
+            Ok(op)
+        }
+    };
+    let number_op = || {
+        if op == Op::Contains {
+            Err(anyhow!("`{}` can't be compared with {}", field, symbol))
+        } else {
+            Ok(op)
+        }
+    };
+    match field {
+        "name" => Ok(Term::Name(text_op()?, value.to_string())),
+        "root" => Ok(Term::Root(text_op()?, value.to_string())),
+        "duration" => Ok(Term::Duration(number_op()?, parse_duration(value)?)),
+        "spans" => Ok(Term::Spans(
+            number_op()?,
+            value
+                .parse()
+                .map_err(|_| anyhow!("Invalid span count `{}`", value))?,
+        )),
+        "status" => {
+            let has_error = match value {
+                "error" => true,
+                "ok" => false,
+                _ => bail!("Unknown status `{}`, expected error or ok", value),
+            };
+            match op {
+                Op::Eq => Ok(Term::Error(has_error)),
+                Op::Ne => Ok(Term::Error(!has_error)),
+                _ => bail!("`status` can't be compared with {}", symbol),
+            }
+        }
+        _ => match field.strip_prefix("attr.") {
+            Some(key) if !key.is_empty() => Ok(Term::Attribute(
+                key.to_string(),
+                text_op()?,
+                value.to_string(),
+            )),
+            _ => bail!("Unknown field `{}`", field),
+        },
+    }
+}
+
+fn parse_duration(value: &str) -> Result<Duration> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(|| anyhow!("Missing unit in `{}`", value))?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid duration `{}`", value))?;
+    let nanos_per_unit = match unit {
+        "ns" => 1.0,
+        "us" | "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        "m" => 60e9,
+        _ => bail!("Unknown unit `{}`", unit),
+    };
+    Ok(Duration::from_nanos(
+        (number * nanos_per_unit).round() as u64
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::{id::Id, test_span::span};
+    use opentelemetry_proto::tonic::common::v1::any_value::Value;
+    use pretty_assertions::assert_eq;
+
+    /// A trace of a `validate_block` root lasting 100ms, with a failed
+    /// `apply` child at slot 1234.
+    fn graph() -> TraceGraph {
+        let mut graph = TraceGraph::default();
+        graph.insert_root_span(span(1).name("validate_block").end_ms(100).build());
+        graph.insert_child_span(
+            span(2)
+                .parent(1)
+                .name("apply")
+                .end_ms(20)
+                .attribute("slot", Value::IntValue(1234))
+                .failed()
+                .build(),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_matches() {
+        let graph = graph();
+        let summary = TraceSummary::new(&graph, Id([1; 16])).unwrap();
+        let matches = |filter: &str| {
+            filter
+                .parse::<TraceFilter>()
+                .unwrap()
+                .matches(&graph, &summary)
+        };
+
+        assert!(matches(""));
+        assert!(matches("apply"));
+        assert!(matches("name=apply root=validate_block"));
+        assert!(!matches("name=validate"));
+        assert!(matches("name!=store"));
+        assert!(!matches("name!=apply"));
+        assert!(!matches("root=apply"));
+        assert!(matches("attr.slot=1234"));
+        assert!(!matches("attr.slot=1235"));
+        assert!(!matches("attr.peer~127"));
+        assert!(matches("duration>=100ms duration<1s"));
+        assert!(!matches("duration>100ms"));
+        assert!(matches("spans=2"));
+        assert!(!matches("spans>2"));
+        assert!(matches("status=error"));
+        assert!(!matches("status=ok"));
+        assert!(!matches("apply status=ok"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let filter: TraceFilter =
+            "validate name=validate_block root~chain duration>=50ms attr.slot=1234 status=error"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            filter.terms,
+            vec![
+                Term::Name(Op::Contains, "validate".to_string()),
+                Term::Name(Op::Eq, "validate_block".to_string()),
+                Term::Root(Op::Contains, "chain".to_string()),
+                Term::Duration(Op::Ge, Duration::from_millis(50)),
+                Term::Attribute("slot".to_string(), Op::Eq, "1234".to_string()),
+                Term::Error(true),
+            ]
+        );
+        assert_eq!("".parse::<TraceFilter>().unwrap(), TraceFilter::default());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("duration>50".parse::<TraceFilter>().is_err());
+        assert!("name>a".parse::<TraceFilter>().is_err());
+        assert!("status=maybe".parse::<TraceFilter>().is_err());
+        assert!("color=red".parse::<TraceFilter>().is_err());
+        assert!("spans=".parse::<TraceFilter>().is_err());
+    }
+}
@@ -3,8 +3,9 @@ use crate::otel::id::{SpanId, TraceId};
 use crate::otel::span_ext::SpanExt;
 use crate::otel::trace_iter::TraceIter;
 use crate::otel::{SubTree, TraceMeta};
+use imbl::HashMap;
 use opentelemetry_proto::tonic::trace::v1::Span;
-use std::collections::{HashMap, VecDeque};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::SystemTime;
 use tracing::error;
@@ -18,6 +19,11 @@ pub struct TraceInfo {
 }
 
 /// Holds the state of processed traces, nodes, and spans.
+///
+/// The maps are persistent: a clone shares their structure with the original,
+/// and an insertion or removal only copies the path to the entry. Cloning the
+/// graph to publish a snapshot is cheap, and updating it afterwards costs in
+/// proportion to the spans updated rather than to the whole graph.
 #[derive(Clone, Debug, Default)]
 pub struct TraceGraph {
     /// A 1-1 map of SpanId to Span
@@ -53,6 +59,8 @@ impl TraceGraph {
             .entry(new_root_start)
             .or_default()
             .push(new_root_id);
+        trace_meta.span_count += 1;
+        trace_meta.has_error |= span.is_error();
 
         // Insert the full span and SubTree data into the main HashMaps.
         self.subtrees
@@ -87,6 +95,16 @@ impl TraceGraph {
             error!("Unexpected: no parent {} for child {}", parent_id, span_id);
         }
 
+        // Count the span in its trace. This also replaces the TraceMeta, which
+        // tells the trace list the trace changed.
+        if let Some(trace_meta_arc) = self.traces.get_mut(&span.trace_id()) {
+            let trace_meta = Arc::make_mut(trace_meta_arc);
+            trace_meta.span_count += 1;
+            trace_meta.has_error |= span.is_error();
+        } else {
+            error!("Unexpected: no trace meta for child {}", span_id);
+        }
+
         // Add the span to subtrees
         self.subtrees
             .insert(span_id, SubTree::new(start_time, end_time));
@@ -0,0 +1,261 @@
+use crate::{
+    logs::{model::LogStore, service::ingest_logs},
+    metrics::{model::MetricUpdate, service::ingest_metrics},
+    otel::trace_service::TraceSink,
+    receiver::ReceiverHealth,
+};
+use axum::{
+    Router,
+    extract::{DefaultBodyLimit, State},
+    http::{
+        HeaderMap, StatusCode,
+        header::{CONTENT_ENCODING, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use bytes::Bytes;
+use flate2::read::GzDecoder;
+use opentelemetry_proto::tonic::collector::{
+    logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+    trace::v1::ExportTraceServiceRequest,
+};
+use prost::Message;
+use serde::de::DeserializeOwned;
+use std::io::Read;
+use tokio::sync::mpsc::Sender;
+use tracing::warn;
+
+/// Large enough for the biggest batches amaru exports.
+const MAX_BODY_SIZE: usize = 32 * 1024 * 1024;
+
+/// The state shared by the OTLP/HTTP handlers.
+#[derive(Clone)]
+pub struct HttpState {
+    pub traces: TraceSink,
+    pub metrics: Sender<MetricUpdate>,
+    pub logs: LogStore,
+    pub health: ReceiverHealth,
+}
+
+/// The OTLP/HTTP routes, accepting protobuf and JSON bodies, optionally
+/// gzip-compressed.
+pub fn router(state: HttpState) -> Router {
+    Router::new()
+        .route("/v1/traces", post(handle_traces))
+        .route("/v1/metrics", post(handle_metrics))
+        .route("/v1/logs", post(handle_logs))
+        .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
+        .with_state(state)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    Protobuf,
+    Json,
+}
+
+impl Encoding {
+    fn of(headers: &HeaderMap) -> Self {
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        if content_type.starts_with("application/json") {
+            Encoding::Json
+        } else {
+            Encoding::Protobuf
+        }
+    }
+
+    /// An empty `Export*ServiceResponse`, in the encoding of the request.
+    fn empty_response(self) -> Response {
+        match self {
+            Encoding::Protobuf => {
+                ([(CONTENT_TYPE, "application/x-protobuf")], Vec::new()).into_response()
+            }
+            Encoding::Json => ([(CONTENT_TYPE, "application/json")], "{}").into_response(),
+        }
+    }
+}
+
+/// Decodes a request body, failing with the status to reply with. The body
+/// limit applying to the compressed body, a gzipped one is inflated up to it
+/// only.
+fn decode<T: Message + Default + DeserializeOwned>(
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(T, Encoding), (StatusCode, String)> {
+    let gzipped = headers
+        .get(CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("gzip"));
+    let mut inflated = Vec::new();
+    let body = if gzipped {
+        GzDecoder::new(body)
+            .take(MAX_BODY_SIZE as u64 + 1)
+            .read_to_end(&mut inflated)
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid gzip body: {}", e)))?;
+        if inflated.len() > MAX_BODY_SIZE {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Body inflates to more than {} bytes", MAX_BODY_SIZE),
+            ));
+        }
+        &inflated[..]
+    } else {
+        body
+    };
+
+    let encoding = Encoding::of(headers);
+    let message = match encoding {
+        Encoding::Protobuf => T::decode(body).map_err(|e| e.to_string()),
+        Encoding::Json => serde_json::from_slice(body).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    Ok((message, encoding))
+}
+
+async fn handle_traces(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let (req, encoding) = match decode::<ExportTraceServiceRequest>(&headers, &body) {
+        Ok(decoded) => decoded,
+        Err((status, e)) => {
+            warn!("Ignored traces: {}", e);
+            return (status, e).into_response();
+        }
+    };
+    state.health.batch_received();
+    if state.traces.export(req).await.is_err() {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+    encoding.empty_response()
+}
+
+async fn handle_metrics(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let (req, encoding) = match decode::<ExportMetricsServiceRequest>(&headers, &body) {
+        Ok(decoded) => decoded,
+        Err((status, e)) => {
+            warn!("Ignored metrics: {}", e);
+            return (status, e).into_response();
+        }
+    };
+    state.health.batch_received();
+    ingest_metrics(&state.metrics, req).await;
+    encoding.empty_response()
+}
+
+async fn handle_logs(State(state): State<HttpState>, headers: HeaderMap, body: Bytes) -> Response {
+    let (req, encoding) = match decode::<ExportLogsServiceRequest>(&headers, &body) {
+        Ok(decoded) => decoded,
+        Err((status, e)) => {
+            warn!("Ignored logs: {}", e);
+            return (status, e).into_response();
+        }
+    };
+    state.health.batch_received();
+    ingest_logs(&state.logs, req);
+    encoding.empty_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use flate2::{Compression, write::GzEncoder};
+    use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans, Span};
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    #[test]
+    fn test_decode_gzipped_protobuf() {
+        let req = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        name: "a".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&req.encode_to_vec()).unwrap();
+        let body = encoder.finish().unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-protobuf"),
+        );
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        assert_eq!(
+            decode::<ExportTraceServiceRequest>(&headers, &body),
+            Ok((req, Encoding::Protobuf))
+        );
+
+        // Not gzipped after all
+        headers.remove(CONTENT_ENCODING);
+        assert!(decode::<ExportTraceServiceRequest>(&headers, &body).is_err());
+    }
+
+    #[test]
+    fn test_decode_gzip_bomb() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&vec![0; MAX_BODY_SIZE + 1]).unwrap();
+        let body = encoder.finish().unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        assert_eq!(
+            decode::<ExportTraceServiceRequest>(&headers, &body).map_err(|(status, _)| status),
+            Err(StatusCode::PAYLOAD_TOO_LARGE)
+        );
+    }
+
+    #[test]
+    fn test_decode_json() {
+        let body = r#"{
+            "resourceSpans": [{
+                "scopeSpans": [{
+                    "spans": [{
+                        "traceId": "5b8efff798038103d269b633813fc60c",
+                        "spanId": "eee19b7ec3c1b174",
+                        "parentSpanId": "eee19b7ec3c1b173",
+                        "name": "validate_block",
+                        "kind": 1,
+                        "startTimeUnixNano": "1544712660000000000",
+                        "endTimeUnixNano": "1544712661000000000"
+                    }]
+                }]
+            }]
+        }"#;
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let (req, encoding) =
+            decode::<ExportTraceServiceRequest>(&headers, body.as_bytes()).unwrap();
+        assert_eq!(encoding, Encoding::Json);
+        assert_eq!(
+            req.resource_spans[0].scope_spans[0].spans,
+            vec![Span {
+                trace_id: hex::decode("5b8efff798038103d269b633813fc60c").unwrap(),
+                span_id: hex::decode("eee19b7ec3c1b174").unwrap(),
+                parent_span_id: hex::decode("eee19b7ec3c1b173").unwrap(),
+                name: "validate_block".to_string(),
+                kind: 1,
+                start_time_unix_nano: 1_544_712_660_000_000_000,
+                end_time_unix_nano: 1_544_712_661_000_000_000,
+                ..Default::default()
+            }]
+        );
+    }
+}
@@ -1,6 +1,7 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
 use std::fmt;
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Id<const N: usize>(pub [u8; N]);
 
 pub type SpanId = Id<8>;
@@ -34,3 +35,24 @@ impl<const N: usize> TryFrom<Vec<u8>> for Id<N> {
         })
     }
 }
+
+/// Ids are (de)serialized as hex, as they are displayed.
+impl<const N: usize> Serialize for Id<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Id<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex = String::deserialize(deserializer)?;
+        let bytes = hex::decode(&hex).map_err(D::Error::custom)?;
+        Id::try_from(bytes).map_err(D::Error::custom)
+    }
+}
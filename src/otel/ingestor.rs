@@ -1,36 +1,52 @@
 use crate::otel::graph::TraceGraph;
 use crate::otel::id::TraceId;
+use crate::otel::orphanage::Orphanage;
 use crate::otel::processor::TraceProcessor;
+use crate::otel::rate::IngestCounters;
 use crate::otel::span_ext::SpanExt;
+use crate::otel::{OrphanSnapshot, TraceRetention};
 use arc_swap::ArcSwap;
 use opentelemetry_proto::tonic::trace::v1::Span;
 use std::collections::HashSet;
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::SendError;
 use tracing::debug;
 
 /// The TraceIngestor holds
 /// 1. the queue to which batch Vecs of spans are sent and
-/// 2. a retrievable snapshot of the TraceGraph for rendering.
+/// 2. a retrievable snapshot of the TraceGraph for rendering, and one of the
+///    spans still waiting for their parent.
+#[derive(Clone)]
 pub struct TraceIngestor {
     batch_tx: mpsc::Sender<Vec<Span>>,
     snapshot: Arc<ArcSwap<TraceGraph>>,
+    orphans: OrphanSnapshot,
+    counters: Arc<IngestCounters>,
 }
 
 impl TraceIngestor {
-    pub fn new(queue_cap: usize, expire_duration: Duration) -> Self {
+    pub fn new(queue_cap: usize, retention: TraceRetention) -> Self {
         let (tx, rx) = mpsc::channel(queue_cap);
         let snapshot = Arc::new(ArcSwap::from_pointee(TraceGraph::default()));
+        let orphans = Arc::new(ArcSwap::from_pointee(Orphanage::default()));
+        let counters = Arc::new(IngestCounters::default());
 
         // Create and spawn the encapsulated processor.
-        let processor = TraceProcessor::new(rx, snapshot.clone(), expire_duration);
+        let processor = TraceProcessor::new(
+            rx,
+            snapshot.clone(),
+            orphans.clone(),
+            counters.clone(),
+            retention,
+        );
         tokio::spawn(processor.run());
 
         Self {
             batch_tx: tx,
             snapshot,
+            orphans,
+            counters,
         }
     }
 
@@ -43,4 +59,12 @@ impl TraceIngestor {
     pub fn snapshot(&self) -> Arc<ArcSwap<TraceGraph>> {
         self.snapshot.clone()
     }
+
+    pub fn orphans(&self) -> OrphanSnapshot {
+        self.orphans.clone()
+    }
+
+    pub fn counters(&self) -> Arc<IngestCounters> {
+        self.counters.clone()
+    }
 }
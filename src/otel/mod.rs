@@ -1,24 +1,44 @@
 use crate::otel::graph::TraceGraph;
 use crate::otel::id::{RootId, SpanId};
+use crate::otel::orphanage::Orphanage;
 use arc_swap::ArcSwap;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
+pub mod aggregate;
 pub mod ancestor_iter;
+pub mod attribute;
+pub mod compare;
+pub mod critical_path;
 pub mod evictor;
+pub mod filter;
 pub mod graph;
+pub mod http;
 pub mod id;
 pub mod ingestor;
 pub mod orphanage;
 pub mod processor;
+pub mod rate;
+pub mod recording;
 pub mod service;
 pub mod span_ext;
 pub mod store;
+pub mod summary;
+#[cfg(test)]
+pub mod test_span;
 pub mod trace_iter;
 pub mod trace_service;
 
 pub type TraceGraphSnapshot = Arc<ArcSwap<TraceGraph>>;
+pub type OrphanSnapshot = Arc<ArcSwap<Orphanage>>;
+
+/// How long traces are kept, and how many spans at most.
+#[derive(Copy, Clone, Debug)]
+pub struct TraceRetention {
+    pub expire_after: Duration,
+    pub max_spans: usize,
+}
 
 /// The start and end times for a trace tree.
 #[derive(Copy, Clone, Debug)]
@@ -70,6 +90,10 @@ pub struct TraceMeta {
     /// The RootIds for the Trace, sorted by start time. We use Vec<RootId> in
     /// the unlikely case that multiple roots have the same start time.
     roots: BTreeMap<SystemTime, Vec<RootId>>,
+    /// How many spans the trace has, roots and descendants.
+    span_count: usize,
+    /// Whether any span of the trace failed.
+    has_error: bool,
 }
 
 impl TraceMeta {
@@ -77,6 +101,14 @@ impl TraceMeta {
         &self.roots
     }
 
+    pub fn span_count(&self) -> usize {
+        self.span_count
+    }
+
+    pub fn has_error(&self) -> bool {
+        self.has_error
+    }
+
     pub fn start_time(&self) -> Option<SystemTime> {
         self.roots.first_key_value().map(|(time, _)| *time)
     }
@@ -1,40 +1,294 @@
 use crate::otel::SpanId;
+use crate::otel::graph::TraceGraph;
+use crate::otel::id::TraceId;
 use crate::otel::span_ext::SpanExt;
+use imbl::{HashMap, OrdSet};
 use opentelemetry_proto::tonic::trace::v1::Span;
-use std::collections::HashMap;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::SystemTime;
+use tracing::warn;
+
+/// A span waiting for its parent, and when it was received.
+#[derive(Clone, Debug)]
+pub struct Orphan {
+    pub span: Arc<Span>,
+    pub received: SystemTime,
+}
 
 /// Manages spans that have arrived before their parent nodes.
-#[derive(Debug, Default)]
+///
+/// Like the graph, it's backed by a persistent map so that a snapshot is a
+/// cheap clone sharing its structure.
+#[derive(Clone, Debug, Default)]
 pub struct Orphanage {
     /// A 1-Many map of parent_ids to orphan Spans
-    parent_to_orphans: HashMap<SpanId, Vec<Span>>,
+    parent_to_orphans: HashMap<SpanId, Vec<Orphan>>,
+    /// The orphans by start time, and their parent, to evict the oldest first
+    by_start: OrdSet<(SystemTime, SpanId, SpanId)>,
+}
+
+/// The orphans waiting for a parent that isn't itself an orphan, i.e. one
+/// that never arrived (yet).
+#[derive(Clone, Debug)]
+pub struct OrphanGroup {
+    pub trace_id: TraceId,
+    pub missing_parent: SpanId,
+    /// The name of the earliest orphan waiting directly for the parent.
+    pub name: String,
+    /// How many spans wait for the parent, including the orphans waiting for
+    /// them.
+    pub span_count: usize,
+    /// When the first of them was received.
+    pub first_received: SystemTime,
+}
+
+/// Groups of the same missing parent are equal, so that the selection of the
+/// orphan list survives the groups being recomputed.
+impl PartialEq for OrphanGroup {
+    fn eq(&self, other: &Self) -> bool {
+        self.trace_id == other.trace_id && self.missing_parent == other.missing_parent
+    }
 }
 
 impl Orphanage {
     /// Adds a span that is waiting for its parent to arrive.
     pub fn add(&mut self, parent_id: SpanId, orphan_span: Span) {
         // debug!("Will add orphan span: {:?}", DebugSpan(&orphan_span));
+        self.by_start
+            .insert((orphan_span.start_time(), orphan_span.span_id(), parent_id));
         self.parent_to_orphans
             .entry(parent_id)
             .or_default()
-            .push(orphan_span);
+            .push(Orphan {
+                span: Arc::new(orphan_span),
+                received: SystemTime::now(),
+            });
     }
 
     /// Called when a parent is added to the graph; returns its waiting
     /// children.
     pub fn remove(&mut self, parent_id: &SpanId) -> Option<Vec<Span>> {
-        self.parent_to_orphans.remove(parent_id)
+        self.parent_to_orphans.remove(parent_id).map(|orphans| {
+            orphans
+                .into_iter()
+                .map(|orphan| {
+                    self.by_start.remove(&(
+                        orphan.span.start_time(),
+                        orphan.span.span_id(),
+                        *parent_id,
+                    ));
+                    Arc::unwrap_or_clone(orphan.span)
+                })
+                .collect()
+        })
+    }
+
+    /// The number of orphan spans.
+    pub fn len(&self) -> usize {
+        self.by_start.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_start.is_empty()
     }
 
     /// Evicts orphans that are too old to be relevant anymore.
     pub fn evict(&mut self, expire_before: SystemTime) {
         // debug!("Will evict orphans before: {:?}", expire_before);
-        self.parent_to_orphans.retain(|_, orphans| {
-            // Retain the orphans not expired
-            orphans.retain(|orphan| orphan.start_time() >= expire_before);
-            // Retain the entry if it's not empty
-            !orphans.is_empty()
+        let evicted = self.evict_oldest_while(|start| start < expire_before);
+        if evicted > 0 {
+            warn!(
+                "Evicted {} orphan spans whose parent never arrived",
+                evicted
+            );
+        }
+    }
+
+    /// Evicts the `count` oldest orphans, to keep within the spans capacity.
+    pub fn evict_oldest(&mut self, count: usize) {
+        let mut left = count;
+        let evicted = self.evict_oldest_while(|_| {
+            let evict = left > 0;
+            left = left.saturating_sub(1);
+            evict
         });
+        if evicted > 0 {
+            warn!("Evicted {} orphan spans over capacity", evicted);
+        }
+    }
+
+    /// Evicts the orphans in start time order for as long as `evict` holds
+    /// for their start time. Returns the number of evicted spans.
+    ///
+    /// Only the entries of the evicted orphans are touched, so that the
+    /// others stay shared with the previous snapshots.
+    fn evict_oldest_while(&mut self, mut evict: impl FnMut(SystemTime) -> bool) -> usize {
+        let mut evicted = 0;
+        while let Some(&(start, span_id, parent_id)) = self.by_start.get_min() {
+            if !evict(start) {
+                break;
+            }
+            self.by_start.remove(&(start, span_id, parent_id));
+            let Some(orphans) = self.parent_to_orphans.get_mut(&parent_id) else {
+                continue;
+            };
+            let before = orphans.len();
+            orphans.retain(|orphan| {
+                orphan.span.span_id() != span_id || orphan.span.start_time() != start
+            });
+            evicted += before - orphans.len();
+            if orphans.is_empty() {
+                self.parent_to_orphans.remove(&parent_id);
+            }
+        }
+        evicted
+    }
+
+    /// Whether a span is waiting for the given parent.
+    pub fn is_waiting(&self, parent_id: &SpanId, span_id: &SpanId) -> bool {
+        self.parent_to_orphans
+            .get(parent_id)
+            .is_some_and(|orphans| orphans.iter().any(|o| o.span.span_id() == *span_id))
+    }
+
+    /// Whether both are the same snapshot, i.e. nothing changed since one was
+    /// cloned from the other.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.parent_to_orphans.ptr_eq(&other.parent_to_orphans)
+            && self.by_start.ptr_eq(&other.by_start)
+    }
+
+    /// The orphans by missing parent, the ones waiting for another orphan
+    /// being counted with it.
+    pub fn groups(&self) -> Vec<OrphanGroup> {
+        let orphan_ids: HashSet<SpanId> = self
+            .parent_to_orphans
+            .values()
+            .flatten()
+            .map(|orphan| orphan.span.span_id())
+            .collect();
+
+        self.parent_to_orphans
+            .iter()
+            .filter(|(parent_id, _)| !orphan_ids.contains(parent_id))
+            .filter_map(|(parent_id, orphans)| {
+                let first = orphans
+                    .iter()
+                    .min_by_key(|orphan| orphan.span.start_time())?;
+                let subtree = self.subtree_orphans(parent_id);
+                Some(OrphanGroup {
+                    trace_id: first.span.trace_id(),
+                    missing_parent: *parent_id,
+                    name: first.span.name.clone(),
+                    span_count: subtree.len(),
+                    first_received: subtree.iter().map(|orphan| orphan.received).min()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Builds a graph of the orphans waiting for `parent_id`, the ones waiting
+    /// directly for it standing in for the roots of their trace.
+    pub fn subtree(&self, parent_id: &SpanId) -> TraceGraph {
+        let mut graph = TraceGraph::default();
+        for orphan in self.subtree_orphans(parent_id) {
+            let mut span = Span::clone(&orphan.span);
+            if span.parent_id() == Some(*parent_id) {
+                // The graph has no span for the missing parent to walk up to
+                span.parent_span_id.clear();
+                graph.insert_root_span(span);
+            } else {
+                graph.insert_child_span(span);
+            }
+        }
+        graph
+    }
+
+    /// The orphans waiting for `parent_id`, breadth first so that parents
+    /// come before their children. An orphan whose id was already seen is
+    /// left out, as repeated ids can make parents loop back to each other.
+    fn subtree_orphans(&self, parent_id: &SpanId) -> Vec<&Orphan> {
+        let mut visited = HashSet::from([*parent_id]);
+        let mut queue = VecDeque::from([*parent_id]);
+        let mut orphans = Vec::new();
+        while let Some(parent_id) = queue.pop_front() {
+            for orphan in self.parent_to_orphans.get(&parent_id).into_iter().flatten() {
+                let span_id = orphan.span.span_id();
+                if visited.insert(span_id) {
+                    queue.push_back(span_id);
+                    orphans.push(orphan);
+                }
+            }
+        }
+        orphans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::{id::Id, test_span::span};
+    use pretty_assertions::assert_eq;
+
+    /// An orphan of `parent`, which starts later as its id is higher.
+    fn orphan(id: u8, parent: u8) -> Span {
+        span(id).parent(parent).start_ms(id as u64).build()
+    }
+
+    #[test]
+    fn test_groups() {
+        let mut orphanage = Orphanage::default();
+        orphanage.add(Id([1; 8]), orphan(2, 1));
+        orphanage.add(Id([2; 8]), orphan(3, 2));
+        orphanage.add(Id([2; 8]), orphan(4, 2));
+
+        // 3 and 4 wait for 2, which is itself an orphan
+        let groups = orphanage.groups();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].missing_parent, Id([1; 8]));
+        assert_eq!(groups[0].name, "span2");
+        assert_eq!(groups[0].span_count, 3);
+
+        let graph = orphanage.subtree(&Id([1; 8]));
+        assert_eq!(graph.trace_iter(&Id([1; 16])).count(), 3);
+    }
+
+    #[test]
+    fn test_groups_with_cycle() {
+        let mut orphanage = Orphanage::default();
+        orphanage.add(Id([1; 8]), orphan(2, 1));
+        orphanage.add(Id([2; 8]), orphan(3, 2));
+        // A second span 2, waiting for 3
+        orphanage.add(Id([3; 8]), orphan(2, 3));
+
+        let groups = orphanage.groups();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].span_count, 2);
+
+        let graph = orphanage.subtree(&Id([1; 8]));
+        assert_eq!(graph.trace_iter(&Id([1; 16])).count(), 2);
+    }
+
+    #[test]
+    fn test_evict_oldest() {
+        let mut orphanage = Orphanage::default();
+        orphanage.add(Id([1; 8]), orphan(4, 1));
+        orphanage.add(Id([1; 8]), orphan(2, 1));
+        orphanage.add(Id([5; 8]), orphan(3, 5));
+        assert_eq!(orphanage.len(), 3);
+
+        orphanage.evict_oldest(2);
+        assert_eq!(orphanage.len(), 1);
+        assert!(orphanage.is_waiting(&Id([1; 8]), &Id([4; 8])));
+        assert_eq!(orphanage.groups().len(), 1);
+
+        orphanage.evict_oldest(0);
+        assert_eq!(orphanage.len(), 1);
+        assert_eq!(
+            orphanage.remove(&Id([1; 8])).map(|spans| spans.len()),
+            Some(1)
+        );
+        assert!(orphanage.is_empty());
     }
 }
@@ -1,9 +1,11 @@
 use crate::otel::graph::TraceGraph;
+use crate::otel::rate::IngestCounters;
+use crate::otel::span_ext::SpanExt;
 use crate::otel::store::TraceStore;
+use crate::otel::{OrphanSnapshot, TraceRetention};
 use arc_swap::ArcSwap;
 use opentelemetry_proto::tonic::trace::v1::Span;
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// The background worker that processes span batches on its queue and updates
@@ -12,40 +14,64 @@ pub struct TraceProcessor {
     store: TraceStore,
     batch_rx: mpsc::Receiver<Vec<Span>>,
     snapshot: Arc<ArcSwap<TraceGraph>>,
+    orphans: OrphanSnapshot,
+    counters: Arc<IngestCounters>,
 }
 
 impl TraceProcessor {
     pub fn new(
         batch_rx: mpsc::Receiver<Vec<Span>>,
         snapshot: Arc<ArcSwap<TraceGraph>>,
-        expire_duration: Duration,
+        orphans: OrphanSnapshot,
+        counters: Arc<IngestCounters>,
+        retention: TraceRetention,
     ) -> Self {
         Self {
-            store: TraceStore::new(expire_duration),
+            store: TraceStore::new(retention),
             batch_rx,
             snapshot,
+            orphans,
+            counters,
         }
     }
 
+    /// Adds a batch to the store, counting its spans and its roots, one per
+    /// trace whichever order its spans arrive in.
+    fn add_spans(&mut self, spans: Vec<Span>) {
+        let roots = spans
+            .iter()
+            .filter(|span| span.parent_id().is_none())
+            .count();
+        self.counters.add(roots as u64, spans.len() as u64);
+        self.store.add_spans(spans);
+    }
+
     pub async fn run(mut self) {
         // Wait for incoming messages.
         while let Some(spans) = self.batch_rx.recv().await {
             // Process the first batch received.
-            self.store.add_spans(spans);
+            self.add_spans(spans);
 
             // Drain any other pending messages in the channel.
             while let Ok(more_spans) = self.batch_rx.try_recv() {
-                self.store.add_spans(more_spans);
+                self.add_spans(more_spans);
             }
 
-            // Evict expired spans and update the snapshot.
+            // Evict expired and excess spans and update the snapshot.
             self.store.evict_expired();
+            self.store.evict_over_capacity();
             let new_snapshot = self.snapshot();
             self.snapshot.store(Arc::new(new_snapshot));
+            // The orphans rarely change once the parents arrive in order, so
+            // only publish them when they did
+            if !self.orphans.load().ptr_eq(self.store.orphanage()) {
+                self.orphans.store(Arc::new(self.store.orphanage().clone()));
+            }
         }
     }
 
-    /// Constructs a new snapshot by performing a clone of trace graph state.
+    /// Constructs a new snapshot by cloning the trace graph, which shares its
+    /// structure with the graph of the store.
     fn snapshot(&self) -> TraceGraph {
         self.store.graph().clone()
     }
@@ -0,0 +1,93 @@
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// How many traces and spans the trace processor ingested so far, shared with
+/// the UI.
+#[derive(Debug, Default)]
+pub struct IngestCounters {
+    traces: AtomicU64,
+    spans: AtomicU64,
+}
+
+impl IngestCounters {
+    pub fn add(&self, traces: u64, spans: u64) {
+        self.traces.fetch_add(traces, Ordering::Relaxed);
+        self.spans.fetch_add(spans, Ordering::Relaxed);
+    }
+
+    /// The traces and spans ingested so far.
+    pub fn totals(&self) -> (u64, u64) {
+        (
+            self.traces.load(Ordering::Relaxed),
+            self.spans.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Traces and spans per second, over the samples of the counters taken
+/// within a sliding window.
+#[derive(Debug)]
+pub struct IngestRate {
+    window: Duration,
+    samples: VecDeque<(Instant, (u64, u64))>,
+}
+
+impl IngestRate {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub fn sample(&mut self, at: Instant, totals: (u64, u64)) {
+        self.samples.push_back((at, totals));
+        // Keep the last sample out of the window, so that it is covered whole
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|(time, _)| at.duration_since(*time) >= self.window)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// The traces and spans per second, once two samples were taken.
+    pub fn per_second(&self) -> Option<(f64, f64)> {
+        let (first_at, (first_traces, first_spans)) = self.samples.front()?;
+        let (last_at, (last_traces, last_spans)) = self.samples.back()?;
+        let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+        if elapsed == 0.0 {
+            return None;
+        }
+        Some((
+            (last_traces - first_traces) as f64 / elapsed,
+            (last_spans - first_spans) as f64 / elapsed,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_per_second() {
+        let start = Instant::now();
+        let mut rate = IngestRate::new(Duration::from_secs(2));
+        rate.sample(start, (0, 0));
+        assert_eq!(rate.per_second(), None);
+
+        rate.sample(start + Duration::from_secs(1), (10, 100));
+        assert_eq!(rate.per_second(), Some((10.0, 100.0)));
+
+        // The first sample leaves the window
+        rate.sample(start + Duration::from_secs(3), (12, 300));
+        rate.sample(start + Duration::from_secs(4), (12, 300));
+        assert_eq!(rate.per_second(), Some((2.0 / 3.0, 200.0 / 3.0)));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::config::get_data_dir;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use prost::Message;
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use tokio::{
+    sync::mpsc::{self, Receiver, Sender},
+    task,
+};
+use tracing::warn;
+
+/// The encoded requests waiting to be written at most, before recording waits.
+const RECORDING_QUEUE_CAPACITY: usize = 1_000;
+
+/// Appends the export requests received by the collector to a file, as
+/// length-delimited OTLP protobuf messages, so that they can be replayed.
+///
+/// Requests are written by a blocking task, so that the collector only waits
+/// on the disk when the queue is full.
+pub struct TraceRecorder {
+    path: PathBuf,
+    tx: Sender<Vec<u8>>,
+}
+
+impl TraceRecorder {
+    pub fn create(path: PathBuf) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = File::options().create(true).append(true).open(&path)?;
+        let (tx, rx) = mpsc::channel(RECORDING_QUEUE_CAPACITY);
+        let writer_path = path.clone();
+        task::spawn_blocking(move || {
+            if let Err(e) = write_requests(rx, BufWriter::new(file)) {
+                warn!(
+                    "Stopped recording traces to {}: {}",
+                    writer_path.display(),
+                    e
+                );
+            }
+        });
+        Ok(Self { path, tx })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Queues a request to be written.
+    pub async fn record(&self, request: &ExportTraceServiceRequest) -> Result<()> {
+        self.tx
+            .send(request.encode_length_delimited_to_vec())
+            .await
+            .map_err(|_| anyhow!("The recording stopped"))
+    }
+}
+
+/// Writes the queued requests until the recorder is dropped. The writer is
+/// flushed once the queue is drained, so that a recording is usable even if
+/// amaru-doctor doesn't exit cleanly, without flushing every request.
+fn write_requests(mut rx: Receiver<Vec<u8>>, mut writer: impl Write) -> Result<()> {
+    while let Some(request) = rx.blocking_recv() {
+        writer.write_all(&request)?;
+        while let Ok(request) = rx.try_recv() {
+            writer.write_all(&request)?;
+        }
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// A new recording file in the `traces` dir of the data dir.
+pub fn recording_path() -> PathBuf {
+    get_data_dir().join("traces").join(format!(
+        "traces-{}.otlp",
+        Utc::now().format("%Y%m%dT%H%M%S")
+    ))
+}
+
+/// Reads back all the requests of a recording.
+pub fn read_recording(path: &Path) -> Result<Vec<ExportTraceServiceRequest>> {
+    let bytes =
+        fs::read(path).map_err(|e| anyhow!("Can't read recording {}: {}", path.display(), e))?;
+    Ok(read_requests(&bytes))
+}
+
+/// Decodes requests until the end of the bytes. A recording cut short while a
+/// request was being written keeps the requests before it.
+fn read_requests(mut bytes: &[u8]) -> Vec<ExportTraceServiceRequest> {
+    let mut requests = Vec::new();
+    while !bytes.is_empty() {
+        match ExportTraceServiceRequest::decode_length_delimited(&mut bytes) {
+            Ok(request) => requests.push(request),
+            Err(e) => {
+                warn!(
+                    "Ignoring the end of the recording after {} requests: {}",
+                    requests.len(),
+                    e
+                );
+                break;
+            }
+        }
+    }
+    requests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans, Span};
+    use pretty_assertions::assert_eq;
+
+    fn request(name: &str) -> ExportTraceServiceRequest {
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        name: name.to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_write_requests() {
+        let (tx, rx) = mpsc::channel(2);
+        tx.try_send(request("a").encode_length_delimited_to_vec())
+            .unwrap();
+        tx.try_send(request("b").encode_length_delimited_to_vec())
+            .unwrap();
+        drop(tx);
+
+        let mut bytes = Vec::new();
+        write_requests(rx, &mut bytes).unwrap();
+        assert_eq!(read_requests(&bytes), vec![request("a"), request("b")]);
+    }
+
+    #[test]
+    fn test_read_requests() {
+        let mut bytes = Vec::new();
+        bytes.extend(request("a").encode_length_delimited_to_vec());
+        bytes.extend(request("b").encode_length_delimited_to_vec());
+        assert_eq!(read_requests(&bytes), vec![request("a"), request("b")]);
+
+        // A truncated last request is dropped
+        bytes.truncate(bytes.len() - 1);
+        assert_eq!(read_requests(&bytes), vec![request("a")]);
+    }
+}
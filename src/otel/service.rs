@@ -1,41 +1,200 @@
-use crate::otel::{TraceGraphSnapshot, ingestor::TraceIngestor, trace_service::AmaruTraceService};
+use crate::{
+    config::CollectorConfig,
+    logs::{model::LogStore, service::AmaruLogsService},
+    metrics::{model::MetricUpdate, service::AmaruMetricsService},
+    otel::{
+        OrphanSnapshot, TraceGraphSnapshot, TraceRetention,
+        http::{self, HttpState},
+        ingestor::TraceIngestor,
+        rate::IngestCounters,
+        recording::{TraceRecorder, read_recording},
+        trace_service::{AmaruTraceService, TraceSink},
+    },
+    receiver::{ReceiverHealth, ReceiverState},
+};
 use anyhow::Result;
-use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::TraceServiceServer;
-use std::{net::SocketAddr, time::Duration};
-use tokio::task::{self, JoinHandle};
-use tonic::transport::Server;
+use opentelemetry_proto::tonic::collector::{
+    logs::v1::logs_service_server::LogsServiceServer,
+    metrics::v1::metrics_service_server::MetricsServiceServer,
+    trace::v1::trace_service_server::TraceServiceServer,
+};
+use std::{net::SocketAddr, path::Path, sync::Arc, time::Duration};
+use tokio::{
+    sync::mpsc::{self, Receiver, Sender},
+    task,
+};
+use tonic::{
+    codec::CompressionEncoding,
+    transport::{Server, server::TcpIncoming},
+};
+use tracing::{info, warn};
 
+/// Receives traces, metrics and logs from amaru over OTLP/gRPC and OTLP/HTTP,
+/// each transport accepting all signals.
 pub struct OtelCollectorService {
-    addr: SocketAddr,
+    config: CollectorConfig,
+    recorder: Option<TraceRecorder>,
 }
 
 pub struct OtelCollectorHandle {
     pub snapshot: TraceGraphSnapshot,
-    pub task_handle: JoinHandle<Result<()>>,
+    pub orphans: OrphanSnapshot,
+    pub counters: Arc<IngestCounters>,
+    pub metrics_rx: Receiver<MetricUpdate>,
+    pub logs: LogStore,
+    /// One per transport.
+    pub health: Vec<ReceiverHealth>,
 }
 
+const GRPC_RECEIVER: &str = "OTLP/gRPC";
+const HTTP_RECEIVER: &str = "OTLP/HTTP";
+
 impl OtelCollectorService {
-    pub fn new(addr: &str) -> Self {
+    pub fn new(config: CollectorConfig) -> Self {
         Self {
-            addr: addr.parse().expect("Invalid address for OTEL service"),
+            config,
+            recorder: None,
         }
     }
 
+    /// Also appends every received trace request to a recording, see
+    /// `replay`.
+    pub fn with_recorder(mut self, recorder: TraceRecorder) -> Self {
+        info!("Recording traces to {}", recorder.path().display());
+        self.recorder = Some(recorder);
+        self
+    }
+
     pub fn start(self) -> OtelCollectorHandle {
-        let collector = TraceIngestor::new(10_000, Duration::from_secs(10 * 60));
-        let snapshot = collector.snapshot();
-        let trace_service = AmaruTraceService::new(collector);
-        let task_handle = task::spawn(async move {
-            Server::builder()
-                .add_service(TraceServiceServer::new(trace_service))
-                .serve(self.addr)
-                .await?;
-            Ok(())
-        });
+        let retention = TraceRetention {
+            expire_after: self.config.trace_retention(),
+            max_spans: self.config.max_spans,
+        };
+        let ingestor = TraceIngestor::new(self.config.trace_queue_capacity, retention);
+        let snapshot = ingestor.snapshot();
+        let orphans = ingestor.orphans();
+        let counters = ingestor.counters();
+        let traces = TraceSink::new(ingestor, self.recorder);
+        let (metrics_tx, metrics_rx) = mpsc::channel(100);
+        let logs = LogStore::new(self.config.max_logs);
+
+        let health = vec![
+            match self.config.grpc_addr() {
+                Some(addr) => start_grpc(addr, traces.clone(), metrics_tx.clone(), logs.clone()),
+                None => ReceiverHealth::new(GRPC_RECEIVER, ReceiverState::Disabled),
+            },
+            match self.config.http_addr() {
+                Some(addr) => start_http(addr, traces, metrics_tx, logs.clone()),
+                None => ReceiverHealth::new(HTTP_RECEIVER, ReceiverState::Disabled),
+            },
+        ];
 
         OtelCollectorHandle {
             snapshot,
-            task_handle,
+            orphans,
+            counters,
+            metrics_rx,
+            logs,
+            health,
         }
     }
+
+    /// Loads a recording in place of running the receivers. Recorded traces
+    /// are older than the eviction delay, so they are only evicted past
+    /// `max_spans`.
+    pub fn replay(path: &Path, max_spans: usize) -> Result<OtelCollectorHandle> {
+        let requests = read_recording(path)?;
+        info!(
+            "Replaying {} requests from {}",
+            requests.len(),
+            path.display()
+        );
+
+        let ingestor = TraceIngestor::new(
+            10_000,
+            TraceRetention {
+                expire_after: Duration::MAX,
+                max_spans,
+            },
+        );
+        let snapshot = ingestor.snapshot();
+        let orphans = ingestor.orphans();
+        let counters = ingestor.counters();
+        let traces = TraceSink::new(ingestor, None);
+        task::spawn(async move {
+            for request in requests {
+                if let Err(e) = traces.export(request).await {
+                    warn!("Can't replay spans: {}", e);
+                    break;
+                }
+            }
+        });
+
+        // Neither metrics nor logs are recorded
+        let (_, metrics_rx) = mpsc::channel(1);
+        let health = ReceiverHealth::new("Traces", ReceiverState::Replaying(path.to_path_buf()));
+        Ok(OtelCollectorHandle {
+            snapshot,
+            orphans,
+            counters,
+            metrics_rx,
+            logs: LogStore::new(0),
+            health: vec![health],
+        })
+    }
+}
+
+fn start_grpc(
+    addr: SocketAddr,
+    traces: TraceSink,
+    metrics: Sender<MetricUpdate>,
+    logs: LogStore,
+) -> ReceiverHealth {
+    let health = ReceiverHealth::new(GRPC_RECEIVER, ReceiverState::Starting);
+    let trace_service = TraceServiceServer::new(AmaruTraceService::new(traces, health.clone()))
+        .accept_compressed(CompressionEncoding::Gzip);
+    let metrics_service =
+        MetricsServiceServer::new(AmaruMetricsService::new(metrics, health.clone()))
+            .accept_compressed(CompressionEncoding::Gzip);
+    let logs_service = LogsServiceServer::new(AmaruLogsService::new(logs, health.clone()))
+        .accept_compressed(CompressionEncoding::Gzip);
+
+    let task_health = health.clone();
+    task::spawn(async move {
+        let listener = task_health.bind(addr).await?;
+        Server::builder()
+            .add_service(trace_service)
+            .add_service(metrics_service)
+            .add_service(logs_service)
+            .serve_with_incoming(TcpIncoming::from(listener))
+            .await
+            .inspect_err(|e| task_health.failed(e))?;
+        Ok::<_, anyhow::Error>(())
+    });
+    health
+}
+
+fn start_http(
+    addr: SocketAddr,
+    traces: TraceSink,
+    metrics: Sender<MetricUpdate>,
+    logs: LogStore,
+) -> ReceiverHealth {
+    let health = ReceiverHealth::new(HTTP_RECEIVER, ReceiverState::Starting);
+    let app = http::router(HttpState {
+        traces,
+        metrics,
+        logs,
+        health: health.clone(),
+    });
+
+    let task_health = health.clone();
+    task::spawn(async move {
+        let listener = task_health.bind(addr).await?;
+        axum::serve(listener, app)
+            .await
+            .inspect_err(|e| task_health.failed(e))?;
+        Ok::<_, anyhow::Error>(())
+    });
+    health
 }
@@ -13,6 +13,7 @@ pub trait SpanExt {
     fn start_time(&self) -> SystemTime;
     fn end_time(&self) -> SystemTime;
     fn duration(&self) -> Duration;
+    fn is_error(&self) -> bool;
 }
 
 pub struct DebugSpan<'a>(pub &'a dyn SpanExt);
@@ -57,4 +58,9 @@ impl SpanExt for Span {
             .duration_since(self.start_time())
             .unwrap_or_default()
     }
+
+    /// Whether the span status is `STATUS_CODE_ERROR`.
+    fn is_error(&self) -> bool {
+        self.status.as_ref().is_some_and(|status| status.code == 2)
+    }
 }
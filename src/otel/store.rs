@@ -1,8 +1,8 @@
 use crate::otel::{
-    evictor::Evictor, graph::TraceGraph, id::TraceId, orphanage::Orphanage, span_ext::SpanExt,
+    TraceRetention, evictor::Evictor, graph::TraceGraph, id::TraceId, orphanage::Orphanage,
+    span_ext::SpanExt,
 };
 use opentelemetry_proto::tonic::trace::v1::Span;
-use std::time::Duration;
 
 /// A high-level orchestrator for storing, managing, and expiring trace data.
 /// It holds a TraceGraph, an Orphanage, and an EvictionManager for this logic.
@@ -10,14 +10,16 @@ pub struct TraceStore {
     graph: TraceGraph,
     orphanage: Orphanage,
     evictor: Evictor,
+    max_spans: usize,
 }
 
 impl TraceStore {
-    pub fn new(expire_duration: Duration) -> Self {
+    pub fn new(retention: TraceRetention) -> Self {
         Self {
             graph: TraceGraph::default(),
             orphanage: Orphanage::default(),
-            evictor: Evictor::new(expire_duration),
+            evictor: Evictor::new(retention.expire_after),
+            max_spans: retention.max_spans,
         }
     }
 
@@ -26,6 +28,11 @@ impl TraceStore {
         &self.graph
     }
 
+    /// Provides read-only access to the spans still waiting for their parent.
+    pub fn orphanage(&self) -> &Orphanage {
+        &self.orphanage
+    }
+
     /// Adds a batch of spans to the store, connecting them to the trace graph
     /// or placing them in the orphanage if their parents have not yet arrived.
     pub fn add_spans(&mut self, spans: Vec<Span>) {
@@ -72,4 +79,64 @@ impl TraceStore {
     pub fn evict_expired(&mut self) -> Vec<TraceId> {
         self.evictor.evict(&mut self.graph, &mut self.orphanage)
     }
+
+    /// Evicts the oldest traces until at most `max_spans` spans are left in
+    /// the graph, then the oldest orphans until the graph and the orphanage
+    /// hold at most `max_spans` spans together. Returns the list of evicted
+    /// TraceIds.
+    pub fn evict_over_capacity(&mut self) -> Vec<TraceId> {
+        let evicted = self.evictor.evict_oldest(&mut self.graph, self.max_spans);
+        let orphans_capacity = self.max_spans.saturating_sub(self.graph.spans.len());
+        self.orphanage
+            .evict_oldest(self.orphanage.len().saturating_sub(orphans_capacity));
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::{id::Id, test_span::span};
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_evict_over_capacity() {
+        let mut store = TraceStore::new(TraceRetention {
+            expire_after: Duration::from_secs(3600),
+            max_spans: 5,
+        });
+        store.add_spans(vec![
+            // Three traces of two spans, oldest first
+            span(1).start_ms(0).end_ms(10).build(),
+            span(2).parent(1).start_ms(1).end_ms(10).build(),
+            span(3).trace(2).start_ms(100).end_ms(110).build(),
+            span(4).trace(2).parent(3).start_ms(101).end_ms(110).build(),
+            span(5).trace(3).start_ms(200).end_ms(210).build(),
+            span(6).trace(3).parent(5).start_ms(201).end_ms(210).build(),
+            // Two orphans, the first older than all but the first trace
+            span(7).trace(4).parent(99).start_ms(50).end_ms(60).build(),
+            span(8)
+                .trace(4)
+                .parent(98)
+                .start_ms(300)
+                .end_ms(310)
+                .build(),
+        ]);
+        assert_eq!(store.graph().spans.len() + store.orphanage().len(), 8);
+
+        // The oldest trace goes first, then the oldest orphan, whatever its
+        // age compared to the traces left
+        assert_eq!(store.evict_over_capacity(), vec![Id([1; 16])]);
+        let mut traces: Vec<_> = store.graph().traces.keys().copied().collect();
+        traces.sort();
+        assert_eq!(traces, vec![Id([2; 16]), Id([3; 16])]);
+        assert_eq!(store.orphanage().len(), 1);
+        assert!(store.orphanage().is_waiting(&Id([98; 8]), &Id([8; 8])));
+        assert!(store.graph().spans.len() + store.orphanage().len() <= 5);
+
+        // Within capacity, nothing more is evicted
+        assert_eq!(store.evict_over_capacity(), Vec::new());
+        assert_eq!(store.orphanage().len(), 1);
+    }
 }
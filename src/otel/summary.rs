@@ -0,0 +1,51 @@
+use crate::otel::{graph::TraceGraph, id::TraceId};
+use std::time::{Duration, SystemTime};
+
+/// What the trace list shows of a trace.
+#[derive(Clone, Debug)]
+pub struct TraceSummary {
+    pub trace_id: TraceId,
+    /// The name of the earliest root span.
+    pub root_name: String,
+    pub start: SystemTime,
+    pub duration: Duration,
+    pub span_count: usize,
+    /// Whether any span of the trace failed.
+    pub has_error: bool,
+    /// Whether the trace is marked for comparison.
+    pub marked: bool,
+}
+
+impl TraceSummary {
+    pub fn new(graph: &TraceGraph, trace_id: TraceId) -> Option<Self> {
+        let meta = graph.traces.get(&trace_id)?;
+        let start = meta.start_time()?;
+        let end = meta.end_time(graph)?;
+        let root_name = meta
+            .roots()
+            .values()
+            .flatten()
+            .next()
+            .and_then(|root_id| graph.spans.get(root_id))
+            .map(|root| root.name.clone())
+            .unwrap_or_default();
+
+        Some(Self {
+            trace_id,
+            root_name,
+            start,
+            duration: end.duration_since(start).unwrap_or_default(),
+            span_count: meta.span_count(),
+            has_error: meta.has_error(),
+            marked: false,
+        })
+    }
+}
+
+/// Summaries of the same trace are equal, so that the selection of the trace
+/// list survives the trace growing.
+impl PartialEq for TraceSummary {
+    fn eq(&self, other: &Self) -> bool {
+        self.trace_id == other.trace_id
+    }
+}
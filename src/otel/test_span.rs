@@ -0,0 +1,74 @@
+use opentelemetry_proto::tonic::{
+    common::v1::{AnyValue, KeyValue, any_value::Value},
+    trace::v1::{Span, Status, span::Event, status::StatusCode},
+};
+
+/// Starts building a span of trace `[1; 16]`, with the id `[id; 8]`, named
+/// `span<id>`, and no parent.
+pub fn span(id: u8) -> SpanBuilder {
+    SpanBuilder(Span {
+        trace_id: vec![1; 16],
+        span_id: vec![id; 8],
+        name: format!("span{}", id),
+        ..Default::default()
+    })
+}
+
+/// Builds the spans of the tests, their ids being a repeated byte and their
+/// times in milliseconds since the epoch.
+pub struct SpanBuilder(Span);
+
+impl SpanBuilder {
+    pub fn trace(mut self, trace: u8) -> Self {
+        self.0.trace_id = vec![trace; 16];
+        self
+    }
+
+    pub fn parent(mut self, parent: u8) -> Self {
+        self.0.parent_span_id = vec![parent; 8];
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.0.name = name.to_string();
+        self
+    }
+
+    pub fn start_ms(mut self, start_ms: u64) -> Self {
+        self.0.start_time_unix_nano = start_ms * 1_000_000;
+        self
+    }
+
+    pub fn end_ms(mut self, end_ms: u64) -> Self {
+        self.0.end_time_unix_nano = end_ms * 1_000_000;
+        self
+    }
+
+    pub fn attribute(mut self, key: &str, value: Value) -> Self {
+        self.0.attributes.push(KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue { value: Some(value) }),
+        });
+        self
+    }
+
+    pub fn event_ms(mut self, time_ms: u64) -> Self {
+        self.0.events.push(Event {
+            time_unix_nano: time_ms * 1_000_000,
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn failed(mut self) -> Self {
+        self.0.status = Some(Status {
+            code: StatusCode::Error as i32,
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn build(self) -> Span {
+        self.0
+    }
+}
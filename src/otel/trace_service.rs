@@ -1,42 +1,83 @@
-use crate::otel::ingestor::TraceIngestor;
-use opentelemetry_proto::tonic::collector::trace::v1::{
-    ExportTraceServiceRequest, ExportTraceServiceResponse, trace_service_server::TraceService,
+use crate::{
+    otel::{ingestor::TraceIngestor, recording::TraceRecorder},
+    receiver::ReceiverHealth,
 };
+use opentelemetry_proto::tonic::{
+    collector::trace::v1::{
+        ExportTraceServiceRequest, ExportTraceServiceResponse, trace_service_server::TraceService,
+    },
+    trace::v1::Span,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::error::SendError;
 use tonic::{Request, Response, Status};
+use tracing::warn;
 
-pub struct AmaruTraceService {
+/// Where received traces go, whatever the transport they came through.
+#[derive(Clone)]
+pub struct TraceSink {
     ingestor: TraceIngestor,
+    recorder: Option<Arc<TraceRecorder>>,
 }
 
-impl AmaruTraceService {
-    pub fn new(collector: TraceIngestor) -> Self {
+impl TraceSink {
+    pub fn new(ingestor: TraceIngestor, recorder: Option<TraceRecorder>) -> Self {
         Self {
-            ingestor: collector,
+            ingestor,
+            recorder: recorder.map(Arc::new),
         }
     }
-}
 
-/// The main entry-point for accepting amaru OTEL data.
-#[tonic::async_trait]
-impl TraceService for AmaruTraceService {
-    async fn export(
-        &self,
-        req: Request<ExportTraceServiceRequest>,
-    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+    pub async fn export(&self, req: ExportTraceServiceRequest) -> Result<(), SendError<Vec<Span>>> {
+        if let Some(recorder) = &self.recorder
+            && let Err(e) = recorder.record(&req).await
+        {
+            // Losing the recording shouldn't stop traces from being displayed
+            warn!(
+                "Can't record traces to {}: {}",
+                recorder.path().display(),
+                e
+            );
+        }
+
         // Flatten spans into a single Vec
         let mut all_spans = Vec::new();
-        for r_spans in req.into_inner().resource_spans {
+        for r_spans in req.resource_spans {
             for s_spans in r_spans.scope_spans {
                 all_spans.extend(s_spans.spans);
             }
         }
 
-        if !all_spans.is_empty() {
-            self.ingestor
-                .ingest(all_spans)
-                .await
-                .map_err(|e| Status::from_error(Box::new(e)))?;
+        if all_spans.is_empty() {
+            return Ok(());
         }
+        self.ingestor.ingest(all_spans).await
+    }
+}
+
+pub struct AmaruTraceService {
+    sink: TraceSink,
+    health: ReceiverHealth,
+}
+
+impl AmaruTraceService {
+    pub fn new(sink: TraceSink, health: ReceiverHealth) -> Self {
+        Self { sink, health }
+    }
+}
+
+/// The main entry-point for accepting amaru OTEL data over gRPC.
+#[tonic::async_trait]
+impl TraceService for AmaruTraceService {
+    async fn export(
+        &self,
+        req: Request<ExportTraceServiceRequest>,
+    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+        self.health.batch_received();
+        self.sink
+            .export(req.into_inner())
+            .await
+            .map_err(|e| Status::from_error(Box::new(e)))?;
 
         Ok(Response::new(ExportTraceServiceResponse::default()))
     }
@@ -0,0 +1,144 @@
+use std::{
+    fmt, io,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+use tokio::net::TcpListener;
+use tracing::{error, info, warn};
+
+/// Where an embedded receiver is at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReceiverState {
+    Disabled,
+    Starting,
+    /// `requested` is set when its port was taken and a free one was used
+    /// instead.
+    Listening {
+        addr: SocketAddr,
+        requested: Option<SocketAddr>,
+    },
+    Failed(String),
+    Replaying(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+pub struct ReceiverStatus {
+    pub name: &'static str,
+    pub state: ReceiverState,
+    pub last_batch: Option<SystemTime>,
+}
+
+impl ReceiverStatus {
+    pub fn since_last_batch(&self) -> Option<Duration> {
+        self.last_batch
+            .map(|at| SystemTime::now().duration_since(at).unwrap_or_default())
+    }
+}
+
+impl fmt::Display for ReceiverState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReceiverState::Disabled => write!(f, "disabled"),
+            ReceiverState::Starting => write!(f, "starting"),
+            ReceiverState::Listening {
+                addr,
+                requested: None,
+            } => write!(f, "listening on {}", addr),
+            ReceiverState::Listening {
+                addr,
+                requested: Some(requested),
+            } => write!(f, "listening on {} ({} in use)", addr, requested),
+            ReceiverState::Failed(reason) => write!(f, "failed: {}", reason),
+            ReceiverState::Replaying(path) => write!(f, "replaying {}", path.display()),
+        }
+    }
+}
+
+/// The shared status of a receiver, updated by its task and read by the page
+/// displaying it.
+#[derive(Clone, Debug)]
+pub struct ReceiverHealth(Arc<RwLock<ReceiverStatus>>);
+
+impl ReceiverHealth {
+    pub fn new(name: &'static str, state: ReceiverState) -> Self {
+        Self(Arc::new(RwLock::new(ReceiverStatus {
+            name,
+            state,
+            last_batch: None,
+        })))
+    }
+
+    pub fn status(&self) -> ReceiverStatus {
+        self.0.read().unwrap().clone()
+    }
+
+    pub fn set_state(&self, state: ReceiverState) {
+        self.0.write().unwrap().state = state;
+    }
+
+    pub fn failed(&self, reason: impl fmt::Display) {
+        let mut status = self.0.write().unwrap();
+        error!("{} receiver failed: {}", status.name, reason);
+        status.state = ReceiverState::Failed(reason.to_string());
+    }
+
+    pub fn batch_received(&self) {
+        self.0.write().unwrap().last_batch = Some(SystemTime::now());
+    }
+
+    /// Binds `addr`, falling back to a free port of the same interface when
+    /// it is already in use. The outcome is reflected in the state.
+    pub async fn bind(&self, addr: SocketAddr) -> io::Result<TcpListener> {
+        let (listener, requested) = match TcpListener::bind(addr).await {
+            Ok(listener) => (listener, None),
+            Err(e) if e.kind() == io::ErrorKind::AddrInUse => {
+                warn!("{} is in use, falling back to a free port", addr);
+                let listener = TcpListener::bind(SocketAddr::new(addr.ip(), 0))
+                    .await
+                    .inspect_err(|e| self.failed(e))?;
+                (listener, Some(addr))
+            }
+            Err(e) => {
+                self.failed(format!("can't bind {}: {}", addr, e));
+                return Err(e);
+            }
+        };
+        let local_addr = listener.local_addr()?;
+        info!(
+            "{} receiver listening on {}",
+            self.status().name,
+            local_addr
+        );
+        self.set_state(ReceiverState::Listening {
+            addr: local_addr,
+            requested,
+        });
+        Ok(listener)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn test_bind_falls_back_to_a_free_port() {
+        let taken = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let requested = taken.local_addr().unwrap();
+        let health = ReceiverHealth::new("test", ReceiverState::Starting);
+
+        let listener = health.bind(requested).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        assert_ne!(addr.port(), requested.port());
+        assert_eq!(
+            health.status().state,
+            ReceiverState::Listening {
+                addr,
+                requested: Some(requested),
+            }
+        );
+    }
+}
@@ -1,7 +1,14 @@
-use crate::ui::to_list_item::ToListItem;
+use crate::{
+    export::ExportScope,
+    logs::model::Severity,
+    otel::id::{SpanId, TraceId},
+    ui::to_list_item::ToListItem,
+};
+use amaru_kernel::Epoch;
 use crossterm::event::KeyCode;
 use ratatui::widgets::ListItem;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use strum::{Display, EnumIter};
 
 #[derive(Clone, Copy, Debug, Default, Display, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -11,6 +18,8 @@ pub enum ComponentId {
     Root,
     LedgerPage,
     OtelPage,
+    SpansPage,
+    LogsPage,
     MetricsPage,
     ChainPage,
 
@@ -22,6 +31,7 @@ pub enum ComponentId {
     LedgerModeTabs,
     LedgerBrowseOptions,
     LedgerSearchOptions,
+    LedgerSnapshotOptions,
     LedgerAccountsList,
     LedgerAccountDetails,
     LedgerBlockIssuersList,
@@ -34,19 +44,44 @@ pub enum ComponentId {
     LedgerProposalDetails,
     LedgerUtxosList,
     LedgerUtxoDetails,
+    LedgerStatsDetails,
     LedgerUtxosByAddrList,
     LedgerUtxosByAddrDetails,
+    LedgerAccountByCredentialList,
+    LedgerAccountByCredentialDetails,
+    LedgerPoolByIdList,
+    LedgerPoolByIdDetails,
+    LedgerDRepByIdList,
+    LedgerDRepByIdDetails,
+    LedgerProposalByIdList,
+    LedgerProposalByIdDetails,
+    LedgerDiffList,
+    LedgerDiffBeforeDetails,
+    LedgerDiffAfterDetails,
 
     // --- Chain Page ---
+    ChainHeadersList,
     ChainSearch,
     ChainSearchHeader,
     ChainSearchBlock,
+    ChainSearchTransactions,
     ChainSearchNonces,
 
     // --- Otel Page ---
     OtelTraceList,
     OtelFlameGraph,
     OtelSpanDetails,
+    OtelOrphanList,
+    OtelTraceCompare,
+
+    // --- Spans Page ---
+    SpansList,
+    SpansDetails,
+
+    // --- Logs Page ---
+    LogsSeverityTabs,
+    LogsList,
+    LogsDetails,
 
     // --- Metrics Page ---
     Metrics,
@@ -78,6 +113,23 @@ pub enum Action {
     SetFocus(ComponentId),
     FocusNext,
     FocusPrev,
+    ExportSelected(ExportFormat),
+    ExportList(ExportFormat),
+    /// Exports traces in the Chrome Trace Event format, on the Otel page.
+    ExportChromeTrace(ExportScope),
+    /// Shows a trace on the Otel page, focusing one of its spans if set.
+    ShowTrace(TraceId, Option<SpanId>),
+    /// Searches the Chain page, e.g. for the block hash of a span attribute.
+    SearchChain(String),
+    /// Searches UTxOs on the Ledger page, e.g. for the outputs of a
+    /// transaction.
+    SearchUtxos(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Json,
+    Cbor,
 }
 
 impl Action {
@@ -97,6 +149,7 @@ pub enum LedgerBrowse {
     Pools,
     Proposals,
     Utxos,
+    Stats,
 }
 
 impl ToListItem for LedgerBrowse {
@@ -105,12 +158,37 @@ impl ToListItem for LedgerBrowse {
     }
 }
 
+/// The ledger state the Ledger page reads from: either the live db or one of
+/// the per-epoch snapshots amaru keeps next to it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LedgerSnapshot {
+    #[default]
+    Live,
+    Epoch(Epoch),
+}
+
+impl fmt::Display for LedgerSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerSnapshot::Live => write!(f, "live"),
+            LedgerSnapshot::Epoch(epoch) => write!(f, "epoch {}", epoch),
+        }
+    }
+}
+
+impl ToListItem for LedgerSnapshot {
+    fn to_list_item(&self) -> ListItem<'static> {
+        ListItem::new(self.to_string())
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Display, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LedgerMode {
     #[default]
     Browse,
     Search,
+    Diff,
 }
 
 #[derive(Clone, Copy, Default, Debug, EnumIter, PartialEq, Eq, Serialize)]
@@ -118,6 +196,14 @@ pub enum LedgerSearch {
     #[default]
     #[serde(rename = "utxos by address")]
     UtxosByAddress,
+    #[serde(rename = "account by credential")]
+    AccountByCredential,
+    #[serde(rename = "pool by id")]
+    PoolById,
+    #[serde(rename = "drep by id")]
+    DRepById,
+    #[serde(rename = "proposal by id")]
+    ProposalById,
 }
 
 impl ToListItem for LedgerSearch {
@@ -133,5 +219,31 @@ pub enum InspectOption {
     Ledger,
     Chain,
     Otel,
+    Spans,
+    Logs,
     Metrics,
 }
+
+/// The least severe log records shown on the Logs page.
+#[derive(Clone, Copy, Debug, Default, Display, EnumIter, PartialEq, Eq)]
+#[strum(serialize_all = "lowercase")]
+pub enum SeverityFilter {
+    #[default]
+    All,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl SeverityFilter {
+    pub fn min_severity(self) -> Severity {
+        match self {
+            SeverityFilter::All => Severity::Unspecified,
+            SeverityFilter::Debug => Severity::Debug,
+            SeverityFilter::Info => Severity::Info,
+            SeverityFilter::Warn => Severity::Warn,
+            SeverityFilter::Error => Severity::Error,
+        }
+    }
+}
@@ -1 +1,3 @@
 pub mod owned_iter;
+pub mod raw_ledger;
+pub mod utxo_index;
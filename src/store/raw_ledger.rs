@@ -0,0 +1,189 @@
+use anyhow::{Result, anyhow};
+use minicbor::{Decode, Encode};
+use rocksdb::{DB, Options};
+use std::path::Path;
+
+/// The kinds of ledger entries, each stored under its own key prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedgerColumn {
+    Accounts,
+    BlockIssuers,
+    DReps,
+    Pools,
+    Proposals,
+    Utxos,
+}
+
+impl LedgerColumn {
+    /// The prefix amaru puts in front of the CBOR key of the entries.
+    fn prefix(self) -> &'static [u8] {
+        match self {
+            LedgerColumn::Accounts => b"acct:",
+            LedgerColumn::BlockIssuers => b"slot:",
+            LedgerColumn::DReps => b"drep:",
+            LedgerColumn::Pools => b"pool:",
+            LedgerColumn::Proposals => b"prop:",
+            LedgerColumn::Utxos => b"utxo:",
+        }
+    }
+}
+
+/// Read-only access to the bytes of a ledger db, to export entries as amaru
+/// stores them rather than as decoded and encoded again.
+///
+/// An entry is a CBOR key and a CBOR value, exported as a two elements array
+/// of both.
+pub struct RawLedger {
+    db: DB,
+}
+
+impl RawLedger {
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = DB::open_for_read_only(&Options::default(), path, false)
+            .map_err(|e| anyhow!("Can't open ledger db at {}: {}", path.display(), e))?;
+        Ok(Self { db })
+    }
+
+    /// The stored entry of a key.
+    pub fn get<K: Encode<()>>(&self, column: LedgerColumn, key: &K) -> Result<Vec<u8>> {
+        let key = minicbor::to_vec(key).map_err(|e| anyhow!("Can't encode key: {}", e))?;
+        let value = self
+            .value(column, &key)?
+            .ok_or_else(|| anyhow!("Entry not found"))?;
+        Ok(entry(&key, &value))
+    }
+
+    /// The decoded value of a key, for the entries the store has no point
+    /// lookup for.
+    pub fn decode<K, V>(&self, column: LedgerColumn, key: &K) -> Result<Option<V>>
+    where
+        K: Encode<()>,
+        V: for<'b> Decode<'b, ()>,
+    {
+        let key = minicbor::to_vec(key).map_err(|e| anyhow!("Can't encode key: {}", e))?;
+        self.value(column, &key)?
+            .map(|value| minicbor::decode(&value).map_err(|e| anyhow!("Can't decode value: {}", e)))
+            .transpose()
+    }
+
+    fn value(&self, column: LedgerColumn, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.db.get([column.prefix(), key].concat())?)
+    }
+
+    /// All the stored entries of a column, in key order.
+    pub fn iter(&self, column: LedgerColumn) -> impl Iterator<Item = Result<Vec<u8>>> + '_ {
+        let prefix = column.prefix();
+        self.db
+            .prefix_iterator(prefix)
+            .map(|item| item.map_err(anyhow::Error::from))
+            .take_while(move |item| match item {
+                Ok((key, _)) => key.starts_with(prefix),
+                Err(_) => true,
+            })
+            .map(move |item| item.map(|(key, value)| entry(&key[prefix.len()..], &value)))
+    }
+}
+
+/// A two elements CBOR array of the key and the value, both already CBOR.
+fn entry(key: &[u8], value: &[u8]) -> Vec<u8> {
+    let mut entry = Vec::with_capacity(1 + key.len() + value.len());
+    entry.push(0x82);
+    entry.extend_from_slice(key);
+    entry.extend_from_slice(value);
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amaru_kernel::{
+        Bytes, Hash, MemoizedTransactionOutput, Point, PostAlonzoTransactionOutput,
+        TransactionInput, TransactionOutput, Value, from_cbor, network::NetworkName,
+        protocol_parameters::PREVIEW_INITIAL_PROTOCOL_PARAMETERS, to_cbor,
+    };
+    use amaru_ledger::store::{self, GovernanceActivity, Store, TransactionalContext};
+    use amaru_stores::rocksdb::{RocksDB, RocksDbConfig};
+    use pretty_assertions::assert_eq;
+    use std::iter;
+
+    #[test]
+    fn test_entry() {
+        let key = minicbor::to_vec(42u64).unwrap();
+        let value = minicbor::to_vec("pool").unwrap();
+        assert_eq!(
+            entry(&key, &value),
+            minicbor::to_vec((42u64, "pool")).unwrap()
+        );
+    }
+
+    /// Writes a UTxO through amaru's store and reads it back by prefix, so
+    /// that the prefix layout assumed here is checked against the store's.
+    #[test]
+    fn test_prefixes_match_the_store() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("raw-ledger-{}", std::process::id()));
+        let input = TransactionInput {
+            transaction_id: Hash::from([1; 32]),
+            index: 0,
+        };
+        let output: MemoizedTransactionOutput = from_cbor(&to_cbor(
+            &TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+                address: Bytes::from(hex::decode(
+                    "61bbe56449ba4ee08c471d69978e01db384d31e29133af4546e6057335",
+                )?),
+                value: Value::Coin(42),
+                datum_option: None,
+                script_ref: None,
+            }),
+        ))
+        .unwrap();
+
+        {
+            let db = RocksDB::empty(RocksDbConfig::new(path.clone()))?;
+            let tx = db.create_transaction();
+            tx.save(
+                NetworkName::Preview.into(),
+                &PREVIEW_INITIAL_PROTOCOL_PARAMETERS,
+                &mut GovernanceActivity {
+                    consecutive_dormant_epochs: 0,
+                },
+                &Point::Origin,
+                None,
+                store::Columns {
+                    utxo: iter::once((input.clone(), output.clone())),
+                    pools: iter::empty(),
+                    accounts: iter::empty(),
+                    dreps: iter::empty(),
+                    cc_members: iter::empty(),
+                    proposals: iter::empty(),
+                    votes: iter::empty(),
+                },
+                Default::default(),
+                iter::empty(),
+            )?;
+            tx.commit()?;
+        }
+
+        let raw = RawLedger::open(&path)?;
+        let stored: Option<MemoizedTransactionOutput> = raw.decode(LedgerColumn::Utxos, &input)?;
+        assert_eq!(
+            stored.map(|output| to_cbor(&output)),
+            Some(to_cbor(&output))
+        );
+        assert_eq!(
+            raw.iter(LedgerColumn::Utxos).collect::<Result<Vec<_>>>()?,
+            vec![raw.get(LedgerColumn::Utxos, &input)?]
+        );
+        for column in [
+            LedgerColumn::Accounts,
+            LedgerColumn::DReps,
+            LedgerColumn::Pools,
+            LedgerColumn::Proposals,
+        ] {
+            assert_eq!(raw.iter(column).count(), 0, "{:?}", column);
+        }
+
+        drop(raw);
+        std::fs::remove_dir_all(&path)?;
+        Ok(())
+    }
+}
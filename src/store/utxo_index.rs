@@ -0,0 +1,351 @@
+use crate::{
+    config::get_data_dir,
+    model::ledger_search::{UtxoQuery, payment_credential, stake_credential},
+};
+use amaru_kernel::{Address, Hash, Point, StakeCredential, TransactionInput};
+use amaru_ledger::store::ReadStore;
+use amaru_stores::rocksdb::ReadOnlyRocksDB;
+use anyhow::{Result, anyhow};
+use rocksdb::{DB, Options, WriteBatch};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+use tracing::{debug, info, warn};
+
+const ADDRESS: u8 = 0;
+const PAYMENT_CREDENTIAL: u8 = 1;
+const STAKE_CREDENTIAL: u8 = 2;
+
+/// Size of an encoded `TransactionInput`: a 32 bytes id and a u64 index.
+const INPUT_LEN: usize = 40;
+
+const BATCH_SIZE: usize = 10_000;
+
+/// An on-disk secondary index from addresses, payment credentials and stake
+/// credentials to the inputs of the UTxOs they own.
+///
+/// Each entry is a key only: a kind byte, the looked up value and the
+/// encoded input, so a lookup is a prefix iteration. An index is only valid
+/// for the ledger tip it was built at, and lives in a directory named after
+/// that tip under the data dir.
+pub struct UtxoIndex {
+    db: DB,
+}
+
+impl UtxoIndex {
+    fn root_dir() -> PathBuf {
+        get_data_dir().join("utxo-index")
+    }
+
+    fn dir_for(tip: &Point) -> PathBuf {
+        let name = match tip {
+            Point::Origin => "origin".to_string(),
+            Point::Specific(slot, hash) => format!("{}-{}", slot, hex::encode(hash)),
+        };
+        Self::root_dir().join(name)
+    }
+
+    /// Opens the index built for the ledger's current tip, if there is one.
+    pub fn open_fresh(ledger: &ReadOnlyRocksDB) -> Option<Self> {
+        let tip = ReadStore::tip(ledger).ok()?;
+        let dir = Self::dir_for(&tip);
+        if !dir.exists() {
+            info!(
+                "No UTxO index for tip {:?}, UTxO searches will scan the whole set",
+                tip
+            );
+            return None;
+        }
+        match DB::open_for_read_only(&Options::default(), &dir, false) {
+            Ok(db) => Some(Self { db }),
+            Err(e) => {
+                info!("Can't open UTxO index at {}: {}", dir.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Builds the index for the ledger's current tip, replacing any index
+    /// built for an older tip. Returns the index directory.
+    pub fn build(ledger: &ReadOnlyRocksDB) -> Result<PathBuf> {
+        let tip = ReadStore::tip(ledger).map_err(|e| anyhow!("Can't read ledger tip: {:?}", e))?;
+        let dir = Self::dir_for(&tip);
+        if dir.exists() {
+            info!("UTxO index for tip {:?} already exists", tip);
+            return Ok(dir);
+        }
+
+        // Build next to the final location so that a half built index is
+        // never picked up
+        let tmp_dir = dir.with_extension("tmp");
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
+        fs::create_dir_all(&tmp_dir)?;
+
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        let db = DB::open(&options, &tmp_dir)?;
+
+        let mut batch = WriteBatch::default();
+        let mut count = 0usize;
+        let utxos = ReadStore::iter_utxos(ledger).map_err(|e| anyhow!("{:?}", e))?;
+        for (input, output) in utxos {
+            for prefix in prefixes_for(&output.address) {
+                batch.put(entry_key(prefix, &input), b"");
+            }
+            count += 1;
+            if count.is_multiple_of(BATCH_SIZE) {
+                db.write(std::mem::take(&mut batch))?;
+                debug!("Indexed {} UTxOs", count);
+            }
+        }
+        db.write(batch)?;
+        db.flush()?;
+        drop(db);
+
+        fs::rename(&tmp_dir, &dir)?;
+        info!("Indexed {} UTxOs in {}", count, dir.display());
+
+        // Indexes of older tips are never valid again
+        for entry in fs::read_dir(Self::root_dir())?.flatten() {
+            if entry.path() != dir {
+                fs::remove_dir_all(entry.path())?;
+            }
+        }
+
+        Ok(dir)
+    }
+
+    pub fn lookup(&self, query: &UtxoQuery) -> Result<Vec<TransactionInput>> {
+        let prefix = match query {
+            UtxoQuery::Address(address) => address_prefix(address),
+            UtxoQuery::PaymentCredential(credential) => {
+                credential_prefix(PAYMENT_CREDENTIAL, credential)
+            }
+            UtxoQuery::StakeCredential(credential) => {
+                credential_prefix(STAKE_CREDENTIAL, credential)
+            }
+            UtxoQuery::TransactionId(_) => {
+                return Err(anyhow!("UTxOs aren't indexed by transaction id"));
+            }
+        };
+
+        let mut inputs = Vec::new();
+        for entry in self.db.prefix_iterator(&prefix) {
+            let (key, _) = entry?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            inputs.push(decode_input(&key[prefix.len()..])?);
+        }
+        Ok(inputs)
+    }
+}
+
+/// Where the UTxO index of a ledger is at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UtxoIndexStatus {
+    /// Not looked for yet, or not built for the ledger's current tip.
+    Missing,
+    Building,
+    Ready,
+    Failed,
+}
+
+enum IndexState {
+    Unopened,
+    Missing,
+    Building,
+    Ready(Arc<UtxoIndex>),
+    Failed,
+}
+
+/// The UTxO index of a ledger db, shared by the UTxO search and the Ledger
+/// page that offers to build it. It's opened on the first search, and built
+/// in the background when asked to.
+#[derive(Clone)]
+pub struct SharedUtxoIndex {
+    ledger: Arc<ReadOnlyRocksDB>,
+    state: Arc<RwLock<IndexState>>,
+}
+
+impl SharedUtxoIndex {
+    pub fn new(ledger: Arc<ReadOnlyRocksDB>) -> Self {
+        Self {
+            ledger,
+            state: Arc::new(RwLock::new(IndexState::Unopened)),
+        }
+    }
+
+    /// The index, opening it the first time. Blocks on the db, so it's to be
+    /// called off the UI thread.
+    pub fn get(&self) -> Option<Arc<UtxoIndex>> {
+        let mut state = self.state.write().unwrap();
+        if let IndexState::Unopened = *state {
+            *state = match UtxoIndex::open_fresh(&self.ledger) {
+                Some(index) => IndexState::Ready(Arc::new(index)),
+                None => IndexState::Missing,
+            };
+        }
+        match &*state {
+            IndexState::Ready(index) => Some(index.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn status(&self) -> UtxoIndexStatus {
+        match *self.state.read().unwrap() {
+            IndexState::Unopened | IndexState::Missing => UtxoIndexStatus::Missing,
+            IndexState::Building => UtxoIndexStatus::Building,
+            IndexState::Ready(_) => UtxoIndexStatus::Ready,
+            IndexState::Failed => UtxoIndexStatus::Failed,
+        }
+    }
+
+    /// Builds the index in the background, unless it's ready or being built.
+    pub fn build(&self) {
+        {
+            let mut state = self.state.write().unwrap();
+            if let IndexState::Building | IndexState::Ready(_) = *state {
+                return;
+            }
+            *state = IndexState::Building;
+        }
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let index = UtxoIndex::build(&this.ledger).and_then(|_| {
+                UtxoIndex::open_fresh(&this.ledger)
+                    .ok_or_else(|| anyhow!("The ledger tip moved while indexing"))
+            });
+            *this.state.write().unwrap() = match index {
+                Ok(index) => IndexState::Ready(Arc::new(index)),
+                Err(e) => {
+                    warn!("Can't build the UTxO index: {}", e);
+                    IndexState::Failed
+                }
+            };
+        });
+    }
+}
+
+fn prefixes_for(address: &Address) -> Vec<Vec<u8>> {
+    let mut prefixes = vec![address_prefix(address)];
+    if let Some(credential) = payment_credential(address) {
+        prefixes.push(credential_prefix(PAYMENT_CREDENTIAL, &credential));
+    }
+    if let Some(credential) = stake_credential(address) {
+        prefixes.push(credential_prefix(STAKE_CREDENTIAL, &credential));
+    }
+    prefixes
+}
+
+/// Addresses vary in length, so theirs is part of the prefix to keep one
+/// address from matching another it is a prefix of.
+fn address_prefix(address: &Address) -> Vec<u8> {
+    let bytes = address.to_vec();
+    let mut prefix = Vec::with_capacity(bytes.len() + 3);
+    prefix.push(ADDRESS);
+    prefix.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+    prefix.extend_from_slice(&bytes);
+    prefix
+}
+
+fn credential_prefix(kind: u8, credential: &StakeCredential) -> Vec<u8> {
+    let (tag, hash) = match credential {
+        StakeCredential::AddrKeyhash(hash) => (0, hash),
+        StakeCredential::ScriptHash(hash) => (1, hash),
+    };
+    let mut prefix = Vec::with_capacity(30);
+    prefix.push(kind);
+    prefix.push(tag);
+    prefix.extend_from_slice(hash.as_ref());
+    prefix
+}
+
+fn entry_key(mut prefix: Vec<u8>, input: &TransactionInput) -> Vec<u8> {
+    prefix.extend_from_slice(input.transaction_id.as_ref());
+    prefix.extend_from_slice(&input.index.to_be_bytes());
+    prefix
+}
+
+fn decode_input(bytes: &[u8]) -> Result<TransactionInput> {
+    if bytes.len() != INPUT_LEN {
+        return Err(anyhow!("Corrupted UTxO index entry"));
+    }
+    let (id, index) = bytes.split_at(32);
+    Ok(TransactionInput {
+        transaction_id: Hash::from(<[u8; 32]>::try_from(id)?),
+        index: u64::from_be_bytes(index.try_into()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn input() -> TransactionInput {
+        TransactionInput {
+            transaction_id: Hash::new([7; 32]),
+            index: 258,
+        }
+    }
+
+    #[test]
+    fn test_entry_key_round_trip() {
+        // A base address with a payment key hash and a stake key hash
+        let mut bytes = vec![0x01];
+        bytes.extend([1; 28]);
+        bytes.extend([2; 28]);
+        let address = Address::from_bytes(&bytes).unwrap();
+
+        let prefixes = prefixes_for(&address);
+        assert_eq!(
+            prefixes,
+            vec![
+                address_prefix(&address),
+                credential_prefix(
+                    PAYMENT_CREDENTIAL,
+                    &StakeCredential::AddrKeyhash(Hash::new([1; 28]))
+                ),
+                credential_prefix(
+                    STAKE_CREDENTIAL,
+                    &StakeCredential::AddrKeyhash(Hash::new([2; 28]))
+                ),
+            ]
+        );
+        for prefix in prefixes {
+            let key = entry_key(prefix.clone(), &input());
+            assert_eq!(key.len(), prefix.len() + INPUT_LEN);
+            assert_eq!(decode_input(&key[prefix.len()..]).unwrap(), input());
+        }
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let address = Address::from_bytes(&[[0x61].as_slice(), &[1; 28]].concat()).unwrap();
+        let prefix = address_prefix(&address);
+        assert_eq!(prefix[..3], [ADDRESS, 0, 29]);
+        assert_eq!(prefix[3..], address.to_vec());
+
+        let key_hash = credential_prefix(
+            STAKE_CREDENTIAL,
+            &StakeCredential::AddrKeyhash(Hash::new([3; 28])),
+        );
+        let script = credential_prefix(
+            STAKE_CREDENTIAL,
+            &StakeCredential::ScriptHash(Hash::new([3; 28])),
+        );
+        assert_eq!(key_hash.len(), 30);
+        assert_ne!(key_hash, script);
+    }
+
+    #[test]
+    fn test_decode_input_errors() {
+        assert!(decode_input(&[0; INPUT_LEN - 1]).is_err());
+        assert!(decode_input(&[0; INPUT_LEN + 1]).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use crossterm::{
+    clipboard::CopyToClipboard,
     cursor,
     event::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
@@ -188,6 +189,13 @@ impl<B: Backend> Tui<B> {
     }
 }
 
+/// Copies `text` to the system clipboard through the terminal (OSC 52), which
+/// also works over ssh.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    crossterm::execute!(stdout(), CopyToClipboard::to_clipboard_from(text))?;
+    Ok(())
+}
+
 impl<B: Backend> Deref for Tui<B> {
     type Target = ratatui::Terminal<B>;
 
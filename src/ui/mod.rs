@@ -243,3 +243,16 @@ pub fn format_duration(duration: Duration) -> String {
         format!("{} µs", micros)
     }
 }
+
+/// A one line bar chart of counts, scaled to the highest one.
+pub fn sparkline(counts: &[usize]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = counts.iter().copied().max().unwrap_or_default();
+    counts
+        .iter()
+        .map(|&count| match count {
+            0 => ' ',
+            _ => BARS[(count * (BARS.len() - 1)).div_ceil(max)],
+        })
+        .collect()
+}
@@ -1,11 +1,25 @@
+use amaru_consensus::{BlockHeader, IsHeader};
 use amaru_ledger::store::columns::{accounts, dreps, pools, proposals, slots, utxo};
-use ratatui::widgets::ListItem;
+use chrono::{DateTime, TimeZone, Utc};
+use ratatui::{
+    style::{Color, Style},
+    widgets::ListItem,
+};
+use std::sync::Arc;
 
 use crate::{
-    otel::id::TraceId,
-    ui::to_rich::{
-        account::StakeCredentialDisplay, proposal::ComparableProposalIdDisplay,
-        utxo::TransactionInputDisplay,
+    logs::model::LogEntry,
+    model::{
+        block_body::BlockTransaction,
+        ledger_diff::{Change, DiffEntry},
+    },
+    otel::{aggregate::SpanStats, orphanage::OrphanGroup, summary::TraceSummary},
+    ui::{
+        format_duration, sparkline,
+        to_rich::{
+            account::StakeCredentialDisplay, log::severity_color,
+            proposal::ComparableProposalIdDisplay, utxo::TransactionInputDisplay,
+        },
     },
 };
 
@@ -13,6 +27,12 @@ pub trait ToListItem {
     fn to_list_item(&self) -> ListItem<'static>;
 }
 
+impl<T: ToListItem> ToListItem for Arc<T> {
+    fn to_list_item(&self) -> ListItem<'static> {
+        (**self).to_list_item()
+    }
+}
+
 pub type AccountItem = (accounts::Key, accounts::Row);
 
 impl ToListItem for AccountItem {
@@ -61,8 +81,150 @@ impl ToListItem for UtxoItem {
     }
 }
 
-impl ToListItem for TraceId {
+impl ToListItem for BlockHeader {
+    fn to_list_item(&self) -> ListItem<'static> {
+        ListItem::new(format!(
+            "{} #{} {}",
+            self.slot(),
+            self.block_height(),
+            self.hash()
+        ))
+    }
+}
+
+impl ToListItem for BlockTransaction {
+    fn to_list_item(&self) -> ListItem<'static> {
+        let invalid = if self.is_valid { "" } else { " (invalid)" };
+        ListItem::new(format!("#{} {}{}", self.index, self.id, invalid))
+    }
+}
+
+fn change_list_item<T>(change: &Change<T>, entity: &str, id: String) -> ListItem<'static> {
+    let (sign, color) = match change {
+        Change::Added(_) => ('+', Color::Green),
+        Change::Removed(_) => ('-', Color::Red),
+        Change::Changed(..) => ('~', Color::Yellow),
+    };
+    ListItem::new(format!("{} {} {}", sign, entity, id)).style(Style::default().fg(color))
+}
+
+impl ToListItem for DiffEntry {
+    fn to_list_item(&self) -> ListItem<'static> {
+        let entity = self.entity();
+        match self {
+            DiffEntry::Account(change) => change_list_item(
+                change,
+                entity,
+                change_key(change, |(k, _)| StakeCredentialDisplay(k).to_string()),
+            ),
+            DiffEntry::Pool(change) => {
+                change_list_item(change, entity, change_key(change, |(k, _)| k.to_string()))
+            }
+            DiffEntry::DRep(change) => change_list_item(
+                change,
+                entity,
+                change_key(change, |(k, _)| StakeCredentialDisplay(k).to_string()),
+            ),
+            DiffEntry::Proposal(change) => change_list_item(
+                change,
+                entity,
+                change_key(change, |(k, _)| ComparableProposalIdDisplay(k).to_string()),
+            ),
+            DiffEntry::Utxos {
+                before,
+                after,
+                added,
+                removed,
+            } => ListItem::new(format!(
+                "~ {} {} -> {} (+{} -{})",
+                entity, before.count, after.count, added, removed
+            )),
+        }
+    }
+}
+
+fn change_key<T>(change: &Change<T>, key: impl Fn(&T) -> String) -> String {
+    change
+        .before()
+        .or(change.after())
+        .map(key)
+        .unwrap_or_default()
+}
+
+impl ToListItem for TraceSummary {
+    fn to_list_item(&self) -> ListItem<'static> {
+        let start: DateTime<Utc> = self.start.into();
+        let marker = if self.marked { '●' } else { ' ' };
+        let item = ListItem::new(format!(
+            "{}{} {:>10} {:>5} {}",
+            marker,
+            start.format("%H:%M:%S%.3f"),
+            format_duration(self.duration),
+            self.span_count,
+            self.root_name
+        ));
+        if self.has_error {
+            item.style(Style::default().fg(Color::Red))
+        } else {
+            item
+        }
+    }
+}
+
+/// How long ago the first orphan was received, how many wait for the
+/// parent, and which.
+impl ToListItem for OrphanGroup {
+    fn to_list_item(&self) -> ListItem<'static> {
+        let age = self.first_received.elapsed().unwrap_or_default();
+        ListItem::new(format!(
+            "{:>4}s {:>5} {} (no {})",
+            age.as_secs(),
+            self.span_count,
+            self.name,
+            self.missing_parent
+        ))
+        .style(Style::default().fg(Color::Yellow))
+    }
+}
+
+/// Records attached to a span are marked, as they can be shown on the Otel
+/// page.
+impl ToListItem for LogEntry {
+    fn to_list_item(&self) -> ListItem<'static> {
+        let time = Utc.timestamp_nanos(self.time_unix_nano as i64);
+        let marker = if self.span_id.is_some() { '*' } else { ' ' };
+        ListItem::new(format!(
+            "{} {:<5} {}{}",
+            time.format("%H:%M:%S%.3f"),
+            self.severity,
+            marker,
+            self.body
+        ))
+        .style(Style::default().fg(severity_color(self.severity)))
+    }
+}
+
+impl ToListItem for SpanStats {
     fn to_list_item(&self) -> ListItem<'static> {
-        self.to_string().into()
+        let label = match &self.group {
+            Some(group) => format!("{} [{}]", self.name, group),
+            None => self.name.clone(),
+        };
+        let item = ListItem::new(format!(
+            "{:<40} {:>7} {:>10} {:>10} {:>10} {:>10} {:>6} {}",
+            label,
+            self.count,
+            format_duration(self.p50),
+            format_duration(self.p90),
+            format_duration(self.p99),
+            format_duration(self.max),
+            self.errors,
+            sparkline(&self.histogram)
+        ));
+        if self.errors > 0 {
+            item.style(Style::default().fg(Color::Red))
+        } else {
+            item
+        }
     }
 }
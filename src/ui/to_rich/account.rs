@@ -82,18 +82,31 @@ impl ToRichText for Row {
     }
 }
 
+pub struct DRepDisplay<'a>(pub &'a DRep);
+
+impl<'a> fmt::Display for DRepDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            DRep::Key(h) => write!(f, "Key({})", h),
+            DRep::Script(h) => write!(f, "Script({})", h),
+            DRep::Abstain => write!(f, "Abstain"),
+            DRep::NoConfidence => write!(f, "NoConfidence"),
+        }
+    }
+}
+
 impl ToRichText for DRep {
     fn to_rich_text(&self) -> RichText {
-        let (label, value, color) = match self {
-            DRep::Key(h) => ("DRep", format!("Key({})", h), Color::Green),
-            DRep::Script(h) => ("DRep", format!("Script({})", h), Color::Magenta),
-            DRep::Abstain => ("DRep", "Abstain".to_string(), Color::Yellow),
-            DRep::NoConfidence => ("DRep", "NoConfidence".to_string(), Color::Red),
+        let color = match self {
+            DRep::Key(_) => Color::Green,
+            DRep::Script(_) => Color::Magenta,
+            DRep::Abstain => Color::Yellow,
+            DRep::NoConfidence => Color::Red,
         };
 
         RichText::Lines(labeled(
-            label.to_string(),
-            RichText::Single(Span::raw(value)),
+            "DRep".to_string(),
+            RichText::Single(Span::raw(DRepDisplay(self).to_string())),
             Style::default().fg(color),
         ))
     }
@@ -0,0 +1,322 @@
+use crate::{
+    model::block_body::{BlockTransaction, WitnessCounts},
+    ui::{
+        RichText, ToRichText, labeled, labeled_default, labeled_default_opt_single,
+        labeled_default_single,
+        to_rich::{
+            account::{DRepDisplay, StakeCredentialDisplay},
+            proposal::ProposalIdDisplay,
+            utxo::TransactionInputDisplay,
+        },
+    },
+};
+use amaru_kernel::TransactionInput;
+use pallas_primitives::conway::{Certificate, Vote, Voter};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use std::fmt;
+
+/// A certificate on a single line, its kind followed by what it applies to.
+pub struct CertificateDisplay<'a>(pub &'a Certificate);
+
+impl<'a> fmt::Display for CertificateDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Certificate::StakeRegistration(credential) => write!(
+                f,
+                "Stake registration of {}",
+                StakeCredentialDisplay(credential)
+            ),
+            Certificate::StakeDeregistration(credential) => write!(
+                f,
+                "Stake deregistration of {}",
+                StakeCredentialDisplay(credential)
+            ),
+            Certificate::StakeDelegation(credential, pool) => write!(
+                f,
+                "Stake delegation of {} to {}",
+                StakeCredentialDisplay(credential),
+                pool
+            ),
+            Certificate::PoolRegistration {
+                operator,
+                pledge,
+                cost,
+                ..
+            } => write!(
+                f,
+                "Pool registration of {} (pledge {} lovelace, cost {} lovelace)",
+                operator, pledge, cost
+            ),
+            Certificate::PoolRetirement(pool, epoch) => {
+                write!(f, "Pool retirement of {} at epoch {}", pool, epoch)
+            }
+            Certificate::Reg(credential, deposit) => write!(
+                f,
+                "Stake registration of {} ({} lovelace deposit)",
+                StakeCredentialDisplay(credential),
+                deposit
+            ),
+            Certificate::UnReg(credential, refund) => write!(
+                f,
+                "Stake deregistration of {} ({} lovelace refund)",
+                StakeCredentialDisplay(credential),
+                refund
+            ),
+            Certificate::VoteDeleg(credential, drep) => write!(
+                f,
+                "Vote delegation of {} to {}",
+                StakeCredentialDisplay(credential),
+                DRepDisplay(drep)
+            ),
+            Certificate::StakeVoteDeleg(credential, pool, drep) => write!(
+                f,
+                "Stake and vote delegation of {} to {} and {}",
+                StakeCredentialDisplay(credential),
+                pool,
+                DRepDisplay(drep)
+            ),
+            Certificate::StakeRegDeleg(credential, pool, deposit) => write!(
+                f,
+                "Stake registration and delegation of {} to {} ({} lovelace deposit)",
+                StakeCredentialDisplay(credential),
+                pool,
+                deposit
+            ),
+            Certificate::VoteRegDeleg(credential, drep, deposit) => write!(
+                f,
+                "Stake registration and vote delegation of {} to {} ({} lovelace deposit)",
+                StakeCredentialDisplay(credential),
+                DRepDisplay(drep),
+                deposit
+            ),
+            Certificate::StakeVoteRegDeleg(credential, pool, drep, deposit) => write!(
+                f,
+                "Stake registration and delegation of {} to {} and {} ({} lovelace deposit)",
+                StakeCredentialDisplay(credential),
+                pool,
+                DRepDisplay(drep),
+                deposit
+            ),
+            Certificate::AuthCommitteeHot(cold, hot) => write!(
+                f,
+                "Committee hot key authorization of {} for {}",
+                StakeCredentialDisplay(hot),
+                StakeCredentialDisplay(cold)
+            ),
+            Certificate::ResignCommitteeCold(cold, _) => write!(
+                f,
+                "Committee resignation of {}",
+                StakeCredentialDisplay(cold)
+            ),
+            Certificate::RegDRepCert(credential, deposit, _) => write!(
+                f,
+                "DRep registration of {} ({} lovelace deposit)",
+                StakeCredentialDisplay(credential),
+                deposit
+            ),
+            Certificate::UnRegDRepCert(credential, refund) => write!(
+                f,
+                "DRep retirement of {} ({} lovelace refund)",
+                StakeCredentialDisplay(credential),
+                refund
+            ),
+            Certificate::UpdateDRepCert(credential, _) => {
+                write!(f, "DRep update of {}", StakeCredentialDisplay(credential))
+            }
+        }
+    }
+}
+
+pub struct VoterDisplay<'a>(pub &'a Voter);
+
+impl<'a> fmt::Display for VoterDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Voter::ConstitutionalCommitteeKey(h) => write!(f, "Committee Key({})", h),
+            Voter::ConstitutionalCommitteeScript(h) => write!(f, "Committee Script({})", h),
+            Voter::DRepKey(h) => write!(f, "DRep Key({})", h),
+            Voter::DRepScript(h) => write!(f, "DRep Script({})", h),
+            Voter::StakePoolKey(h) => write!(f, "Pool {}", h),
+        }
+    }
+}
+
+pub struct VoteDisplay<'a>(pub &'a Vote);
+
+impl<'a> fmt::Display for VoteDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self.0 {
+            Vote::Yes => "Yes",
+            Vote::No => "No",
+            Vote::Abstain => "Abstain",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+fn lines_or_none(lines: Vec<Line<'static>>) -> RichText {
+    if lines.is_empty() {
+        RichText::Single(Span::raw("None"))
+    } else {
+        RichText::Lines(lines)
+    }
+}
+
+fn inputs_rich_text(inputs: &[TransactionInput]) -> RichText {
+    lines_or_none(
+        inputs
+            .iter()
+            .map(|i| Line::from(TransactionInputDisplay(i).to_string()))
+            .collect(),
+    )
+}
+
+impl ToRichText for BlockTransaction {
+    fn to_rich_text(&self) -> RichText {
+        let mut lines = Vec::new();
+        lines.extend(labeled_default_single("Id", self.id));
+        lines.extend(labeled_default_single("Index", self.index));
+        let (validity, style) = if self.is_valid {
+            ("Valid", Style::default())
+        } else {
+            ("Invalid", Style::default().fg(Color::Red))
+        };
+        lines.extend(labeled(
+            "Phase-2".to_string(),
+            RichText::Single(Span::raw(validity)),
+            style,
+        ));
+        lines.extend(labeled_default_single("Fee", self.fee));
+        lines.extend(labeled_default_opt_single(
+            "Validity Start",
+            self.validity_start,
+        ));
+        lines.extend(labeled_default_opt_single("TTL", self.ttl));
+        lines.extend(labeled(
+            "Inputs".to_string(),
+            inputs_rich_text(&self.inputs),
+            Style::default(),
+        ));
+        lines.extend(labeled(
+            "Reference Inputs".to_string(),
+            inputs_rich_text(&self.reference_inputs),
+            Style::default(),
+        ));
+        lines.extend(labeled(
+            "Collateral".to_string(),
+            inputs_rich_text(&self.collateral),
+            Style::default(),
+        ));
+
+        let outputs = self
+            .outputs
+            .iter()
+            .enumerate()
+            .flat_map(|(i, output)| labeled_default(&format!("#{}", i), output))
+            .collect();
+        lines.extend(labeled(
+            "Outputs".to_string(),
+            lines_or_none(outputs),
+            Style::default(),
+        ));
+
+        lines.extend(labeled(
+            "Certificates".to_string(),
+            lines_or_none(
+                self.certificates
+                    .iter()
+                    .map(|c| Line::from(CertificateDisplay(c).to_string()))
+                    .collect(),
+            ),
+            Style::default(),
+        ));
+        lines.extend(labeled(
+            "Withdrawals".to_string(),
+            lines_or_none(
+                self.withdrawals
+                    .iter()
+                    .map(|(account, coin)| Line::from(format!("{}: {} lovelace", account, coin)))
+                    .collect(),
+            ),
+            Style::default(),
+        ));
+        lines.extend(labeled(
+            "Mint".to_string(),
+            lines_or_none(
+                self.mint
+                    .iter()
+                    .map(|(policy, name, amount)| {
+                        Line::from(format!("{}.{}: {}", policy, name, amount))
+                    })
+                    .collect(),
+            ),
+            Style::default(),
+        ));
+        lines.extend(labeled(
+            "Votes".to_string(),
+            lines_or_none(
+                self.votes
+                    .iter()
+                    .map(|(voter, proposal, vote)| {
+                        Line::from(format!(
+                            "{} on {}: {}",
+                            VoterDisplay(voter),
+                            ProposalIdDisplay(proposal),
+                            VoteDisplay(vote)
+                        ))
+                    })
+                    .collect(),
+            ),
+            Style::default(),
+        ));
+
+        let proposals = self
+            .proposals
+            .iter()
+            .enumerate()
+            .flat_map(|(i, proposal)| labeled_default(&format!("#{}", i), proposal))
+            .collect();
+        lines.extend(labeled(
+            "Proposals".to_string(),
+            lines_or_none(proposals),
+            Style::default(),
+        ));
+
+        lines.extend(labeled_default("Witnesses", &self.witnesses));
+        lines.extend(labeled_default_single(
+            "Auxiliary Data",
+            if self.has_auxiliary_data { "Yes" } else { "No" },
+        ));
+        RichText::Lines(lines)
+    }
+}
+
+impl ToRichText for WitnessCounts {
+    fn to_rich_text(&self) -> RichText {
+        let mut lines = Vec::new();
+        lines.extend(labeled_default_single("VKeys", self.vkeys));
+        lines.extend(labeled_default_single("Bootstraps", self.bootstraps));
+        lines.extend(labeled_default_single(
+            "Native Scripts",
+            self.native_scripts,
+        ));
+        lines.extend(labeled_default_single(
+            "Plutus V1 Scripts",
+            self.plutus_v1_scripts,
+        ));
+        lines.extend(labeled_default_single(
+            "Plutus V2 Scripts",
+            self.plutus_v2_scripts,
+        ));
+        lines.extend(labeled_default_single(
+            "Plutus V3 Scripts",
+            self.plutus_v3_scripts,
+        ));
+        lines.extend(labeled_default_single("Plutus Data", self.plutus_data));
+        lines.extend(labeled_default_single("Redeemers", self.redeemers));
+        RichText::Lines(lines)
+    }
+}
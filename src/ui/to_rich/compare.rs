@@ -0,0 +1,60 @@
+use crate::{
+    otel::{
+        compare::{AlignedSpan, AttributeChange, DurationDelta},
+        span_ext::SpanExt,
+    },
+    ui::{RichText, ToRichText, format_duration, labeled_default, labeled_default_single},
+};
+use opentelemetry_proto::tonic::trace::v1::Span;
+use ratatui::text::Line;
+use std::sync::Arc;
+
+struct AttributeChanges(Vec<AttributeChange>);
+
+impl ToRichText for AlignedSpan {
+    fn to_rich_text(&self) -> RichText {
+        let mut lines = Vec::new();
+        let duration = |span: &Option<Arc<Span>>| {
+            span.as_ref().map_or("Absent".to_string(), |span| {
+                format_duration(span.duration())
+            })
+        };
+
+        lines.extend(labeled_default_single("Name", &self.name));
+        lines.extend(labeled_default_single("Baseline", duration(&self.baseline)));
+        lines.extend(labeled_default_single("Other", duration(&self.other)));
+        if let Some(delta) = self.delta() {
+            let delta = match delta {
+                DurationDelta::Longer(delta) => format!("+{}", format_duration(delta)),
+                DurationDelta::Shorter(delta) => format!("-{}", format_duration(delta)),
+            };
+            lines.extend(labeled_default_single("Delta", delta));
+        }
+
+        let changes = self.attribute_changes();
+        if !changes.is_empty() {
+            lines.extend(labeled_default(
+                "Changed Attributes",
+                &AttributeChanges(changes),
+            ));
+        }
+
+        lines.into()
+    }
+}
+
+impl ToRichText for AttributeChanges {
+    fn to_rich_text(&self) -> RichText {
+        self.0
+            .iter()
+            .map(|change| {
+                Line::from(format!(
+                    "  - {}: {} → {}",
+                    change.key,
+                    change.baseline.as_deref().unwrap_or("-"),
+                    change.other.as_deref().unwrap_or("-")
+                ))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{
+    model::ledger_diff::{Change, DiffEntry, UtxoStats},
+    ui::{RichText, ToRichText, labeled_default, labeled_default_single},
+};
+use ratatui::text::Span;
+
+/// One side of a `DiffEntry`, so that both snapshots can be rendered next to
+/// each other with the entry's own `ToRichText`.
+pub enum DiffSide<'a> {
+    Before(&'a DiffEntry),
+    After(&'a DiffEntry),
+}
+
+fn side_rich_text<T: ToRichText>(change: &Change<T>, before: bool) -> RichText {
+    let item = if before {
+        change.before()
+    } else {
+        change.after()
+    };
+    item.map(ToRichText::to_rich_text)
+        .unwrap_or(RichText::Single(Span::raw("Absent")))
+}
+
+fn utxo_stats_rich_text(stats: &UtxoStats) -> RichText {
+    let mut lines = Vec::new();
+    lines.extend(labeled_default_single("UTxOs", stats.count));
+    lines.extend(labeled_default_single("Lovelace", stats.lovelace));
+    RichText::Lines(lines)
+}
+
+impl ToRichText for DiffSide<'_> {
+    fn to_rich_text(&self) -> RichText {
+        let (entry, before) = match self {
+            DiffSide::Before(entry) => (entry, true),
+            DiffSide::After(entry) => (entry, false),
+        };
+        match entry {
+            DiffEntry::Account(change) => side_rich_text(change, before),
+            DiffEntry::Pool(change) => side_rich_text(change, before),
+            DiffEntry::DRep(change) => side_rich_text(change, before),
+            DiffEntry::Proposal(change) => side_rich_text(change, before),
+            DiffEntry::Utxos {
+                before: before_stats,
+                after: after_stats,
+                added,
+                removed,
+            } => {
+                if before {
+                    let mut lines = utxo_stats_rich_text(before_stats).unwrap_lines();
+                    lines.extend(labeled_default_single("Removed", removed));
+                    RichText::Lines(lines)
+                } else {
+                    let mut lines = utxo_stats_rich_text(after_stats).unwrap_lines();
+                    lines.extend(labeled_default_single("Added", added));
+                    RichText::Lines(lines)
+                }
+            }
+        }
+    }
+}
+
+impl ToRichText for DiffEntry {
+    fn to_rich_text(&self) -> RichText {
+        let mut lines = Vec::new();
+        lines.extend(labeled_default("Before", &DiffSide::Before(self)));
+        lines.extend(labeled_default("After", &DiffSide::After(self)));
+        RichText::Lines(lines)
+    }
+}
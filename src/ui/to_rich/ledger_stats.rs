@@ -0,0 +1,88 @@
+use crate::{
+    model::ledger_stats::{LedgerStats, ScanPhase},
+    ui::{
+        RichText, ToRichText, labeled, labeled_default_opt_single, labeled_default_single,
+        to_rich::account::DRepDisplay,
+    },
+};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+fn distribution<K: ToString>(stake: &[(K, u64)], done: bool) -> RichText {
+    if !done {
+        return RichText::Single(Span::raw("Available once UTxOs are scanned"));
+    }
+    if stake.is_empty() {
+        return RichText::Single(Span::raw("None"));
+    }
+    RichText::Lines(
+        stake
+            .iter()
+            .map(|(key, lovelace)| {
+                Line::from(format!("{}: {} lovelace", key.to_string(), lovelace))
+            })
+            .collect(),
+    )
+}
+
+impl ToRichText for LedgerStats {
+    fn to_rich_text(&self) -> RichText {
+        let done = self.phase == ScanPhase::Done;
+        let mut lines = Vec::new();
+        let (progress, style) = if done {
+            ("Done".to_string(), Style::default())
+        } else {
+            (
+                format!("Scanning {} ({} so far)", self.phase, self.scanned),
+                Style::default().fg(Color::Yellow),
+            )
+        };
+        lines.extend(labeled(
+            "Progress".to_string(),
+            RichText::Single(Span::raw(progress)),
+            style,
+        ));
+
+        lines.extend(labeled_default_single("UTxOs", self.utxos));
+        lines.extend(labeled_default_single("Lovelace in UTxOs", self.lovelace));
+        lines.extend(labeled_default_single("Native Assets", self.native_assets));
+        lines.extend(labeled_default_single("Accounts", self.accounts));
+        lines.extend(labeled_default_single(
+            "Delegated Accounts",
+            self.delegated_accounts,
+        ));
+        lines.extend(labeled_default_single("Pools", self.pools));
+        lines.extend(labeled_default_single(
+            "Pending Retirements",
+            self.pools_retiring,
+        ));
+        lines.extend(labeled_default_single("DReps", self.dreps));
+        lines.extend(labeled_default_single("Proposals", self.proposals));
+        lines.extend(labeled_default_opt_single(
+            "Active Proposals",
+            self.active_proposals,
+        ));
+        lines.extend(labeled_default_opt_single(
+            "Expired Proposals",
+            self.active_proposals.map(|active| self.proposals - active),
+        ));
+        lines.extend(labeled(
+            "Top Pools by Stake".to_string(),
+            distribution(&self.top_pools, done),
+            Style::default(),
+        ));
+        let top_dreps: Vec<_> = self
+            .top_dreps
+            .iter()
+            .map(|(drep, stake)| (DRepDisplay(drep), *stake))
+            .collect();
+        lines.extend(labeled(
+            "Top DReps by Voting Power".to_string(),
+            distribution(&top_dreps, done),
+            Style::default(),
+        ));
+        RichText::Lines(lines)
+    }
+}
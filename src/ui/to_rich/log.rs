@@ -0,0 +1,45 @@
+use crate::{
+    logs::model::{LogEntry, Severity},
+    ui::{
+        RichText, ToRichText, labeled_default, labeled_default_opt_single, labeled_default_single,
+        to_rich::span::Attributes,
+    },
+};
+use chrono::{TimeZone, Utc};
+use ratatui::style::Color;
+
+impl ToRichText for LogEntry {
+    fn to_rich_text(&self) -> RichText {
+        let mut lines = Vec::new();
+
+        let time = Utc.timestamp_nanos(self.time_unix_nano as i64);
+        lines.extend(labeled_default_single(
+            "Time",
+            time.format("%Y-%m-%d %H:%M:%S%.6f").to_string(),
+        ));
+        lines.extend(labeled_default_single("Severity", self.severity));
+        if !self.severity_text.is_empty() {
+            lines.extend(labeled_default_single("Level", &self.severity_text));
+        }
+        lines.extend(labeled_default_single("Scope", &self.scope));
+        lines.extend(labeled_default_opt_single("Trace Id", self.trace_id));
+        lines.extend(labeled_default_opt_single("Span Id", self.span_id));
+        lines.extend(labeled_default_single("Body", &self.body));
+
+        if !self.attributes.is_empty() {
+            lines.extend(labeled_default("Attributes", &Attributes(&self.attributes)));
+        }
+
+        lines.into()
+    }
+}
+
+pub fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Unspecified | Severity::Trace => Color::DarkGray,
+        Severity::Debug => Color::Gray,
+        Severity::Info => Color::White,
+        Severity::Warn => Color::Yellow,
+        Severity::Error | Severity::Fatal => Color::Red,
+    }
+}
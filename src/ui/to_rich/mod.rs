@@ -2,13 +2,19 @@ use crate::ui::{RichText, ToRichText};
 use ratatui::text::{Line, Span};
 
 pub mod account;
+pub mod block;
 pub mod block_issuer;
+pub mod compare;
+pub mod diff;
 pub mod drep;
 pub mod header;
+pub mod ledger_stats;
+pub mod log;
 pub mod nonces;
 pub mod pool;
 pub mod proposal;
 pub mod span;
+pub mod span_stats;
 pub mod utxo;
 
 impl From<Vec<Line<'static>>> for RichText {
@@ -1,5 +1,5 @@
 use crate::{
-    otel::span_ext::SpanExt,
+    otel::{attribute::KnownAttribute, span_ext::SpanExt},
     ui::{
         RichText, ToRichText, format_duration, labeled_default, labeled_default_opt_single,
         labeled_default_single,
@@ -10,42 +10,84 @@ use opentelemetry_proto::tonic::{
     common::v1::{AnyValue, KeyValue, any_value::Value},
     trace::v1::{Span, span::Event},
 };
-use ratatui::text::Line;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span as TextSpan},
+};
+use std::time::Duration;
 
-struct Attributes<'a>(&'a [KeyValue]);
+pub struct Attributes<'a>(pub &'a [KeyValue]);
 struct Events<'a>(&'a [Event]);
 
+/// Attributes, one of which may be selected.
+struct SelectableAttributes<'a> {
+    attributes: &'a [KeyValue],
+    selected: Option<usize>,
+}
+
+/// A span of the trace graph, with the time it spent outside of its
+/// children and the index of the selected attribute, if any.
+pub struct SpanDetails<'a> {
+    pub span: &'a Span,
+    pub self_time: Option<Duration>,
+    pub selected_attribute: Option<usize>,
+}
+
 impl ToRichText for Span {
     fn to_rich_text(&self) -> RichText {
+        SpanDetails {
+            span: self,
+            self_time: None,
+            selected_attribute: None,
+        }
+        .to_rich_text()
+    }
+}
+
+impl<'a> ToRichText for SpanDetails<'a> {
+    fn to_rich_text(&self) -> RichText {
+        let span = self.span;
         let mut lines = Vec::new();
 
-        lines.extend(labeled_default_single("Name", &self.name));
-        lines.extend(labeled_default_single("Span Id", self.span_id()));
-        lines.extend(labeled_default_single("Trace Id", self.trace_id()));
-        lines.extend(labeled_default_opt_single("Parent Id", self.parent_id()));
-        lines.extend(labeled_default_single("Kind", format_span_kind(self.kind)));
+        lines.extend(labeled_default_single("Name", &span.name));
+        lines.extend(labeled_default_single("Span Id", span.span_id()));
+        lines.extend(labeled_default_single("Trace Id", span.trace_id()));
+        lines.extend(labeled_default_opt_single("Parent Id", span.parent_id()));
+        lines.extend(labeled_default_single("Kind", format_span_kind(span.kind)));
         lines.extend(labeled_default_opt_single(
             "Status",
-            self.status
+            span.status
                 .as_ref()
                 .map(|s| format!("{} - {}", format_status_code(s.code), s.message)),
         ));
-        let start_time = Utc.timestamp_nanos(self.start_time_unix_nano as i64);
+        let start_time = Utc.timestamp_nanos(span.start_time_unix_nano as i64);
         lines.extend(labeled_default_single(
             "Start",
             start_time.format("%H:%M:%S%.6f").to_string(),
         ));
         lines.extend(labeled_default_single(
             "Duration",
-            format_duration(self.duration()),
+            format_duration(span.duration()),
         ));
+        if let Some(self_time) = self.self_time {
+            lines.extend(labeled_default_single(
+                "Self Time",
+                format_duration(self_time),
+            ));
+        }
 
-        if !self.attributes.is_empty() {
-            lines.extend(labeled_default("Attributes", &Attributes(&self.attributes)));
+        if !span.attributes.is_empty() {
+            lines.extend(labeled_default(
+                "Attributes",
+                &SelectableAttributes {
+                    attributes: &span.attributes,
+                    selected: self.selected_attribute,
+                },
+            ));
         }
 
-        if !self.events.is_empty() {
-            lines.extend(labeled_default("Events", &Events(&self.events)));
+        if !span.events.is_empty() {
+            lines.extend(labeled_default("Events", &Events(&span.events)));
         }
 
         lines.into()
@@ -54,14 +96,44 @@ impl ToRichText for Span {
 
 impl<'a> ToRichText for Attributes<'a> {
     fn to_rich_text(&self) -> RichText {
-        self.0
+        SelectableAttributes {
+            attributes: self.0,
+            selected: None,
+        }
+        .to_rich_text()
+    }
+}
+
+/// Known attributes have their value highlighted, and the selected one says
+/// what can be done with it.
+impl<'a> ToRichText for SelectableAttributes<'a> {
+    fn to_rich_text(&self) -> RichText {
+        self.attributes
             .iter()
-            .map(|attr| {
-                Line::from(format!(
-                    "  - {}: {}",
-                    attr.key,
-                    format_any_value(&attr.value)
-                ))
+            .enumerate()
+            .map(|(index, attr)| {
+                let value = format_any_value(&attr.value);
+                let known = KnownAttribute::parse(&attr.key, &value);
+                let value_style = match known {
+                    Some(_) => Style::default().fg(Color::Cyan),
+                    None => Style::default(),
+                };
+                let mut spans = vec![
+                    TextSpan::raw(format!("  - {}: ", attr.key)),
+                    TextSpan::styled(value, value_style),
+                ];
+                if self.selected != Some(index) {
+                    return Line::from(spans);
+                }
+                let hint = match known {
+                    Some(known) => format!("  Enter: {}, y: copy", known.target()),
+                    None => "  y: copy".to_string(),
+                };
+                spans.push(TextSpan::styled(
+                    hint,
+                    Style::default().add_modifier(Modifier::ITALIC),
+                ));
+                Line::from(spans).patch_style(Style::default().bg(Color::DarkGray))
             })
             .collect()
     }
@@ -90,7 +162,7 @@ impl ToRichText for Event {
     }
 }
 
-fn format_any_value(value: &Option<AnyValue>) -> String {
+pub fn format_any_value(value: &Option<AnyValue>) -> String {
     let Some(any_value) = value else {
         return String::from("<None>");
     };
@@ -0,0 +1,51 @@
+use crate::{
+    otel::aggregate::SpanStats,
+    ui::{RichText, ToRichText, format_duration, labeled_default, labeled_default_single},
+};
+use ratatui::text::Line;
+
+struct Histogram<'a>(&'a SpanStats);
+
+impl ToRichText for SpanStats {
+    fn to_rich_text(&self) -> RichText {
+        let mut lines = Vec::new();
+
+        lines.extend(labeled_default_single("Name", &self.name));
+        if let Some(group) = &self.group {
+            lines.extend(labeled_default_single("Group", group));
+        }
+        lines.extend(labeled_default_single("Count", self.count));
+        lines.extend(labeled_default_single("Errors", self.errors));
+        lines.extend(labeled_default_single("Min", format_duration(self.min)));
+        lines.extend(labeled_default_single("p50", format_duration(self.p50)));
+        lines.extend(labeled_default_single("p90", format_duration(self.p90)));
+        lines.extend(labeled_default_single("p99", format_duration(self.p99)));
+        lines.extend(labeled_default_single("Max", format_duration(self.max)));
+        lines.extend(labeled_default("Histogram", &Histogram(self)));
+        lines.extend(labeled_default_single(
+            "Slowest",
+            format!("trace {} (Enter to show)", self.slowest.0),
+        ));
+
+        lines.into()
+    }
+}
+
+impl<'a> ToRichText for Histogram<'a> {
+    fn to_rich_text(&self) -> RichText {
+        const WIDTH: usize = 20;
+        let max = self.0.histogram.iter().copied().max().unwrap_or_default();
+        let mut lines = vec![Line::from("")];
+        for (count, bound) in self.0.histogram.iter().zip(self.0.bucket_bounds()) {
+            let bar = "█".repeat((count * WIDTH).div_ceil(max.max(1)));
+            lines.push(Line::from(format!(
+                "  ≤ {:>10} {:<width$} {}",
+                format_duration(bound),
+                bar,
+                count,
+                width = WIDTH
+            )));
+        }
+        lines.into()
+    }
+}
@@ -30,6 +30,14 @@ impl ListViewState {
         self.title
     }
 
+    pub fn set_title(&mut self, title: &'static str) {
+        self.title = title;
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
     pub fn max_visible_index(&self) -> usize {
         self.offset + self.height
     }
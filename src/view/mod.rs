@@ -2,6 +2,7 @@ pub mod empty_list;
 pub mod flame_graph;
 pub mod item_details;
 pub mod list;
+pub mod receiver_status;
 pub mod search;
 pub mod span;
 pub mod span_bar;
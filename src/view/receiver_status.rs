@@ -0,0 +1,45 @@
+use crate::receiver::{ReceiverHealth, ReceiverState};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// Splits the area of a page into its content and the status line below it.
+pub fn split_status(area: Rect) -> [Rect; 2] {
+    Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area)
+}
+
+/// Draws a one line summary of each receiver: its state, and how long ago it
+/// last received a batch.
+pub fn draw_receiver_status(frame: &mut Frame<'_>, area: Rect, health: &[ReceiverHealth]) {
+    let mut spans = Vec::new();
+    for status in health.iter().map(ReceiverHealth::status) {
+        if !spans.is_empty() {
+            spans.push(Span::raw(" | "));
+        }
+        let color = match &status.state {
+            ReceiverState::Listening {
+                requested: None, ..
+            }
+            | ReceiverState::Replaying(_) => Color::Green,
+            ReceiverState::Listening { .. } | ReceiverState::Starting => Color::Yellow,
+            ReceiverState::Failed(_) => Color::Red,
+            ReceiverState::Disabled => Color::Gray,
+        };
+        spans.push(Span::styled(
+            format!("{}: {}", status.name, status.state),
+            Style::default().fg(color),
+        ));
+        if let Some(elapsed) = status.since_last_batch() {
+            spans.push(Span::raw(format!(
+                ", last batch {}s ago",
+                elapsed.as_secs()
+            )));
+        } else if matches!(status.state, ReceiverState::Listening { .. }) {
+            spans.push(Span::raw(", nothing received yet"));
+        }
+    }
+    frame.render_widget(Line::from(spans), area);
+}
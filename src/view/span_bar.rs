@@ -1,29 +1,38 @@
 use crate::{
-    model::otel_view::OtelViewState,
     otel::{TreeBounds, graph::TraceGraph, id::SpanId, span_ext::SpanExt},
     ui::format_duration,
 };
 use anyhow::{Result, anyhow};
 use opentelemetry_proto::tonic::trace::v1::Span as OtelSpan;
 use ratatui::prelude::*;
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Marks the time of an event on a span bar.
+const EVENT_TICK: char = '◆';
 
 /// Holds the context needed to render a Span bar
 pub struct SpanBarRenderer<'a> {
     graph: &'a TraceGraph,
-    state: &'a OtelViewState,
+    focused_span: Option<SpanId>,
     tree_bounds: &'a TreeBounds,
     max_render_width: usize,
     scale: f64,
+    /// The spans to highlight, when showing the critical path.
+    critical_path: Option<HashSet<SpanId>>,
 }
 
 impl<'a> SpanBarRenderer<'a> {
     /// Creates a new SpanBarRenderer.
     pub fn new(
         graph: &'a TraceGraph,
-        state: &'a OtelViewState,
+        focused_span: Option<SpanId>,
         tree_bounds: &'a TreeBounds,
         max_render_width: usize,
+        critical_path: Option<HashSet<SpanId>>,
     ) -> Result<Self> {
         let tree_duration = tree_bounds.duration();
         if tree_duration.is_zero() {
@@ -33,10 +42,11 @@ impl<'a> SpanBarRenderer<'a> {
         }
         Ok(Self {
             graph,
-            state,
+            focused_span,
             tree_bounds,
             max_render_width,
             scale: (max_render_width as f64) / tree_duration.as_micros() as f64,
+            critical_path,
         })
     }
 
@@ -64,26 +74,75 @@ impl<'a> SpanBarRenderer<'a> {
         }
 
         let bar_text = get_bar_text(span, bar_width)?;
-        let is_focused = self
-            .state
-            .focused_span
+        let is_focused = self.focused_span == Some(*span_id);
+
+        let on_critical_path = self
+            .critical_path
             .as_ref()
-            .is_some_and(|s| s.span_id() == *span_id);
+            .map(|path| path.contains(span_id));
 
         let bar_style = get_bar_style(
             is_focused,
             is_ancestor,
+            span.is_error(),
+            on_critical_path,
             span.duration(),
             self.tree_bounds.duration(),
         )?;
+        let tick_style = bar_style.fg(Color::Magenta).add_modifier(Modifier::BOLD);
 
-        Ok(Line::from(vec![
-            Span::raw(" ".repeat(bar_offset)),
-            Span::styled(bar_text, bar_style),
-        ]))
+        let mut spans = vec![Span::raw(" ".repeat(bar_offset))];
+        spans.extend(get_bar_spans(
+            &bar_text,
+            &get_event_ticks(span, bar_width),
+            bar_style,
+            tick_style,
+        ));
+        Ok(Line::from(spans))
     }
 }
 
+/// Gets the positions of the span events within a bar of `bar_width`
+/// characters.
+fn get_event_ticks(span: &OtelSpan, bar_width: usize) -> BTreeSet<usize> {
+    let start = span.start_time_unix_nano;
+    let duration = span.end_time_unix_nano.saturating_sub(start).max(1);
+    span.events
+        .iter()
+        .map(|event| {
+            let offset = event.time_unix_nano.clamp(start, start + duration) - start;
+            let position = (offset as f64 / duration as f64 * bar_width as f64) as usize;
+            position.min(bar_width - 1)
+        })
+        .collect()
+}
+
+/// Splits the text of a bar in runs, the characters at `ticks` being replaced
+/// by an event tick.
+fn get_bar_spans(
+    bar_text: &str,
+    ticks: &BTreeSet<usize>,
+    bar_style: Style,
+    tick_style: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    for (i, c) in bar_text.chars().enumerate() {
+        if ticks.contains(&i) {
+            if !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), bar_style));
+            }
+            spans.push(Span::styled(EVENT_TICK.to_string(), tick_style));
+        } else {
+            run.push(c);
+        }
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, bar_style));
+    }
+    spans
+}
+
 /// Calculates the screen x-offset and character width of a span bar.
 fn get_span_layout(
     span: &OtelSpan,
@@ -101,10 +160,14 @@ fn get_span_layout(
     Ok((offset_chars, num_chars))
 }
 
-/// Gets the style for a span bar based on its state.
+/// Gets the style for a span bar based on its state. Failed spans stand out
+/// whatever their duration. `on_critical_path` is `None` when the critical
+/// path isn't shown, otherwise the spans off it are dimmed.
 fn get_bar_style(
     is_focused: bool,
     is_ancestor: bool,
+    is_error: bool,
+    on_critical_path: Option<bool>,
     span_duration: Duration,
     tree_duration: Duration,
 ) -> Result<Style> {
@@ -112,10 +175,30 @@ fn get_bar_style(
         return Ok(Style::default().fg(Color::Black).bg(Color::LightCyan));
     }
     if is_ancestor {
-        return Ok(Style::default().bg(Color::DarkGray).fg(Color::Gray));
+        let fg = if is_error {
+            Color::LightRed
+        } else {
+            Color::Gray
+        };
+        return Ok(Style::default().bg(Color::DarkGray).fg(fg));
+    }
+    if is_error {
+        return Ok(Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD));
+    }
+    match on_critical_path {
+        Some(true) => Ok(Style::default()
+            .fg(Color::Black)
+            .bg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD)),
+        Some(false) => Ok(Style::default().fg(Color::Gray).bg(Color::Rgb(50, 50, 50))),
+        None => {
+            let bg_color = get_bar_color(tree_duration, span_duration)?;
+            Ok(Style::default().fg(Color::White).bg(bg_color))
+        }
     }
-    let bg_color = get_bar_color(tree_duration, span_duration)?;
-    Ok(Style::default().fg(Color::White).bg(bg_color))
 }
 
 /// Gets the color for the span bar based on its duration and the total trace
@@ -150,3 +233,79 @@ fn get_bar_text(span: &Arc<OtelSpan>, bar_width: usize) -> Result<String> {
     let truncated_label: String = label.chars().take(bar_width.saturating_sub(1)).collect();
     Ok(format!("{}…", truncated_label))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::test_span::span;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_get_event_ticks() {
+        let span = span(1)
+            .start_ms(100)
+            .end_ms(200)
+            .event_ms(50)
+            .event_ms(100)
+            .event_ms(150)
+            .event_ms(200)
+            .event_ms(250)
+            .build();
+        assert_eq!(get_event_ticks(&span, 10), BTreeSet::from([0, 5, 9]));
+    }
+
+    #[test]
+    fn test_get_bar_spans() {
+        let bar_style = Style::default().bg(Color::Blue);
+        let tick_style = Style::default().fg(Color::Magenta);
+        let tick = || Span::styled(EVENT_TICK.to_string(), tick_style);
+        assert_eq!(
+            get_bar_spans("abcdef", &BTreeSet::from([0, 3]), bar_style, tick_style),
+            vec![
+                tick(),
+                Span::styled("bc", bar_style),
+                tick(),
+                Span::styled("ef", bar_style),
+            ]
+        );
+        assert_eq!(
+            get_bar_spans("abc", &BTreeSet::new(), bar_style, tick_style),
+            vec![Span::styled("abc", bar_style)]
+        );
+    }
+
+    #[test]
+    fn test_get_bar_style() -> Result<()> {
+        let error = Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD);
+        let span_duration = Duration::from_millis(10);
+        let tree_duration = Duration::from_millis(100);
+        for on_critical_path in [None, Some(true), Some(false)] {
+            assert_eq!(
+                get_bar_style(
+                    false,
+                    false,
+                    true,
+                    on_critical_path,
+                    span_duration,
+                    tree_duration
+                )?,
+                error
+            );
+        }
+        assert_eq!(
+            get_bar_style(
+                false,
+                false,
+                false,
+                Some(false),
+                span_duration,
+                tree_duration
+            )?,
+            Style::default().fg(Color::Gray).bg(Color::Rgb(50, 50, 50))
+        );
+        Ok(())
+    }
+}
@@ -33,6 +33,10 @@ impl<T: Clone + PartialEq + ToListItem> DynamicListViewModel<T> {
         self.view.select(new_selected_index, len);
     }
 
+    pub fn set_title(&mut self, title: &'static str) {
+        self.view.set_title(title);
+    }
+
     pub fn set_height(&mut self, height: usize) {
         self.view.set_height(height);
     }
@@ -41,6 +45,21 @@ impl<T: Clone + PartialEq + ToListItem> DynamicListViewModel<T> {
         self.items.get(self.view.selected_index())
     }
 
+    pub fn select_index(&mut self, index: usize) {
+        self.view.select(index, self.items.len());
+    }
+
+    /// Selects `item`, returning false when it isn't in the list.
+    pub fn select_item(&mut self, item: &T) -> bool {
+        match self.items.iter().position(|i| i == item) {
+            Some(index) => {
+                self.select_index(index);
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn select_index_by_row(&mut self, relative_row: usize) {
         self.view
             .select_index_by_row(relative_row, self.items.len());
@@ -64,6 +83,10 @@ impl<T: Clone + PartialEq + ToListItem> DynamicListViewModel<T> {
         self.view.retreat_window();
     }
 
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
     pub fn draw(&self, f: &mut Frame, area: Rect, is_focused: bool) {
         self.view.draw(f, area, &self.items, is_focused);
     }